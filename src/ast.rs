@@ -119,6 +119,7 @@ pub struct Directive {
 pub enum OperationType {
     Query,
     Mutation,
+    Subscription,
 }
 
 #[derive(Clone, PartialEq, Debug)]
@@ -138,12 +139,152 @@ pub struct Fragment {
     pub selection_set: Vec<Selection>,
 }
 
+/// A `schema` definition or extension, mapping operation types to root types
 #[derive(Clone, PartialEq, Debug)]
+#[allow(missing_docs)]
+pub struct SchemaDefinition {
+    pub description: Option<Spanning<String>>,
+    pub directives: Option<Vec<Spanning<Directive>>>,
+    pub operation_types: Vec<(Spanning<OperationType>, Spanning<String>)>,
+}
+
+/// An argument or input object field definition
+#[derive(Clone, PartialEq, Debug)]
+#[allow(missing_docs)]
+pub struct InputValueDefinition {
+    pub description: Option<Spanning<String>>,
+    pub name: Spanning<String>,
+    pub value_type: Spanning<Type>,
+    pub default_value: Option<Spanning<InputValue>>,
+    pub directives: Option<Vec<Spanning<Directive>>>,
+}
+
+/// A field definition on an object or interface type
+#[derive(Clone, PartialEq, Debug)]
+#[allow(missing_docs)]
+pub struct FieldDefinition {
+    pub description: Option<Spanning<String>>,
+    pub name: Spanning<String>,
+    pub arguments: Option<Vec<Spanning<InputValueDefinition>>>,
+    pub field_type: Spanning<Type>,
+    pub directives: Option<Vec<Spanning<Directive>>>,
+}
+
+/// A single value of an enum type definition
+#[derive(Clone, PartialEq, Debug)]
+#[allow(missing_docs)]
+pub struct EnumValueDefinition {
+    pub description: Option<Spanning<String>>,
+    pub name: Spanning<String>,
+    pub directives: Option<Vec<Spanning<Directive>>>,
+}
+
+/// A `scalar` type definition
+#[derive(Clone, PartialEq, Debug)]
+#[allow(missing_docs)]
+pub struct ScalarTypeDefinition {
+    pub description: Option<Spanning<String>>,
+    pub name: Spanning<String>,
+    pub directives: Option<Vec<Spanning<Directive>>>,
+}
+
+/// A `type` definition
+#[derive(Clone, PartialEq, Debug)]
+#[allow(missing_docs)]
+pub struct ObjectTypeDefinition {
+    pub description: Option<Spanning<String>>,
+    pub name: Spanning<String>,
+    pub interfaces: Vec<Spanning<String>>,
+    pub directives: Option<Vec<Spanning<Directive>>>,
+    pub fields: Vec<Spanning<FieldDefinition>>,
+}
+
+/// An `interface` type definition
+#[derive(Clone, PartialEq, Debug)]
+#[allow(missing_docs)]
+pub struct InterfaceTypeDefinition {
+    pub description: Option<Spanning<String>>,
+    pub name: Spanning<String>,
+    pub interfaces: Vec<Spanning<String>>,
+    pub directives: Option<Vec<Spanning<Directive>>>,
+    pub fields: Vec<Spanning<FieldDefinition>>,
+}
+
+/// A `union` type definition
+#[derive(Clone, PartialEq, Debug)]
+#[allow(missing_docs)]
+pub struct UnionTypeDefinition {
+    pub description: Option<Spanning<String>>,
+    pub name: Spanning<String>,
+    pub directives: Option<Vec<Spanning<Directive>>>,
+    pub members: Vec<Spanning<String>>,
+}
+
+/// An `enum` type definition
+#[derive(Clone, PartialEq, Debug)]
+#[allow(missing_docs)]
+pub struct EnumTypeDefinition {
+    pub description: Option<Spanning<String>>,
+    pub name: Spanning<String>,
+    pub directives: Option<Vec<Spanning<Directive>>>,
+    pub values: Vec<Spanning<EnumValueDefinition>>,
+}
+
+/// An `input` object type definition
+#[derive(Clone, PartialEq, Debug)]
+#[allow(missing_docs)]
+pub struct InputObjectTypeDefinition {
+    pub description: Option<Spanning<String>>,
+    pub name: Spanning<String>,
+    pub directives: Option<Vec<Spanning<Directive>>>,
+    pub fields: Vec<Spanning<InputValueDefinition>>,
+}
+
+/// A named type definition in the type system language
+///
+/// The same structure is used for type extensions, e.g. `extend type Query`,
+/// in which case the description is always absent and the field, value, or
+/// member lists might be empty.
+#[derive(Clone, PartialEq, Debug)]
+#[allow(missing_docs)]
+pub enum TypeDefinition {
+    Scalar(ScalarTypeDefinition),
+    Object(ObjectTypeDefinition),
+    Interface(InterfaceTypeDefinition),
+    Union(UnionTypeDefinition),
+    Enum(EnumTypeDefinition),
+    InputObject(InputObjectTypeDefinition),
+}
+
+/// A `directive` definition
+#[derive(Clone, PartialEq, Debug)]
+#[allow(missing_docs)]
+pub struct DirectiveDefinition {
+    pub description: Option<Spanning<String>>,
+    pub name: Spanning<String>,
+    pub arguments: Option<Vec<Spanning<InputValueDefinition>>>,
+    pub repeatable: bool,
+    pub locations: Vec<Spanning<String>>,
+}
+
+/// A top level definition in a GraphQL document
+///
+/// Executable documents only contain operations and fragments. Documents
+/// written in the type system language, e.g. `.graphql` schema files, use the
+/// remaining variants.
+#[derive(Clone, PartialEq, Debug)]
+#[allow(missing_docs)]
 pub enum Definition {
     Operation(Spanning<Operation>),
     Fragment(Spanning<Fragment>),
+    Schema(Spanning<SchemaDefinition>),
+    Type(Spanning<TypeDefinition>),
+    Directive(Spanning<DirectiveDefinition>),
+    SchemaExtension(Spanning<SchemaDefinition>),
+    TypeExtension(Spanning<TypeDefinition>),
 }
 
+/// A parsed GraphQL document
 pub type Document = Vec<Definition>;
 
 /// Parse an unstructured input value into a Rust data type.
@@ -152,9 +293,22 @@ pub type Document = Vec<Definition>;
 /// automatically by the convenience macros `graphql_enum!` and
 /// `graphql_scalar!`. Must be implemented manually when manually exposing new
 /// enums or scalars.
+///
+/// `try_from_input` describes why a conversion failed. The default
+/// implementation reports the offending value; container types override it to
+/// point out where in the value the problem is.
 pub trait FromInputValue: Sized {
     /// Performs the conversion.
     fn from(v: &InputValue) -> Option<Self>;
+
+    /// Performs the conversion, returning a description of the problem if it
+    /// fails.
+    fn try_from_input(v: &InputValue) -> Result<Self, String> {
+        match Self::from(v) {
+            Some(x) => Ok(x),
+            None => Err(format!("Unexpected value {}", v)),
+        }
+    }
 }
 
 /// Losslessly clones a Rust data type into an InputValue.
@@ -306,6 +460,11 @@ impl InputValue {
         <T as FromInputValue>::from(self)
     }
 
+    /// Shorthand form of invoking `FromInputValue::try_from_input()`.
+    pub fn try_convert<T>(&self) -> Result<T, String> where T: FromInputValue {
+        <T as FromInputValue>::try_from_input(self)
+    }
+
     /// Does the value represent null?
     pub fn is_null(&self) -> bool {
         match *self {
@@ -403,6 +562,60 @@ impl InputValue {
     }
 }
 
+impl fmt::Display for InputValue {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            InputValue::Null => write!(f, "null"),
+            InputValue::Int(i) => write!(f, "{}", i),
+            InputValue::Float(v) => write!(f, "{:?}", v),
+            InputValue::String(ref s) => write!(f, "\"{}\"", escape_string(s)),
+            InputValue::Boolean(b) => write!(f, "{}", b),
+            InputValue::Enum(ref e) => write!(f, "{}", e),
+            InputValue::Variable(ref v) => write!(f, "${}", v),
+            InputValue::List(ref l) => {
+                try!(write!(f, "["));
+                for (i, item) in l.iter().enumerate() {
+                    if i > 0 {
+                        try!(write!(f, ", "));
+                    }
+                    try!(write!(f, "{}", item.item));
+                }
+                write!(f, "]")
+            },
+            InputValue::Object(ref o) => {
+                try!(write!(f, "{{"));
+                for (i, &(ref k, ref v)) in o.iter().enumerate() {
+                    if i > 0 {
+                        try!(write!(f, ", "));
+                    }
+                    try!(write!(f, "{}: {}", k.item, v.item));
+                }
+                write!(f, "}}")
+            },
+        }
+    }
+}
+
+pub fn escape_string(s: &str) -> String {
+    let mut escaped = String::with_capacity(s.len());
+
+    for ch in s.chars() {
+        match ch {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            '\t' => escaped.push_str("\\t"),
+            '\u{0008}' => escaped.push_str("\\b"),
+            '\u{000c}' => escaped.push_str("\\f"),
+            c if c < ' ' => escaped.push_str(&format!("\\u{:04X}", c as u32)),
+            c => escaped.push(c),
+        }
+    }
+
+    escaped
+}
+
 impl ToJson for InputValue {
     fn to_json(&self) -> Json {
         match *self {
@@ -447,6 +660,33 @@ impl Arguments {
     }
 }
 
+impl TypeDefinition {
+    /// The name of the defined type
+    pub fn name(&self) -> &Spanning<String> {
+        match *self {
+            TypeDefinition::Scalar(ScalarTypeDefinition { ref name, .. }) |
+            TypeDefinition::Object(ObjectTypeDefinition { ref name, .. }) |
+            TypeDefinition::Interface(InterfaceTypeDefinition { ref name, .. }) |
+            TypeDefinition::Union(UnionTypeDefinition { ref name, .. }) |
+            TypeDefinition::Enum(EnumTypeDefinition { ref name, .. }) |
+            TypeDefinition::InputObject(InputObjectTypeDefinition { ref name, .. }) => name,
+        }
+    }
+}
+
+impl Definition {
+    /// Returns true if the definition can be executed
+    ///
+    /// Only operations and fragments are executable; all other definitions
+    /// describe a type system.
+    pub fn is_executable(&self) -> bool {
+        match *self {
+            Definition::Operation(_) | Definition::Fragment(_) => true,
+            _ => false,
+        }
+    }
+}
+
 impl VariableDefinitions {
     pub fn iter(&self) -> slice::Iter<(Spanning<String>, VariableDefinition)> {
         self.items.iter()
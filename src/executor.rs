@@ -1,10 +1,18 @@
+use std::cmp::Ordering;
 use std::collections::HashMap;
+use std::fmt::Display;
 use std::marker::PhantomData;
+use std::mem;
+
+use futures::{future, Future};
+use futures::future::Loop;
+use rayon::prelude::*;
 
 use ::GraphQLError;
-use ast::{InputValue, ToInputValue, Document, Selection, Fragment, Definition, Type, FromInputValue, OperationType};
-use value::Value;
-use parser::SourcePosition;
+use ast::{InputValue, ToInputValue, Document, Selection, Fragment, Definition, Type, FromInputValue,
+          OperationType, Operation, Field as FieldSelection, Directive};
+use value::{Value, Object};
+use parser::{SourcePosition, Spanning};
 
 use schema::meta::{MetaType, ScalarMeta, ListMeta, NullableMeta,
                    ObjectMeta, EnumMeta, InterfaceMeta, UnionMeta,
@@ -12,7 +20,9 @@ use schema::meta::{MetaType, ScalarMeta, ListMeta, NullableMeta,
                    EnumValue};
 use schema::model::{RootNode, SchemaType};
 
-use types::base::GraphQLType;
+use types::base::{Arguments, GraphQLType, GraphQLSubscriptionType, is_excluded, merge_key_into,
+                  propagate_nulls, merge_values};
+use validation::coerce_variable_values;
 
 /// A type registry used to build schemas
 ///
@@ -28,7 +38,34 @@ pub struct Registry<CtxT> {
 #[derive(Clone)]
 pub enum FieldPath<'a> {
     Root(SourcePosition),
-    Field(String, SourcePosition, &'a FieldPath<'a>),
+    Field(String, SourcePosition, Option<&'a Type>, &'a FieldPath<'a>),
+    Index(usize, SourcePosition, &'a FieldPath<'a>),
+    Deferred(&'a [PathSegment], &'a [Option<Type>], SourcePosition),
+}
+
+/// A single step in the path to a value in the response
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum PathSegment {
+    /// The response name of a field, i.e. its alias or name
+    Field(String),
+    /// The position of an item in a list
+    Index(usize),
+}
+
+/// A field the client selected below the currently resolving field
+///
+/// Returned by `Executor::look_ahead`. Fields selected through fragment
+/// spreads and inline fragments are flattened into the list, keeping the
+/// fragment's type condition. Fields excluded by `@skip` or `@include` are
+/// left out.
+#[derive(Clone, Debug, PartialEq)]
+pub struct LookAheadSelection {
+    name: String,
+    alias: Option<String>,
+    arguments: Vec<(String, InputValue)>,
+    type_condition: Option<String>,
+    possible_types: Option<Vec<String>>,
+    children: Vec<LookAheadSelection>,
 }
 
 /// Query execution engine
@@ -42,25 +79,158 @@ pub struct Executor<'a, CtxT> where CtxT: 'a {
     schema: &'a SchemaType,
     context: &'a CtxT,
     errors: &'a mut Vec<ExecutionError>,
+    deferred: Option<&'a mut Vec<Deferred<CtxT>>>,
     field_path: FieldPath<'a>,
 }
 
+/// Resolution that has been put off until the fields around it are resolved
+///
+/// Implemented by `Load` so that sibling loads can be batched together, and
+/// by `FieldFuture` so that futures of sibling fields can run concurrently.
+#[doc(hidden)]
+pub trait DeferredResolve<CtxT> {
+    /// Take the future that has to complete before the task can resolve
+    ///
+    /// The asynchronous executor waits for the futures of all tasks on a
+    /// level at once. Tasks whose future was not taken have to block on it
+    /// in `resolve`.
+    fn take_future(&mut self) -> Option<Box<Future<Item = (), Error = ()>>> {
+        None
+    }
+
+    /// Resolve the value in place of the placeholder
+    fn resolve(self: Box<Self>, executor: &mut Executor<CtxT>) -> Value;
+}
+
+struct Deferred<CtxT> {
+    path: Vec<PathSegment>,
+    types: Vec<Option<Type>>,
+    location: SourcePosition,
+    selection_set: Option<Vec<Selection>>,
+    task: Box<DeferredResolve<CtxT>>,
+}
+
+/// The state shared by all executors of a single operation
+struct OperationEnv<'a, CtxT: 'a> {
+    fragments: &'a HashMap<String, Fragment>,
+    variables: &'a HashMap<String, InputValue>,
+    schema: &'a SchemaType,
+    context: &'a CtxT,
+}
+
+/// An operation being executed asynchronously
+///
+/// Owns everything the executors borrow, so that it can be moved between the
+/// steps of the future that drives the execution.
+struct AsyncOperation<'a, CtxT: 'a> {
+    fragments: HashMap<String, Fragment>,
+    variables: HashMap<String, InputValue>,
+    schema: &'a SchemaType,
+    context: &'a CtxT,
+    errors: Vec<ExecutionError>,
+}
+
+/// The result of executing a query or mutation asynchronously
+pub type ExecutionFuture<'a> =
+    Box<Future<Item = (Value, Vec<ExecutionError>), Error = GraphQLError<'a>> + 'a>;
+
 /// Error type for errors that occur during query execution
 ///
 /// All execution errors contain the source position in the query of the field
 /// that failed to resolve. It also contains the field stack.
-#[derive(Debug, PartialOrd, Ord, PartialEq, Eq)]
+#[derive(Debug, PartialEq)]
 pub struct ExecutionError {
     location: SourcePosition,
-    path: Vec<String>,
+    path: Vec<PathSegment>,
+    error: FieldError,
+}
+
+/// Error type for errors that occur while resolving a field
+///
+/// Besides the message, a field error can carry arbitrary `extensions` data,
+/// e.g. an error code that clients can match on. It is serialized under the
+/// `extensions` key of the error in the response.
+///
+/// Field errors can be created from strings, so resolvers returning a
+/// `FieldResult` keep working, and string errors can be converted with
+/// `.into()` or `try!`:
+///
+/// ```rust
+/// # use juniper::{FieldError, Value};
+/// fn find_user(id: &str) -> Result<String, FieldError> {
+///     match id {
+///         "1000" => Ok("Robin".to_owned()),
+///         "1001" => Err("User is private".into()),
+///         _ => Err(FieldError::new(
+///             "User not found",
+///             Value::object(vec![
+///                 ("code", Value::string("NOT_FOUND")),
+///             ].into_iter().collect()))),
+///     }
+/// }
+/// # fn main() { assert!(find_user("1002").is_err()); }
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct FieldError {
     message: String,
+    extensions: Value,
+}
+
+/// Stream of responses to a subscription operation
+///
+/// Returned by `juniper::subscribe`. Every event produced by the subscribed
+/// root field is resolved against the operation's selection set, yielding the
+/// same `(Value, Vec<ExecutionError>)` pair as a regular query would.
+///
+/// If the event stream could not be created, the stream yields a single
+/// `null` response carrying the error.
+pub struct SubscriptionStream<'a, CtxT: 'a, SubscriptionT: 'a> {
+    fragments: HashMap<String, Fragment>,
+    variables: HashMap<String, InputValue>,
+    selection_set: Vec<Selection>,
+    location: SourcePosition,
+    schema: &'a SchemaType,
+    context: &'a CtxT,
+    events: Option<Box<Iterator<Item = SubscriptionT> + 'a>>,
+    source_error: Option<ExecutionError>,
 }
 
 /// The result of resolving the value of a field of type `T`
+///
+/// Resolvers that need to attach extensions to their errors can return
+/// `Result<T, FieldError>` instead; fields accept any error type that converts
+/// into a `FieldError`.
 pub type FieldResult<T> = Result<T, String>;
 
 /// The result of resolving an unspecified field
-pub type ExecutionResult = Result<Value, String>;
+///
+/// The error is a `FieldError`, so that extensions survive resolution.
+/// Hand-written `resolve_field` implementations that returned `Err(String)`
+/// convert their messages with `.into()`, while `try!` converts string
+/// errors on its own:
+///
+/// ```rust
+/// use juniper::{ExecutionResult, Value};
+///
+/// fn parse_age(age: &str) -> Result<i64, String> {
+///     age.parse().map_err(|_| format!("Invalid age: {}", age))
+/// }
+///
+/// fn resolve_age(age: &str) -> ExecutionResult {
+///     let age = try!(parse_age(age));
+///
+///     if age < 0 {
+///         return Err("Age can not be negative".into());
+///     }
+///
+///     Ok(Value::int(age))
+/// }
+/// # fn main() {
+/// # assert_eq!(resolve_age("x").unwrap_err().message(), "Invalid age: x");
+/// # assert_eq!(resolve_age("-1").unwrap_err().message(), "Age can not be negative");
+/// # }
+/// ```
+pub type ExecutionResult = Result<Value, FieldError>;
 
 /// Convert a value into a successful field result
 ///
@@ -70,12 +240,49 @@ pub trait IntoFieldResult<T>: Sized {
     /// Wrap `self` in a `Result`
     ///
     /// The implementation of this should always be `Ok(self)`.
-    fn into(self) -> FieldResult<T>;
+    fn into(self) -> Result<T, FieldError>;
 }
 
-impl<T> IntoFieldResult<T> for FieldResult<T> {
-    fn into(self) -> FieldResult<T> {
-        self
+impl<T, E> IntoFieldResult<T> for Result<T, E> where E: Into<FieldError> {
+    fn into(self) -> Result<T, FieldError> {
+        self.map_err(|e| e.into())
+    }
+}
+
+impl FieldError {
+    /// Construct a new error with additional data
+    ///
+    /// The extensions are serialized as-is; pass `Value::null()` to omit them.
+    pub fn new<T: Display>(message: T, extensions: Value) -> FieldError {
+        FieldError {
+            message: format!("{}", message),
+            extensions: extensions,
+        }
+    }
+
+    /// The error message
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+
+    /// Additional data attached to the error, `null` if there is none
+    pub fn extensions(&self) -> &Value {
+        &self.extensions
+    }
+}
+
+impl From<String> for FieldError {
+    fn from(message: String) -> FieldError {
+        FieldError {
+            message: message,
+            extensions: Value::null(),
+        }
+    }
+}
+
+impl<'a> From<&'a str> for FieldError {
+    fn from(message: &'a str) -> FieldError {
+        FieldError::from(message.to_owned())
     }
 }
 
@@ -116,6 +323,7 @@ impl<'a, CtxT> Executor<'a, CtxT> {
             schema: self.schema,
             context: ctx,
             errors: self.errors,
+            deferred: None,
             field_path: self.field_path.clone(),
         }
     }
@@ -124,6 +332,7 @@ impl<'a, CtxT> Executor<'a, CtxT> {
     pub fn sub_executor(
         &mut self,
         field_name: Option<String>,
+        field_type: Option<&'a Type>,
         location: SourcePosition,
         selection_set: Option<Vec<Selection>>,
     )
@@ -136,13 +345,30 @@ impl<'a, CtxT> Executor<'a, CtxT> {
             schema: self.schema,
             context: self.context,
             errors: self.errors,
+            deferred: self.deferred.as_mut().map(|d| &mut **d),
             field_path: match field_name {
-                Some(name) => FieldPath::Field(name, location, &self.field_path),
+                Some(name) => FieldPath::Field(name, location, field_type, &self.field_path),
                 None => self.field_path.clone(),
             },
         }
     }
 
+    #[doc(hidden)]
+    pub fn list_item_sub_executor(&mut self, index: usize) -> Executor<CtxT> {
+        let location = self.field_path.location().clone();
+
+        Executor {
+            fragments: self.fragments,
+            variables: self.variables,
+            current_selection_set: self.current_selection_set.clone(),
+            schema: self.schema,
+            context: self.context,
+            errors: self.errors,
+            deferred: self.deferred.as_mut().map(|d| &mut **d),
+            field_path: FieldPath::Index(index, location, &self.field_path),
+        }
+    }
+
     /// Access the current context
     ///
     /// You usually provide the context when calling the top-level `execute`
@@ -161,56 +387,249 @@ impl<'a, CtxT> Executor<'a, CtxT> {
         self.variables
     }
 
+    #[doc(hidden)]
+    pub fn location(&self) -> &SourcePosition {
+        self.field_path.location()
+    }
+
     #[doc(hidden)]
     pub fn fragment_by_name(&self, name: &str) -> Option<&'a Fragment> {
         self.fragments.get(name)
     }
 
+    /// Look ahead at the fields selected below the current field
+    ///
+    /// This lets resolvers fetch only what the client asked for, e.g. by
+    /// building a projection or join up front. Arguments are resolved against
+    /// the query variables.
+    pub fn look_ahead(&self) -> Vec<LookAheadSelection> {
+        let mut selections = Vec::new();
+
+        if let Some(ref selection_set) = self.current_selection_set {
+            collect_look_ahead(
+                selection_set, None, self.schema, self.fragments, self.variables, &mut selections);
+        }
+
+        selections
+    }
+
+    /// Put off resolving a value until the rest of the current level is done
+    ///
+    /// Returns a `null` placeholder that is replaced by the result of the
+    /// task once it runs. Placeholders are exempt from null propagation; if
+    /// the task resolves a non-null value to `null`, the `null` propagates to
+    /// the closest nullable parent at that point. Executors without a
+    /// deferral queue, e.g. ones with a replaced context, run the task right
+    /// away.
+    #[doc(hidden)]
+    pub fn defer(&mut self, task: Box<DeferredResolve<CtxT>>) -> Value {
+        if self.deferred.is_none() {
+            return task.resolve(self);
+        }
+
+        let mut path = Vec::new();
+        self.field_path.construct_path(&mut path);
+
+        let mut types = Vec::new();
+        self.field_path.construct_types(&mut types);
+
+        let deferred = Deferred {
+            path: path,
+            types: types,
+            location: self.field_path.location().clone(),
+            selection_set: self.current_selection_set.clone(),
+            task: task,
+        };
+
+        if let Some(ref mut queue) = self.deferred {
+            queue.push(deferred);
+        }
+
+        Value::null()
+    }
+
+    #[doc(hidden)]
+    pub fn deferred_count(&self) -> usize {
+        self.deferred.as_ref().map_or(0, |d| d.len())
+    }
+
+    /// The positions of the placeholders that tasks deferred since `start`
+    /// left in the value of the current field
+    ///
+    /// Positions are list indices relative to the current field. Tasks
+    /// deferred inside nested objects are left out, since their placeholders
+    /// are handled when those objects are resolved.
+    #[doc(hidden)]
+    pub fn placeholders_since(&self, start: usize) -> Vec<Vec<usize>> {
+        let deferred = match self.deferred {
+            Some(ref deferred) => deferred,
+            None => return vec![],
+        };
+
+        let mut path = Vec::new();
+        self.field_path.construct_path(&mut path);
+
+        deferred[start..].iter()
+            .filter(|task| task.path.starts_with(&path))
+            .filter_map(|task| task.path[path.len()..].iter()
+                .map(|segment| match *segment {
+                    PathSegment::Index(index) => Some(index),
+                    PathSegment::Field(_) => None,
+                })
+                .collect())
+            .collect()
+    }
+
     /// Add an error to the execution engine
-    pub fn push_error(&mut self, error: String, location: SourcePosition) {
+    pub fn push_error<E: Into<FieldError>>(&mut self, error: E, location: SourcePosition) {
         let mut path = Vec::new();
         self.field_path.construct_path(&mut path);
 
         self.errors.push(ExecutionError {
             location: location,
             path: path,
-            message: error,
+            error: error.into(),
         });
     }
 }
 
 impl<'a> FieldPath<'a> {
-    fn construct_path(&self, acc: &mut Vec<String>) {
+    fn construct_path(&self, acc: &mut Vec<PathSegment>) {
         match *self {
             FieldPath::Root(_) => (),
-            FieldPath::Field(ref name, _, ref parent) => {
+            FieldPath::Field(ref name, _, _, ref parent) => {
                 parent.construct_path(acc);
-                acc.push(name.clone());
+                acc.push(PathSegment::Field(name.clone()));
+            }
+            FieldPath::Index(index, _, ref parent) => {
+                parent.construct_path(acc);
+                acc.push(PathSegment::Index(index));
+            }
+            FieldPath::Deferred(path, _, _) => acc.extend(path.iter().cloned()),
+        }
+    }
+
+    /// The schema types of the values along the path, `None` where unknown
+    fn construct_types(&self, acc: &mut Vec<Option<Type>>) {
+        match *self {
+            FieldPath::Root(_) => (),
+            FieldPath::Field(_, _, field_type, ref parent) => {
+                parent.construct_types(acc);
+                acc.push(field_type.cloned());
+            }
+            FieldPath::Index(_, _, ref parent) => {
+                parent.construct_types(acc);
+
+                let item_type = match acc.last() {
+                    Some(&Some(Type::List(ref inner))) |
+                    Some(&Some(Type::NonNullList(ref inner))) => Some((**inner).clone()),
+                    _ => None,
+                };
+                acc.push(item_type);
             }
+            FieldPath::Deferred(_, types, _) => acc.extend(types.iter().cloned()),
         }
     }
 
     fn location(&self) -> &SourcePosition {
         match *self {
             FieldPath::Root(ref pos) |
-            FieldPath::Field(_, ref pos, _) => pos
+            FieldPath::Field(_, ref pos, _, _) |
+            FieldPath::Index(_, ref pos, _) |
+            FieldPath::Deferred(_, _, ref pos) => pos
         }
     }
 }
 
+impl<'a> From<&'a str> for PathSegment {
+    fn from(name: &'a str) -> PathSegment {
+        PathSegment::Field(name.to_owned())
+    }
+}
+
+impl From<usize> for PathSegment {
+    fn from(index: usize) -> PathSegment {
+        PathSegment::Index(index)
+    }
+}
+
+impl LookAheadSelection {
+    /// The name of the selected field
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// The alias of the selected field, if any
+    pub fn alias(&self) -> Option<&str> {
+        self.alias.as_ref().map(|s| s.as_str())
+    }
+
+    /// The name of the field in the response, i.e. its alias or name
+    pub fn response_name(&self) -> &str {
+        self.alias().unwrap_or(&self.name)
+    }
+
+    /// The arguments provided to the field, with variables substituted
+    ///
+    /// Arguments left out of the query are not included, even if they have
+    /// default values.
+    pub fn arguments(&self) -> &[(String, InputValue)] {
+        &self.arguments
+    }
+
+    /// Look up a single argument by name
+    pub fn argument(&self, name: &str) -> Option<&InputValue> {
+        self.arguments.iter().find(|&&(ref k, _)| k == name).map(|&(_, ref v)| v)
+    }
+
+    /// The type condition of the fragment this field was selected through
+    ///
+    /// `None` if the field was selected directly, or through fragments
+    /// without a type condition.
+    pub fn type_condition(&self) -> Option<&str> {
+        self.type_condition.as_ref().map(|s| s.as_str())
+    }
+
+    /// Whether this field is selected for objects of the given concrete type
+    ///
+    /// Takes all enclosing type conditions into account, including those
+    /// naming interfaces or unions.
+    pub fn applies_to(&self, type_name: &str) -> bool {
+        self.possible_types.as_ref().map_or(true, |ts| ts.iter().any(|t| t == type_name))
+    }
+
+    /// The fields selected below this field
+    pub fn children(&self) -> &[LookAheadSelection] {
+        &self.children
+    }
+
+    /// Find a child field by name
+    pub fn child(&self, name: &str) -> Option<&LookAheadSelection> {
+        self.children.iter().find(|c| c.name == name)
+    }
+}
+
 impl ExecutionError {
     #[doc(hidden)]
-    pub fn new(location: SourcePosition, path: &[&str], message: &str) -> ExecutionError {
+    pub fn new<P, E>(location: SourcePosition, path: &[P], error: E) -> ExecutionError
+        where P: Into<PathSegment> + Clone,
+              E: Into<FieldError>,
+    {
         ExecutionError {
             location: location,
-            path: path.iter().map(|s| (*s).to_owned()).collect(),
-            message: message.to_owned(),
+            path: path.iter().cloned().map(|s| s.into()).collect(),
+            error: error.into(),
         }
     }
 
     /// The error message
     pub fn message(&self) -> &str {
-        &self.message
+        self.error.message()
+    }
+
+    /// Additional data attached to the error by the resolver
+    pub fn extensions(&self) -> &Value {
+        self.error.extensions()
     }
 
     /// The source location _in the query_ of the field that failed to resolve
@@ -218,72 +637,884 @@ impl ExecutionError {
         &self.location
     }
 
-    /// The path of fields leading to the field that generated this error
-    pub fn path(&self) -> &[String] {
+    /// The path of fields and list indices leading to the field that
+    /// generated this error
+    pub fn path(&self) -> &[PathSegment] {
         &self.path
     }
 }
 
-pub fn execute_validated_query<'a, QueryT, MutationT, CtxT>(
-    document: Document,
+impl Eq for ExecutionError {}
+
+impl PartialOrd for ExecutionError {
+    fn partial_cmp(&self, other: &ExecutionError) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// Errors are ordered by location, path, and message; extensions are not
+/// taken into account
+impl Ord for ExecutionError {
+    fn cmp(&self, other: &ExecutionError) -> Ordering {
+        (&self.location, &self.path, self.message())
+            .cmp(&(&other.location, &other.path, other.message()))
+    }
+}
+
+pub fn execute_validated_query<'a, QueryT, MutationT, SubscriptionT, CtxT>(
+    document: &Document,
     operation_name: Option<&str>,
-    root_node: &RootNode<CtxT, QueryT, MutationT>,
+    root_node: &RootNode<CtxT, QueryT, MutationT, SubscriptionT>,
     variables: &HashMap<String, InputValue>,
     context: &CtxT
 )
     -> Result<(Value, Vec<ExecutionError>), GraphQLError<'a>>
     where QueryT: GraphQLType<CtxT>,
-          MutationT: GraphQLType<CtxT>
+          MutationT: GraphQLType<CtxT>,
+          SubscriptionT: GraphQLType<CtxT>,
+{
+    execute_validated_operation(
+        document, operation_name, root_node, variables, context,
+        |env, selection_set, location, errors| {
+            let mut deferred = Vec::new();
+            let mut value = env.resolve_root(
+                root_node, selection_set, location, errors, &mut deferred);
+
+            env.resolve_deferred(&mut value, &mut deferred, errors);
+            value
+        })
+}
+
+pub fn execute_validated_query_parallel<'a, QueryT, MutationT, SubscriptionT, CtxT>(
+    document: &Document,
+    operation_name: Option<&str>,
+    root_node: &RootNode<CtxT, QueryT, MutationT, SubscriptionT>,
+    variables: &HashMap<String, InputValue>,
+    context: &CtxT
+)
+    -> Result<(Value, Vec<ExecutionError>), GraphQLError<'a>>
+    where QueryT: GraphQLType<CtxT> + Sync,
+          MutationT: GraphQLType<CtxT> + Sync,
+          SubscriptionT: GraphQLType<CtxT> + Sync,
+          CtxT: Sync,
+{
+    execute_validated_operation(
+        document, operation_name, root_node, variables, context,
+        |env, selection_set, location, errors| {
+            env.resolve_root_parallel(root_node, selection_set, location, errors)
+        })
+}
+
+/// Execute a query or mutation, resolving a query's root fields with
+/// `resolve_query`
+///
+/// The root fields of a mutation are always resolved one after another.
+fn execute_validated_operation<'a, QueryT, MutationT, SubscriptionT, CtxT, F>(
+    document: &Document,
+    operation_name: Option<&str>,
+    root_node: &RootNode<CtxT, QueryT, MutationT, SubscriptionT>,
+    variables: &HashMap<String, InputValue>,
+    context: &CtxT,
+    resolve_query: F,
+)
+    -> Result<(Value, Vec<ExecutionError>), GraphQLError<'a>>
+    where QueryT: GraphQLType<CtxT>,
+          MutationT: GraphQLType<CtxT>,
+          SubscriptionT: GraphQLType<CtxT>,
+          F: FnOnce(&OperationEnv<CtxT>, Vec<Selection>, SourcePosition, &mut Vec<ExecutionError>) -> Value,
+{
+    let (op, fragments) = try!(get_operation(document, operation_name));
+
+    if op.item.operation_type == OperationType::Subscription {
+        return Err(GraphQLError::IsSubscription);
+    }
+
+    let variables = try!(coerce_operation_variables(&op, variables, &root_node.schema));
+
+    let env = OperationEnv {
+        fragments: &fragments,
+        variables: &variables,
+        schema: &root_node.schema,
+        context: context,
+    };
+
+    let mut errors = Vec::new();
+
+    let value = match op.item.operation_type {
+        OperationType::Query => resolve_query(&env, op.item.selection_set, op.start, &mut errors),
+        OperationType::Mutation => {
+            let mut result = Some(Object::new());
+
+            for field in collect_root_fields(op.item.selection_set, &fragments, &variables) {
+                let mut deferred = Vec::new();
+                let mut value = env.resolve_root(
+                    &root_node.mutation_type, vec![field], op.start.clone(), &mut errors, &mut deferred);
+
+                env.resolve_deferred(&mut value, &mut deferred, &mut errors);
+                merge_root_value(&mut result, value);
+
+                // A non-null root field failed, so the remaining fields
+                // would be discarded anyway
+                if result.is_none() {
+                    break;
+                }
+            }
+
+            result.map_or(Value::null(), Value::object)
+        }
+        OperationType::Subscription => unreachable!(),
+    };
+
+    errors.sort();
+
+    Ok((value, errors))
+}
+
+pub fn execute_validated_query_async<'a, QueryT, MutationT, SubscriptionT, CtxT>(
+    document: &Document,
+    operation_name: Option<&str>,
+    root_node: &'a RootNode<CtxT, QueryT, MutationT, SubscriptionT>,
+    variables: &HashMap<String, InputValue>,
+    context: &'a CtxT
+)
+    -> ExecutionFuture<'a>
+    where QueryT: GraphQLType<CtxT>,
+          MutationT: GraphQLType<CtxT>,
+          SubscriptionT: GraphQLType<CtxT>,
+{
+    let (op, fragments) = match get_operation(document, operation_name) {
+        Ok(op) => op,
+        Err(e) => return Box::new(future::err(e)),
+    };
+
+    if op.item.operation_type == OperationType::Subscription {
+        return Box::new(future::err(GraphQLError::IsSubscription));
+    }
+
+    let variables = match coerce_operation_variables(&op, variables, &root_node.schema) {
+        Ok(variables) => variables,
+        Err(e) => return Box::new(future::err(e)),
+    };
+
+    let mut operation = AsyncOperation {
+        fragments: fragments,
+        variables: variables,
+        schema: &root_node.schema,
+        context: context,
+        errors: Vec::new(),
+    };
+
+    // Nothing is resolved until the future is polled
+    let result = future::lazy(move || -> Box<Future<Item = _, Error = GraphQLError<'a>> + 'a> {
+        match op.item.operation_type {
+            OperationType::Query => {
+                let mut deferred = Vec::new();
+                let value = operation.resolve_root(
+                    root_node, op.item.selection_set, op.start, &mut deferred);
+
+                resolve_deferred_async(operation, value, deferred)
+            }
+            OperationType::Mutation => {
+                let fields = collect_root_fields(
+                    op.item.selection_set, &operation.fragments, &operation.variables);
+                let location = op.start;
+
+                // Each root field, including everything deferred below it, is
+                // resolved before the next one starts
+                Box::new(future::loop_fn(
+                    (operation, Some(Object::new()), fields.into_iter()),
+                    move |(mut operation, mut result, mut fields)| -> Box<Future<Item = _, Error = _>> {
+                        match fields.next() {
+                            Some(field) => {
+                                let mut deferred = Vec::new();
+                                let value = operation.resolve_root(
+                                    &root_node.mutation_type, vec![field], location.clone(), &mut deferred);
+
+                                Box::new(resolve_deferred_async(operation, value, deferred)
+                                    .map(move |(operation, value)| {
+                                        merge_root_value(&mut result, value);
+
+                                        match result {
+                                            Some(_) => Loop::Continue((operation, result, fields)),
+                                            None => Loop::Break((operation, Value::null())),
+                                        }
+                                    }))
+                            }
+                            None => Box::new(future::ok(Loop::Break(
+                                (operation, result.map_or(Value::null(), Value::object))))),
+                        }
+                    }))
+            }
+            OperationType::Subscription => unreachable!(),
+        }
+    });
+
+    Box::new(result.map(|(mut operation, value)| {
+        operation.errors.sort();
+        (value, operation.errors)
+    }))
+}
+
+pub fn execute_validated_subscription<'a, 'b, QueryT, MutationT, SubscriptionT, CtxT>(
+    document: &Document,
+    operation_name: Option<&str>,
+    root_node: &'b RootNode<CtxT, QueryT, MutationT, SubscriptionT>,
+    variables: &HashMap<String, InputValue>,
+    context: &'b CtxT
+)
+    -> Result<SubscriptionStream<'b, CtxT, SubscriptionT>, GraphQLError<'a>>
+    where QueryT: GraphQLType<CtxT>,
+          MutationT: GraphQLType<CtxT>,
+          SubscriptionT: GraphQLSubscriptionType<CtxT>,
+{
+    let (op, fragments) = try!(get_operation(document, operation_name));
+
+    if op.item.operation_type != OperationType::Subscription {
+        return Err(GraphQLError::NotSubscription);
+    }
+
+    let variables = try!(coerce_operation_variables(&op, variables, &root_node.schema));
+
+    let source = create_source_stream(
+        &root_node.subscription_type,
+        &root_node.schema,
+        &op,
+        &fragments,
+        &variables,
+        context);
+
+    let (events, source_error) = match source {
+        Ok(events) => (Some(events), None),
+        Err(e) => (None, Some(e)),
+    };
+
+    Ok(SubscriptionStream {
+        fragments: fragments,
+        variables: variables,
+        selection_set: op.item.selection_set,
+        location: op.start,
+        schema: &root_node.schema,
+        context: context,
+        events: events,
+        source_error: source_error,
+    })
+}
+
+/// Find the operation to execute and the fragments of a document
+///
+/// Only the selected operation and the fragments are copied out of the
+/// document, since the executor consumes the selection sets it resolves.
+fn get_operation<'a>(document: &Document, operation_name: Option<&str>)
+    -> Result<(Spanning<Operation>, HashMap<String, Fragment>), GraphQLError<'a>>
 {
-    let mut fragments = vec![];
+    let mut fragments = HashMap::new();
     let mut operation = None;
 
     for def in document {
-        match def {
-            Definition::Operation(op) => {
+        match *def {
+            Definition::Operation(ref op) => {
                 if operation_name.is_none() && operation.is_some() {
                     return Err(GraphQLError::MultipleOperationsProvided);
                 }
 
-                let move_op = operation_name.is_none()
+                let select_op = operation_name.is_none()
                     || op.item.name.as_ref().map(|s| s.item.as_ref()) == operation_name;
 
-                if move_op {
+                if select_op {
                     operation = Some(op);
                 }
             }
-            Definition::Fragment(f) => fragments.push(f),
+            Definition::Fragment(ref f) => {
+                fragments.insert(f.item.name.item.clone(), f.item.clone());
+            }
+            _ => (),
         };
     }
 
-    let op = match operation {
-        Some(op) => op,
-        None => return Err(GraphQLError::UnknownOperationName),
+    match operation {
+        Some(op) => Ok((op.clone(), fragments)),
+        None => Err(GraphQLError::UnknownOperationName),
+    }
+}
+
+fn coerce_operation_variables<'a>(
+    op: &Spanning<Operation>,
+    variables: &HashMap<String, InputValue>,
+    schema: &SchemaType,
+)
+    -> Result<HashMap<String, InputValue>, GraphQLError<'a>>
+{
+    coerce_variable_values(
+        variables,
+        op.item.variable_definitions.as_ref().map(|defs| &defs.item),
+        schema,
+    ).map_err(GraphQLError::ValidationError)
+}
+
+fn create_source_stream<'a, CtxT, SubscriptionT>(
+    subscription: &'a SubscriptionT,
+    schema: &SchemaType,
+    op: &Spanning<Operation>,
+    fragments: &HashMap<String, Fragment>,
+    variables: &HashMap<String, InputValue>,
+    context: &'a CtxT,
+)
+    -> Result<Box<Iterator<Item = SubscriptionT> + 'a>, ExecutionError>
+    where SubscriptionT: GraphQLSubscriptionType<CtxT>
+{
+    let meta_type = match schema.concrete_subscription_type() {
+        Some(t) => t,
+        None => return Err(ExecutionError::new(
+            op.start.clone(), &[] as &[PathSegment], "Schema is not configured for subscriptions")),
+    };
+
+    let field = match first_root_field(&op.item.selection_set, fragments, variables) {
+        Some(f) => f,
+        None => return Err(ExecutionError::new(
+            op.start.clone(), &[] as &[PathSegment], "Subscription must select one top level field")),
     };
 
-    let mut errors = Vec::new();
-    let value;
+    let response_name = field.item.alias.as_ref().unwrap_or(&field.item.name).item.clone();
+
+    let meta_field = match meta_type.field_by_name(&field.item.name.item) {
+        Some(f) => f,
+        None => return Err(ExecutionError::new(
+            field.start.clone(),
+            &[&response_name[..]],
+            format!("Can not subscribe to field \"{}\"", field.item.name.item))),
+    };
+
+    let arguments = Arguments::new(
+        field.item.arguments.map(|m|
+            m.item.into_iter().map(|(k, v)|
+                (k.item, v.item.into_const(variables))).collect()),
+        &meta_field.arguments);
 
+    let start_pos = field.start;
+
+    subscription.subscribe(&field.item.name.item, &arguments, context)
+        .map_err(|e| ExecutionError::new(start_pos, &[&response_name[..]], e))
+}
+
+fn first_root_field(
+    selection_set: &[Selection],
+    fragments: &HashMap<String, Fragment>,
+    variables: &HashMap<String, InputValue>,
+)
+    -> Option<Spanning<FieldSelection>>
+{
+    for selection in selection_set {
+        let found = match *selection {
+            Selection::Field(ref f) =>
+                if is_excluded(&unspan_directives(&f.item.directives), variables) { None }
+                else { Some(f.clone()) },
+            Selection::FragmentSpread(ref spread) =>
+                if is_excluded(&unspan_directives(&spread.item.directives), variables) { None }
+                else {
+                    fragments.get(&spread.item.name.item)
+                        .and_then(|f| first_root_field(&f.selection_set, fragments, variables))
+                },
+            Selection::InlineFragment(ref fragment) =>
+                if is_excluded(&unspan_directives(&fragment.item.directives), variables) { None }
+                else { first_root_field(&fragment.item.selection_set, fragments, variables) },
+        };
+
+        if found.is_some() {
+            return found;
+        }
+    }
+
+    None
+}
+
+struct LookAheadCondition<'a> {
+    type_name: &'a str,
+    possible_types: Vec<String>,
+}
+
+fn collect_look_ahead(
+    selection_set: &[Selection],
+    condition: Option<&LookAheadCondition>,
+    schema: &SchemaType,
+    fragments: &HashMap<String, Fragment>,
+    variables: &HashMap<String, InputValue>,
+    acc: &mut Vec<LookAheadSelection>,
+) {
+    for selection in selection_set {
+        match *selection {
+            Selection::Field(ref f) => {
+                if is_excluded(&unspan_directives(&f.item.directives), variables) {
+                    continue;
+                }
+
+                let mut children = Vec::new();
+
+                if let Some(ref child_set) = f.item.selection_set {
+                    collect_look_ahead(child_set, None, schema, fragments, variables, &mut children);
+                }
+
+                merge_look_ahead(acc, LookAheadSelection {
+                    name: f.item.name.item.clone(),
+                    alias: f.item.alias.as_ref().map(|a| a.item.clone()),
+                    arguments: f.item.arguments.as_ref()
+                        .map(|args| args.item.iter()
+                            .map(|&(ref k, ref v)| (k.item.clone(), v.item.clone().into_const(variables)))
+                            .collect())
+                        .unwrap_or_else(Vec::new),
+                    type_condition: condition.map(|c| c.type_name.to_owned()),
+                    possible_types: condition.map(|c| c.possible_types.clone()),
+                    children: children,
+                });
+            }
+            Selection::FragmentSpread(ref spread) => {
+                if is_excluded(&unspan_directives(&spread.item.directives), variables) {
+                    continue;
+                }
+
+                if let Some(fragment) = fragments.get(&spread.item.name.item) {
+                    let condition = narrow_look_ahead_condition(
+                        condition, &fragment.type_condition.item, schema);
+
+                    collect_look_ahead(
+                        &fragment.selection_set, Some(&condition), schema, fragments, variables, acc);
+                }
+            }
+            Selection::InlineFragment(ref fragment) => {
+                if is_excluded(&unspan_directives(&fragment.item.directives), variables) {
+                    continue;
+                }
+
+                match fragment.item.type_condition {
+                    Some(ref type_condition) => {
+                        let condition = narrow_look_ahead_condition(
+                            condition, &type_condition.item, schema);
+
+                        collect_look_ahead(
+                            &fragment.item.selection_set, Some(&condition), schema, fragments, variables, acc);
+                    }
+                    None => collect_look_ahead(
+                        &fragment.item.selection_set, condition, schema, fragments, variables, acc),
+                }
+            }
+        }
+    }
+}
+
+fn narrow_look_ahead_condition<'a>(
+    outer: Option<&LookAheadCondition>,
+    type_name: &'a str,
+    schema: &SchemaType,
+)
+    -> LookAheadCondition<'a>
+{
+    let mut possible_types = match schema.concrete_type_by_name(type_name) {
+        Some(t) if t.is_abstract() =>
+            schema.possible_types(t).into_iter().flat_map(|t| t.name()).map(|n| n.to_owned()).collect(),
+        _ => vec![type_name.to_owned()],
+    };
+
+    if let Some(outer) = outer {
+        possible_types.retain(|t| outer.possible_types.contains(t));
+    }
+
+    possible_types.sort();
+
+    LookAheadCondition {
+        type_name: type_name,
+        possible_types: possible_types,
+    }
+}
+
+fn merge_look_ahead(acc: &mut Vec<LookAheadSelection>, selection: LookAheadSelection) {
+    let existing = acc.iter().position(|s|
+        s.response_name() == selection.response_name()
+            && s.possible_types == selection.possible_types);
+
+    match existing {
+        Some(i) => for child in selection.children {
+            merge_look_ahead(&mut acc[i].children, child);
+        },
+        None => acc.push(selection),
+    }
+}
+
+impl<'a, CtxT> OperationEnv<'a, CtxT> {
+    fn resolve_root<T>(
+        &self,
+        root: &T,
+        selection_set: Vec<Selection>,
+        location: SourcePosition,
+        errors: &mut Vec<ExecutionError>,
+        deferred: &mut Vec<Deferred<CtxT>>,
+    )
+        -> Value
+        where T: GraphQLType<CtxT>
     {
         let mut executor = Executor {
-            fragments: &fragments.into_iter().map(|f| (f.item.name.item.clone(), f.item)).collect(),
-            variables: variables,
-            current_selection_set: Some(op.item.selection_set),
-            schema: &root_node.schema,
-            context: context,
-            errors: &mut errors,
-            field_path: FieldPath::Root(op.start),
+            fragments: self.fragments,
+            variables: self.variables,
+            current_selection_set: Some(selection_set),
+            schema: self.schema,
+            context: self.context,
+            errors: errors,
+            deferred: Some(deferred),
+            field_path: FieldPath::Root(location),
+        };
+
+        executor.resolve_into_value(root)
+    }
+
+    /// Resolve each root field on the thread pool
+    ///
+    /// Every field is resolved, including the work it defers, by a single
+    /// worker with its own error list. The results and errors are combined
+    /// in field order once all fields are done.
+    ///
+    /// Deferred tasks are not `Send`, so they can not be collected from all
+    /// workers into a single queue; each root field runs its own levels.
+    /// Nested fields are not spread across the pool.
+    fn resolve_root_parallel<T>(
+        &self,
+        root: &T,
+        selection_set: Vec<Selection>,
+        location: SourcePosition,
+        errors: &mut Vec<ExecutionError>,
+    )
+        -> Value
+        where T: GraphQLType<CtxT> + Sync,
+              CtxT: Sync,
+    {
+        let fields = collect_root_fields(selection_set, self.fragments, self.variables);
+
+        let results = fields.into_par_iter()
+            .map(|field| {
+                let mut errors = Vec::new();
+                let mut deferred = Vec::new();
+                let mut value = self.resolve_root(
+                    root, vec![field], location.clone(), &mut errors, &mut deferred);
+
+                self.resolve_deferred(&mut value, &mut deferred, &mut errors);
+                (value, errors)
+            })
+            .collect::<Vec<_>>();
+
+        let mut result = Some(Object::new());
+
+        for (value, field_errors) in results {
+            merge_root_value(&mut result, value);
+            errors.extend(field_errors);
+        }
+
+        result.map_or(Value::null(), Value::object)
+    }
+
+    /// Run deferred tasks level by level until no new ones are added
+    ///
+    /// All tasks deferred while resolving one level are collected before any
+    /// of them run, so loads for sibling values end up in the same batch.
+    fn resolve_deferred(
+        &self,
+        value: &mut Value,
+        deferred: &mut Vec<Deferred<CtxT>>,
+        errors: &mut Vec<ExecutionError>,
+    ) {
+        while !deferred.is_empty() {
+            let level = mem::replace(deferred, Vec::new());
+            self.resolve_level(value, level, errors, deferred);
+        }
+    }
+
+    /// Run the tasks of one level, collecting newly deferred tasks
+    ///
+    /// Tasks whose placeholder was removed, e.g. by null propagation, are
+    /// dropped. A task that resolves a non-null value to `null` replaces the
+    /// closest nullable parent with `null`, or the whole value if there is
+    /// none.
+    fn resolve_level(
+        &self,
+        value: &mut Value,
+        level: Vec<Deferred<CtxT>>,
+        errors: &mut Vec<ExecutionError>,
+        deferred: &mut Vec<Deferred<CtxT>>,
+    ) {
+        // A field selected more than once defers one task per selection, all
+        // with the same path; the results of later tasks are merged into the
+        // first one
+        let mut filled: Vec<Vec<PathSegment>> = Vec::new();
+
+        for task in level {
+            if value_at_path(value, &task.path).is_none() {
+                continue;
+            }
+
+            let (result, placeholders) = {
+                let mut executor = Executor {
+                    fragments: self.fragments,
+                    variables: self.variables,
+                    current_selection_set: task.selection_set,
+                    schema: self.schema,
+                    context: self.context,
+                    errors: errors,
+                    deferred: Some(deferred),
+                    field_path: FieldPath::Deferred(&task.path, &task.types, task.location),
+                };
+
+                let start = executor.deferred_count();
+                let result = task.task.resolve(&mut executor);
+
+                (result, executor.placeholders_since(start))
+            };
+
+            let result = match task.types.last() {
+                Some(&Some(ref value_type)) => propagate_nulls(value_type, result, &placeholders),
+                _ => Some(result),
+            };
+
+            let is_filled = filled.contains(&task.path);
+
+            match result {
+                Some(result) => if let Some(slot) = value_at_path(value, &task.path) {
+                    if is_filled {
+                        merge_values(slot, result);
+                    }
+                    else {
+                        *slot = result;
+                    }
+                },
+                None => null_nullable_parent(value, &task.path, &task.types),
+            }
+
+            if !is_filled {
+                filled.push(task.path);
+            }
+        }
+    }
+}
+
+impl<'a, CtxT> AsyncOperation<'a, CtxT> {
+    fn resolve_root<T>(
+        &mut self,
+        root: &T,
+        selection_set: Vec<Selection>,
+        location: SourcePosition,
+        deferred: &mut Vec<Deferred<CtxT>>,
+    )
+        -> Value
+        where T: GraphQLType<CtxT>
+    {
+        let env = OperationEnv {
+            fragments: &self.fragments,
+            variables: &self.variables,
+            schema: self.schema,
+            context: self.context,
         };
 
-        value = match op.item.operation_type {
-            OperationType::Query => executor.resolve_into_value(&root_node),
-            OperationType::Mutation => executor.resolve_into_value(&root_node.mutation_type),
+        env.resolve_root(root, selection_set, location, &mut self.errors, deferred)
+    }
+
+    fn resolve_level(
+        &mut self,
+        value: &mut Value,
+        level: Vec<Deferred<CtxT>>,
+        deferred: &mut Vec<Deferred<CtxT>>,
+    ) {
+        let env = OperationEnv {
+            fragments: &self.fragments,
+            variables: &self.variables,
+            schema: self.schema,
+            context: self.context,
         };
+
+        env.resolve_level(value, level, &mut self.errors, deferred);
     }
+}
 
-    errors.sort();
+/// Run deferred tasks level by level, waiting for the futures of each level
+/// concurrently
+fn resolve_deferred_async<'a, CtxT>(
+    operation: AsyncOperation<'a, CtxT>,
+    value: Value,
+    deferred: Vec<Deferred<CtxT>>,
+)
+    -> Box<Future<Item = (AsyncOperation<'a, CtxT>, Value), Error = GraphQLError<'a>> + 'a>
+{
+    Box::new(future::loop_fn((operation, value, deferred), |(mut operation, mut value, mut level)| {
+        let futures = level.iter_mut()
+            .filter_map(|task| task.task.take_future())
+            .collect::<Vec<_>>();
 
-    Ok((value, errors))
+        future::join_all(futures).then(move |_| {
+            let mut deferred = Vec::new();
+            operation.resolve_level(&mut value, level, &mut deferred);
+
+            if deferred.is_empty() {
+                Ok(Loop::Break((operation, value)))
+            }
+            else {
+                Ok(Loop::Continue((operation, value, deferred)))
+            }
+        })
+    }))
+}
+
+/// Flatten the root selection set of an operation into its fields
+///
+/// Mutation root fields are resolved one by one, and query root fields may be
+/// resolved in parallel, so fragments at the root are expanded and excluded
+/// selections dropped up front. Fields with the same response name are merged
+/// into the first of them, so that each is resolved only once.
+fn collect_root_fields(
+    selection_set: Vec<Selection>,
+    fragments: &HashMap<String, Fragment>,
+    variables: &HashMap<String, InputValue>,
+)
+    -> Vec<Selection>
+{
+    let mut fields: Vec<Selection> = Vec::new();
+
+    for field in flatten_root_fields(selection_set, fragments, variables) {
+        let existing = fields.iter_mut().find(|f| response_name(f) == response_name(&field));
+
+        match (existing, field) {
+            (Some(&mut Selection::Field(ref mut existing)), Selection::Field(field)) => {
+                if let Some(selection_set) = field.item.selection_set {
+                    existing.item.selection_set
+                        .get_or_insert_with(Vec::new)
+                        .extend(selection_set);
+                }
+            }
+            (_, field) => fields.push(field),
+        }
+    }
+
+    fields
+}
+
+fn response_name(selection: &Selection) -> Option<&str> {
+    match *selection {
+        Selection::Field(ref f) => Some(&f.item.alias.as_ref().unwrap_or(&f.item.name).item),
+        _ => None,
+    }
+}
+
+fn flatten_root_fields(
+    selection_set: Vec<Selection>,
+    fragments: &HashMap<String, Fragment>,
+    variables: &HashMap<String, InputValue>,
+)
+    -> Vec<Selection>
+{
+    let mut fields = Vec::new();
+
+    for selection in selection_set {
+        match selection {
+            Selection::Field(ref field)
+                if is_excluded(&unspan_directives(&field.item.directives), variables) => (),
+            Selection::Field(_) => fields.push(selection),
+            Selection::FragmentSpread(spread) => {
+                if is_excluded(&unspan_directives(&spread.item.directives), variables) {
+                    continue;
+                }
+
+                if let Some(fragment) = fragments.get(&spread.item.name.item) {
+                    fields.extend(flatten_root_fields(fragment.selection_set.clone(), fragments, variables));
+                }
+            }
+            Selection::InlineFragment(fragment) => {
+                if is_excluded(&unspan_directives(&fragment.item.directives), variables) {
+                    continue;
+                }
+
+                fields.extend(flatten_root_fields(fragment.item.selection_set, fragments, variables));
+            }
+        }
+    }
+
+    fields
+}
+
+/// Merge the result of a single root field into the response data
+///
+/// A root field that nulled its parent makes the whole response `null`.
+fn merge_root_value(result: &mut Option<Object>, value: Value) {
+    match value {
+        Value::Object(object) => if let Some(ref mut result) = *result {
+            for (k, v) in object {
+                merge_key_into(result, k, v);
+            }
+        },
+        _ => *result = None,
+    }
+}
+
+/// Replace the closest nullable parent of a non-null value that resolved to
+/// `null`
+fn null_nullable_parent(value: &mut Value, path: &[PathSegment], types: &[Option<Type>]) {
+    let parent = (0..path.len()).rev()
+        .find(|&i| types.get(i).map_or(true, |t| t.as_ref().map_or(true, |t| !t.is_non_null())));
+
+    match parent {
+        Some(i) => if let Some(slot) = value_at_path(value, &path[..i + 1]) {
+            *slot = Value::null();
+        },
+        None => *value = Value::null(),
+    }
+}
+
+fn value_at_path<'a>(value: &'a mut Value, path: &[PathSegment]) -> Option<&'a mut Value> {
+    match path.split_first() {
+        None => Some(value),
+        Some((&PathSegment::Field(ref name), rest)) => value.as_mut_object_value()
+            .and_then(|o| o.get_mut(name))
+            .and_then(|v| value_at_path(v, rest)),
+        Some((&PathSegment::Index(index), rest)) => match *value {
+            Value::List(ref mut items) => items.get_mut(index).and_then(|v| value_at_path(v, rest)),
+            _ => None,
+        },
+    }
+}
+
+fn unspan_directives(directives: &Option<Vec<Spanning<Directive>>>) -> Option<Vec<Directive>> {
+    directives.as_ref().map(|ds| ds.iter().map(|d| d.item.clone()).collect())
+}
+
+impl<'a, CtxT, SubscriptionT> Iterator for SubscriptionStream<'a, CtxT, SubscriptionT>
+    where SubscriptionT: GraphQLType<CtxT>
+{
+    type Item = (Value, Vec<ExecutionError>);
+
+    fn next(&mut self) -> Option<(Value, Vec<ExecutionError>)> {
+        if let Some(error) = self.source_error.take() {
+            return Some((Value::null(), vec![error]));
+        }
+
+        let event = match self.events {
+            Some(ref mut events) => match events.next() {
+                Some(event) => event,
+                None => return None,
+            },
+            None => return None,
+        };
+
+        let env = OperationEnv {
+            fragments: &self.fragments,
+            variables: &self.variables,
+            schema: self.schema,
+            context: self.context,
+        };
+
+        let mut errors = Vec::new();
+        let mut deferred = Vec::new();
+
+        let mut value = env.resolve_root(
+            &event, self.selection_set.clone(), self.location.clone(), &mut errors, &mut deferred);
+
+        env.resolve_deferred(&mut value, &mut deferred, &mut errors);
+
+        errors.sort();
+
+        Some((value, errors))
+    }
 }
 
 impl<CtxT> Registry<CtxT> {
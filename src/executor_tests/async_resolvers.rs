@@ -0,0 +1,331 @@
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::rc::Rc;
+
+use futures::{future, task, Async, Future, Poll};
+
+use value::Value;
+use schema::model::RootNode;
+use executor::{ExecutionError, FieldError, PathSegment};
+use parser::SourcePosition;
+use types::future::FieldFuture;
+use ::{execute, execute_async};
+
+struct Context {
+    log: Rc<RefCell<Vec<String>>>,
+}
+
+struct Query;
+struct Mutation;
+
+/// Logs when it is first polled and when it completes, yielding once between
+struct Delayed {
+    name: String,
+    log: Rc<RefCell<Vec<String>>>,
+    started: bool,
+}
+
+impl Future for Delayed {
+    type Item = String;
+    type Error = FieldError;
+
+    fn poll(&mut self) -> Poll<String, FieldError> {
+        if !self.started {
+            self.log.borrow_mut().push(format!("start {}", self.name));
+            self.started = true;
+            task::current().notify();
+            return Ok(Async::NotReady);
+        }
+
+        self.log.borrow_mut().push(format!("end {}", self.name));
+        Ok(Async::Ready(self.name.clone()))
+    }
+}
+
+fn delayed(executor: &mut ::Executor<Context>, name: String) -> FieldFuture<String> {
+    FieldFuture::new(Delayed {
+        name: name,
+        log: executor.context().log.clone(),
+        started: false,
+    })
+}
+
+graphql_object!(Query: Context |&self| {
+    field delayed(&mut executor, name: String) -> FieldFuture<String> {
+        delayed(executor, name)
+    }
+
+    field ready(value: String) -> FieldFuture<String> {
+        FieldFuture::new(future::ok(value))
+    }
+
+    field failing() -> FieldFuture<Option<String>> {
+        FieldFuture::new(future::err(FieldError::from("Could not fetch")))
+    }
+
+    field required_failing() -> FieldFuture<String> {
+        FieldFuture::new(future::err(FieldError::from("Could not fetch")))
+    }
+
+    field nested(&mut executor, name: String) -> FieldFuture<Query> {
+        let log = executor.context().log.clone();
+
+        FieldFuture::new(future::lazy(move || {
+            log.borrow_mut().push(format!("nested {}", name));
+            Ok(Query)
+        }))
+    }
+});
+
+graphql_object!(Mutation: Context |&self| {
+    field delayed(&mut executor, name: String) -> FieldFuture<String> {
+        delayed(executor, name)
+    }
+
+    field failing() -> FieldFuture<String> {
+        FieldFuture::new(future::err(FieldError::from("Could not fetch")))
+    }
+});
+
+fn context() -> Context {
+    Context {
+        log: Rc::new(RefCell::new(vec![])),
+    }
+}
+
+#[test]
+fn sibling_futures_run_concurrently() {
+    let schema = RootNode::new(Query, Mutation);
+    let ctx = context();
+
+    let (result, errs) = execute_async(
+        r#"{ a: delayed(name: "a") b: delayed(name: "b") }"#,
+        None, &schema, &HashMap::new(), &ctx)
+        .wait()
+        .expect("Execution failed");
+
+    assert_eq!(errs, []);
+    assert_eq!(
+        result,
+        Value::object(vec![
+            ("a", Value::string("a")),
+            ("b", Value::string("b")),
+        ].into_iter().collect()));
+
+    assert_eq!(*ctx.log.borrow(), ["start a", "start b", "end a", "end b"]);
+}
+
+#[test]
+fn synchronous_execution_blocks_on_each_future() {
+    let schema = RootNode::new(Query, Mutation);
+    let ctx = context();
+
+    let (result, errs) = execute(
+        r#"{ a: delayed(name: "a") b: delayed(name: "b") ready(value: "c") }"#,
+        None, &schema, &HashMap::new(), &ctx)
+        .expect("Execution failed");
+
+    assert_eq!(errs, []);
+    assert_eq!(
+        result,
+        Value::object(vec![
+            ("a", Value::string("a")),
+            ("b", Value::string("b")),
+            ("ready", Value::string("c")),
+        ].into_iter().collect()));
+
+    assert_eq!(*ctx.log.borrow(), ["start a", "end a", "start b", "end b"]);
+}
+
+#[test]
+fn nested_futures_run_level_by_level() {
+    let schema = RootNode::new(Query, Mutation);
+    let ctx = context();
+
+    let (result, errs) = execute_async(
+        r#"{ nested(name: "outer") { delayed(name: "a") nested(name: "inner") { ready(value: "b") } } }"#,
+        None, &schema, &HashMap::new(), &ctx)
+        .wait()
+        .expect("Execution failed");
+
+    assert_eq!(errs, []);
+    assert_eq!(
+        result,
+        Value::object(vec![
+            ("nested", Value::object(vec![
+                ("delayed", Value::string("a")),
+                ("nested", Value::object(vec![
+                    ("ready", Value::string("b")),
+                ].into_iter().collect())),
+            ].into_iter().collect())),
+        ].into_iter().collect()));
+
+    assert_eq!(*ctx.log.borrow(), ["nested outer", "start a", "nested inner", "end a"]);
+}
+
+#[test]
+fn mutation_root_fields_run_serially() {
+    let schema = RootNode::new(Query, Mutation);
+    let ctx = context();
+
+    let (result, errs) = execute_async(
+        r#"mutation { a: delayed(name: "a") ...Rest }
+           fragment Rest on Mutation { b: delayed(name: "b") }"#,
+        None, &schema, &HashMap::new(), &ctx)
+        .wait()
+        .expect("Execution failed");
+
+    assert_eq!(errs, []);
+    assert_eq!(
+        result,
+        Value::object(vec![
+            ("a", Value::string("a")),
+            ("b", Value::string("b")),
+        ].into_iter().collect()));
+
+    assert_eq!(*ctx.log.borrow(), ["start a", "end a", "start b", "end b"]);
+}
+
+#[test]
+fn future_errors_are_reported_with_path() {
+    let schema = RootNode::new(Query, Mutation);
+    let ctx = context();
+
+    let (result, errs) = execute_async(
+        r#"{ ready(value: "a") failing }"#,
+        None, &schema, &HashMap::new(), &ctx)
+        .wait()
+        .expect("Execution failed");
+
+    assert_eq!(
+        result,
+        Value::object(vec![
+            ("ready", Value::string("a")),
+            ("failing", Value::null()),
+        ].into_iter().collect()));
+
+    assert_eq!(errs, vec![
+        ExecutionError::new(
+            SourcePosition::new(20, 0, 20),
+            &[PathSegment::from("failing")],
+            FieldError::from("Could not fetch"),
+        ),
+    ]);
+}
+
+
+#[test]
+fn non_null_future_errors_null_the_parent() {
+    let schema = RootNode::new(Query, Mutation);
+    let ctx = context();
+
+    let (result, errs) = execute_async(
+        r#"{ ready(value: "a") nested(name: "outer") { requiredFailing } }"#,
+        None, &schema, &HashMap::new(), &ctx)
+        .wait()
+        .expect("Execution failed");
+
+    assert_eq!(result, Value::null());
+
+    assert_eq!(errs, vec![
+        ExecutionError::new(
+            SourcePosition::new(44, 0, 44),
+            &[PathSegment::from("nested"), PathSegment::from("requiredFailing")],
+            FieldError::from("Could not fetch"),
+        ),
+    ]);
+}
+
+#[test]
+fn mutation_root_fields_with_the_same_name_run_once() {
+    let schema = RootNode::new(Query, Mutation);
+
+    let ctx = context();
+    let (result, errs) = execute_async(
+        r#"mutation { a: delayed(name: "a") a: delayed(name: "a") }"#,
+        None, &schema, &HashMap::new(), &ctx)
+        .wait()
+        .expect("Execution failed");
+
+    assert_eq!(errs, []);
+    assert_eq!(result, Value::object(vec![("a", Value::string("a"))].into_iter().collect()));
+    assert_eq!(*ctx.log.borrow(), ["start a", "end a"]);
+
+    let ctx = context();
+    let (result, errs) = execute(
+        r#"mutation { a: delayed(name: "a") a: delayed(name: "a") }"#,
+        None, &schema, &HashMap::new(), &ctx)
+        .expect("Execution failed");
+
+    assert_eq!(errs, []);
+    assert_eq!(result, Value::object(vec![("a", Value::string("a"))].into_iter().collect()));
+    assert_eq!(*ctx.log.borrow(), ["start a", "end a"]);
+}
+
+#[test]
+fn mutation_stops_after_a_non_null_root_field_fails() {
+    let schema = RootNode::new(Query, Mutation);
+    let query = r#"mutation { a: delayed(name: "a") failing b: delayed(name: "b") }"#;
+
+    let ctx = context();
+    let (result, errs) = execute_async(query, None, &schema, &HashMap::new(), &ctx)
+        .wait()
+        .expect("Execution failed");
+
+    assert_eq!(result, Value::null());
+    assert_eq!(errs.len(), 1);
+    assert_eq!(*ctx.log.borrow(), ["start a", "end a"]);
+
+    let ctx = context();
+    let (result, errs) = execute(query, None, &schema, &HashMap::new(), &ctx)
+        .expect("Execution failed");
+
+    assert_eq!(result, Value::null());
+    assert_eq!(errs.len(), 1);
+    assert_eq!(*ctx.log.borrow(), ["start a", "end a"]);
+}
+
+#[test]
+fn nothing_is_resolved_before_the_future_is_polled() {
+    let schema = RootNode::new(Query, Mutation);
+    let ctx = context();
+
+    let future = execute_async(
+        r#"{ nested(name: "outer") { ready(value: "a") } }"#,
+        None, &schema, &HashMap::new(), &ctx);
+
+    assert!(ctx.log.borrow().is_empty());
+
+    future.wait().expect("Execution failed");
+
+    assert_eq!(*ctx.log.borrow(), ["nested outer"]);
+}
+
+#[test]
+fn repeated_future_fields_merge_their_selections() {
+    let schema = RootNode::new(Query, Mutation);
+    let doc = r#"{
+        nested(name: "x") { ready(value: "a") }
+        nested(name: "x") { b: ready(value: "b") nested(name: "y") { ready(value: "c") } }
+    }"#;
+
+    let expected = Value::object(vec![
+        ("nested", Value::object(vec![
+            ("ready", Value::string("a")),
+            ("b", Value::string("b")),
+            ("nested", Value::object(vec![
+                ("ready", Value::string("c")),
+            ].into_iter().collect())),
+        ].into_iter().collect())),
+    ].into_iter().collect());
+
+    let ctx = context();
+    assert_eq!(
+        execute(doc, None, &schema, &HashMap::new(), &ctx),
+        Ok((expected.clone(), vec![])));
+
+    let ctx = context();
+    assert_eq!(
+        execute_async(doc, None, &schema, &HashMap::new(), &ctx).wait(),
+        Ok((expected, vec![])));
+}
@@ -1,6 +1,6 @@
 use std::collections::HashMap;
 
-use value::Value;
+use value::{Value, Object};
 use ast::InputValue;
 use schema::model::RootNode;
 
@@ -17,7 +17,7 @@ graphql_object!(TestType: () |&self| {
 });
 
 fn run_variable_query<F>(query: &str, vars: HashMap<String, InputValue>, f: F)
-    where F: Fn(&HashMap<String, Value>) -> ()
+    where F: Fn(&Object) -> ()
 {
     let schema = RootNode::new(TestType, ());
 
@@ -34,7 +34,7 @@ fn run_variable_query<F>(query: &str, vars: HashMap<String, InputValue>, f: F)
 }
 
 fn run_query<F>(query: &str, f: F)
-    where F: Fn(&HashMap<String, Value>) -> ()
+    where F: Fn(&Object) -> ()
 {
     run_variable_query(query, HashMap::new(), f);
 }
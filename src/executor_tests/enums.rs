@@ -1,6 +1,6 @@
 use std::collections::HashMap;
 
-use value::Value;
+use value::{Value, Object};
 use ast::InputValue;
 use schema::model::RootNode;
 use ::GraphQLError::ValidationError;
@@ -28,7 +28,7 @@ graphql_object!(TestType: () |&self| {
 });
 
 fn run_variable_query<F>(query: &str, vars: HashMap<String, InputValue>, f: F)
-    where F: Fn(&HashMap<String, Value>) -> ()
+    where F: Fn(&Object) -> ()
 {
     let schema = RootNode::new(TestType, ());
 
@@ -45,7 +45,7 @@ fn run_variable_query<F>(query: &str, vars: HashMap<String, InputValue>, f: F)
 }
 
 fn run_query<F>(query: &str, f: F)
-    where F: Fn(&HashMap<String, Value>) -> ()
+    where F: Fn(&Object) -> ()
 {
     run_variable_query(query, HashMap::new(), f);
 }
@@ -94,7 +94,7 @@ fn does_not_accept_string_literals() {
 #[test]
 fn accepts_strings_in_variables() {
     run_variable_query(
-        "{ toString(color: RED) }",
+        "query q($color: Color!) { toString(color: $color) }",
         vec![
             ("color".to_owned(), InputValue::string("RED")),
         ].into_iter().collect(),
@@ -105,6 +105,17 @@ fn accepts_strings_in_variables() {
         });
 }
 
+#[test]
+fn accepts_default_values_in_variables() {
+    run_query(
+        "query q($color: Color = GREEN) { toString(color: $color) }",
+        |result| {
+            assert_eq!(
+                result.get("toString"),
+                Some(&Value::string("Color::Green")));
+        });
+}
+
 #[test]
 fn does_not_accept_incorrect_enum_name_in_variables() {
     let schema = RootNode::new(TestType, ());
@@ -147,15 +147,72 @@ mod merge_parallel_fragments {
             Value::object(vec![
                 ("a", Value::string("Apple")),
                 ("b", Value::string("Banana")),
-                ("c", Value::string("Cherry")),
                 ("deep", Value::object(vec![
                     ("b", Value::string("Banana")),
-                    ("c", Value::string("Cherry")),
                     ("deeper", Value::object(vec![
                         ("b", Value::string("Banana")),
                         ("c", Value::string("Cherry")),
                     ].into_iter().collect())),
+                    ("c", Value::string("Cherry")),
+                ].into_iter().collect())),
+                ("c", Value::string("Cherry")),
+            ].into_iter().collect()));
+    }
+}
+
+mod preserves_field_order {
+    use rustc_serialize::json;
+
+    use value::Value;
+    use schema::model::RootNode;
+
+    struct Type;
+
+    graphql_object!(Type: () |&self| {
+        field a() -> &str { "Apple" }
+        field b() -> &str { "Banana" }
+        field c() -> &str { "Cherry" }
+        field deep() -> Type { Type }
+    });
+
+    #[test]
+    fn test() {
+        let schema = RootNode::new(Type, ());
+        let doc = r"
+          { c, deep { b, ...Frag, a }, a, zzz: b }
+          fragment Frag on Type { c, deep { a } }";
+
+        let vars = vec![].into_iter().collect();
+
+        let (result, errs) = ::execute(doc, None, &schema, &vars, &())
+            .expect("Execution failed");
+
+        assert_eq!(errs, []);
+
+        let obj = result.as_object_value().expect("Result is not an object");
+        assert_eq!(obj.keys(), vec!["c", "deep", "a", "zzz"]);
+
+        let deep = obj.get("deep").and_then(|v| v.as_object_value()).expect("deep is not an object");
+        assert_eq!(deep.keys(), vec!["b", "c", "deep", "a"]);
+
+        assert_eq!(
+            json::encode(&result).unwrap(),
+            r#"{"c":"Cherry","deep":{"b":"Banana","c":"Cherry","deep":{"a":"Apple"},"a":"Apple"},"a":"Apple","zzz":"Banana"}"#);
+
+        assert_eq!(
+            result,
+            Value::object(vec![
+                ("c", Value::string("Cherry")),
+                ("deep", Value::object(vec![
+                    ("b", Value::string("Banana")),
+                    ("c", Value::string("Cherry")),
+                    ("deep", Value::object(vec![
+                        ("a", Value::string("Apple")),
+                    ].into_iter().collect())),
+                    ("a", Value::string("Apple")),
                 ].into_iter().collect())),
+                ("a", Value::string("Apple")),
+                ("zzz", Value::string("Banana")),
             ].into_iter().collect()));
     }
 }
@@ -217,22 +274,224 @@ mod nulls_out_errors {
 
         println!("Result: {:?}", result);
 
+        assert_eq!(result, Value::null());
+
+        assert_eq!(
+            errs,
+            vec![
+                ExecutionError::new(
+                    SourcePosition::new(8, 0, 8),
+                    &["syncError"],
+                    "Error for syncError",
+                ),
+            ]);
+    }
+}
+
+mod field_error_extensions {
+    use rustc_serialize::json::{self, Json};
+
+    use value::Value;
+    use schema::model::RootNode;
+    use executor::{ExecutionError, FieldError, FieldResult};
+    use parser::SourcePosition;
+
+    struct Schema;
+
+    graphql_object!(Schema: () |&self| {
+        field not_found() -> Result<Option<&str>, FieldError> {
+            Err(FieldError::new(
+                "Not found",
+                Value::object(vec![
+                    ("code", Value::string("NOT_FOUND")),
+                ].into_iter().collect())))
+        }
+
+        field string_error() -> Result<Option<&str>, String> {
+            Err("String error".to_owned())
+        }
+    });
+
+    #[test]
+    fn test() {
+        let schema = RootNode::new(Schema, ());
+        let doc = r"{ notFound, stringError }";
+
+        let vars = vec![].into_iter().collect();
+
+        let (result, errs) = ::execute(doc, None, &schema, &vars, &())
+            .expect("Execution failed");
+
         assert_eq!(
             result,
             Value::object(vec![
-                ("sync", Value::string("sync")),
-                ("syncError", Value::null()),
+                ("notFound", Value::null()),
+                ("stringError", Value::null()),
             ].into_iter().collect()));
 
         assert_eq!(
             errs,
             vec![
                 ExecutionError::new(
-                    SourcePosition::new(8, 0, 8),
-                    &["syncError"],
-                    "Error for syncError",
+                    SourcePosition::new(2, 0, 2),
+                    &["notFound"],
+                    FieldError::new(
+                        "Not found",
+                        Value::object(vec![
+                            ("code", Value::string("NOT_FOUND")),
+                        ].into_iter().collect())),
+                ),
+                ExecutionError::new(
+                    SourcePosition::new(12, 0, 12),
+                    &["stringError"],
+                    "String error",
                 ),
             ]);
+
+        assert_eq!(
+            Json::from_str(&json::encode(&errs).unwrap()).expect("Invalid JSON"),
+            Json::from_str(r#"[
+                {
+                    "message": "Not found",
+                    "locations": [{"line": 1, "column": 3}],
+                    "path": ["notFound"],
+                    "extensions": {"code": "NOT_FOUND"}
+                },
+                {
+                    "message": "String error",
+                    "locations": [{"line": 1, "column": 13}],
+                    "path": ["stringError"]
+                }
+            ]"#).expect("Invalid JSON constant in test"));
+    }
+}
+
+mod argument_conversion_errors {
+    use value::Value;
+    use schema::meta::MetaType;
+    use schema::model::RootNode;
+    use executor::{Executor, Registry, ExecutionResult, ExecutionError};
+    use types::base::{Arguments, GraphQLType};
+    use parser::SourcePosition;
+
+    struct Schema;
+
+    impl GraphQLType<()> for Schema {
+        fn name() -> Option<&'static str> {
+            Some("Schema")
+        }
+
+        fn meta(registry: &mut Registry<()>) -> MetaType {
+            registry.build_object_type::<Schema>()(&[
+                    registry.field::<Option<String>>("echo")
+                        .argument(registry.arg::<Option<i64>>("value")),
+                ])
+                .into_meta()
+        }
+
+        fn resolve_field(&self, field_name: &str, args: &Arguments, executor: &mut Executor<()>)
+            -> ExecutionResult
+        {
+            assert_eq!(field_name, "echo");
+
+            // Deliberately disagrees with the declared argument type
+            let value: Option<String> = try!(args.try_get("value"));
+
+            executor.resolve(&value)
+        }
+    }
+
+    #[test]
+    fn test() {
+        let schema = RootNode::new(Schema, ());
+        let doc = r"{ echo(value: 5) }";
+
+        let vars = vec![].into_iter().collect();
+
+        let (result, errs) = ::execute(doc, None, &schema, &vars, &())
+            .expect("Execution failed");
+
+        assert_eq!(
+            result,
+            Value::object(vec![
+                ("echo", Value::null()),
+            ].into_iter().collect()));
+
+        assert_eq!(
+            errs,
+            vec![
+                ExecutionError::new(
+                    SourcePosition::new(2, 0, 2),
+                    &["echo"],
+                    r#"Invalid value for argument "value", expected type "Int": Unexpected value 5"#,
+                ),
+            ]);
+    }
+}
+
+mod list_index_paths {
+    use rustc_serialize::json::{self, Json};
+
+    use value::Value;
+    use schema::model::RootNode;
+    use executor::{ExecutionError, FieldResult, PathSegment};
+    use parser::SourcePosition;
+
+    struct Schema;
+    struct Friend(i64);
+
+    graphql_object!(Schema: () |&self| {
+        field friends() -> Vec<Friend> { (0..3).map(Friend).collect() }
+        field friend_slice() -> &[Friend] { &[Friend(0), Friend(1)] }
+    });
+
+    graphql_object!(Friend: () |&self| {
+        field name() -> FieldResult<Option<String>> {
+            if self.0 == 1 {
+                Err("No name".to_owned())
+            }
+            else {
+                Ok(Some(format!("Friend {}", self.0)))
+            }
+        }
+    });
+
+    #[test]
+    fn test() {
+        let schema = RootNode::new(Schema, ());
+        let doc = r"{ friends { name }, slice: friendSlice { name } }";
+
+        let vars = vec![].into_iter().collect();
+
+        let (result, errs) = ::execute(doc, None, &schema, &vars, &())
+            .expect("Execution failed");
+
+        assert_eq!(
+            result.as_object_value().and_then(|o| o.get("friends")),
+            Some(&Value::list(vec![
+                Value::object(vec![("name", Value::string("Friend 0"))].into_iter().collect()),
+                Value::object(vec![("name", Value::null())].into_iter().collect()),
+                Value::object(vec![("name", Value::string("Friend 2"))].into_iter().collect()),
+            ])));
+
+        assert_eq!(
+            errs,
+            vec![
+                ExecutionError::new(
+                    SourcePosition::new(12, 0, 12),
+                    &[PathSegment::from("friends"), PathSegment::Index(1), PathSegment::from("name")],
+                    "No name",
+                ),
+                ExecutionError::new(
+                    SourcePosition::new(41, 0, 41),
+                    &[PathSegment::from("slice"), PathSegment::Index(1), PathSegment::from("name")],
+                    "No name",
+                ),
+            ]);
+
+        assert_eq!(
+            Json::from_str(&json::encode(&errs[0]).unwrap()).expect("Invalid JSON").find("path").cloned(),
+            Some(Json::from_str(r#"["friends", 1, "name"]"#).expect("Invalid JSON constant in test")));
     }
 }
 
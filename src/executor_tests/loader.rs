@@ -0,0 +1,308 @@
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+use value::Value;
+use schema::model::RootNode;
+use executor::{ExecutionError, FieldError, PathSegment};
+use loader::{Loader, LoaderSource, Load};
+use parser::SourcePosition;
+
+struct Person {
+    name: String,
+    friend_ids: Vec<String>,
+}
+
+struct Context {
+    people: Loader<String, Person>,
+}
+
+struct Root;
+
+impl LoaderSource<String, Person> for Context {
+    fn loader(&self) -> &Loader<String, Person> {
+        &self.people
+    }
+}
+
+graphql_object!(Person: Context |&self| {
+    field name() -> &String {
+        &self.name
+    }
+
+    field friends() -> Vec<Load<String, Person>> {
+        self.friend_ids.iter().cloned().map(Load::new).collect()
+    }
+});
+
+graphql_object!(Root: Context |&self| {
+    field people(ids: Vec<String>) -> Vec<Option<Load<String, Person>>> {
+        ids.into_iter().map(|id| Some(Load::new(id))).collect()
+    }
+
+    field person(id: String) -> Option<Load<String, Person>> {
+        Some(Load::new(id))
+    }
+
+    field required_person(id: String) -> Load<String, Person> {
+        Load::new(id)
+    }
+});
+
+fn people() -> HashMap<String, Vec<&'static str>> {
+    vec![
+        ("1", vec!["2", "3"]),
+        ("2", vec!["1"]),
+        ("3", vec!["1", "4"]),
+        ("4", vec![]),
+        ("7", vec!["1", "8"]),
+    ].into_iter().map(|(id, friends)| (id.to_owned(), friends)).collect()
+}
+
+fn context(batches: Arc<Mutex<Vec<Vec<String>>>>) -> Context {
+    Context {
+        people: Loader::new(move |ids: &[String]| {
+            batches.lock().unwrap().push(ids.to_vec());
+
+            let people = people();
+
+            Ok(ids.iter()
+                .filter_map(|id| people.get(id).map(|friends| (id.clone(), Person {
+                    name: format!("Person {}", id),
+                    friend_ids: friends.iter().map(|f| (*f).to_owned()).collect(),
+                })))
+                .collect())
+        }),
+    }
+}
+
+fn person(name: &str, friends: Vec<Value>) -> Value {
+    Value::object(vec![
+        ("name", Value::string(name)),
+        ("friends", Value::list(friends)),
+    ].into_iter().collect())
+}
+
+fn name(name: &str) -> Value {
+    Value::object(vec![
+        ("name", Value::string(name)),
+    ].into_iter().collect())
+}
+
+#[test]
+fn batches_sibling_loads_per_level() {
+    let schema = RootNode::new(Root, ());
+    let batches = Arc::new(Mutex::new(vec![]));
+    let ctx = context(batches.clone());
+
+    let doc = r#"{ people(ids: ["1", "2"]) { name friends { name friends { name } } } }"#;
+    let vars = vec![].into_iter().collect();
+
+    let (result, errs) = ::execute(doc, None, &schema, &vars, &ctx)
+        .expect("Execution failed");
+
+    assert_eq!(errs, []);
+
+    assert_eq!(
+        result,
+        Value::object(vec![
+            ("people", Value::list(vec![
+                person("Person 1", vec![
+                    person("Person 2", vec![name("Person 1")]),
+                    person("Person 3", vec![name("Person 1"), name("Person 4")]),
+                ]),
+                person("Person 2", vec![
+                    person("Person 1", vec![name("Person 2"), name("Person 3")]),
+                ]),
+            ])),
+        ].into_iter().collect()));
+
+    assert_eq!(
+        *batches.lock().unwrap(),
+        vec![
+            vec!["1".to_owned(), "2".to_owned()],
+            vec!["3".to_owned()],
+            vec!["4".to_owned()],
+        ]);
+}
+
+#[test]
+fn missing_keys_resolve_to_null() {
+    let schema = RootNode::new(Root, ());
+    let ctx = context(Arc::new(Mutex::new(vec![])));
+
+    let doc = r#"{ person(id: "5") { name } people(ids: ["4", "6"]) { name } }"#;
+    let vars = vec![].into_iter().collect();
+
+    let (result, errs) = ::execute(doc, None, &schema, &vars, &ctx)
+        .expect("Execution failed");
+
+    assert_eq!(errs, []);
+
+    assert_eq!(
+        result,
+        Value::object(vec![
+            ("person", Value::null()),
+            ("people", Value::list(vec![name("Person 4"), Value::null()])),
+        ].into_iter().collect()));
+}
+
+#[test]
+fn batch_errors_are_reported_for_each_field() {
+    let schema = RootNode::new(Root, ());
+    let ctx = Context {
+        people: Loader::new(|_: &[String]| Err(FieldError::from("Database is down"))),
+    };
+
+    let doc = r#"{ people(ids: ["1", "2"]) { name } }"#;
+    let vars = vec![].into_iter().collect();
+
+    let (result, errs) = ::execute(doc, None, &schema, &vars, &ctx)
+        .expect("Execution failed");
+
+    assert_eq!(
+        result,
+        Value::object(vec![
+            ("people", Value::list(vec![Value::null(), Value::null()])),
+        ].into_iter().collect()));
+
+    assert_eq!(
+        errs,
+        vec![
+            ExecutionError::new(
+                SourcePosition::new(2, 0, 2),
+                &[PathSegment::from("people"), PathSegment::Index(0)],
+                "Database is down",
+            ),
+            ExecutionError::new(
+                SourcePosition::new(2, 0, 2),
+                &[PathSegment::from("people"), PathSegment::Index(1)],
+                "Database is down",
+            ),
+        ]);
+}
+
+#[test]
+fn missing_non_null_keys_null_the_closest_nullable_parent() {
+    let schema = RootNode::new(Root, ());
+    let ctx = context(Arc::new(Mutex::new(vec![])));
+
+    let doc = r#"{ person(id: "7") { name friends { name } } people(ids: ["7", "4"]) { friends { name } } }"#;
+    let vars = vec![].into_iter().collect();
+
+    let (result, errs) = ::execute(doc, None, &schema, &vars, &ctx)
+        .expect("Execution failed");
+
+    assert_eq!(errs, []);
+
+    assert_eq!(
+        result,
+        Value::object(vec![
+            ("person", Value::null()),
+            ("people", Value::list(vec![
+                Value::null(),
+                Value::object(vec![("friends", Value::list(vec![]))].into_iter().collect()),
+            ])),
+        ].into_iter().collect()));
+}
+
+#[test]
+fn missing_non_null_root_keys_null_the_data() {
+    let schema = RootNode::new(Root, ());
+    let ctx = context(Arc::new(Mutex::new(vec![])));
+
+    let doc = r#"{ person(id: "1") { name } requiredPerson(id: "8") { name } }"#;
+    let vars = vec![].into_iter().collect();
+
+    let (result, errs) = ::execute(doc, None, &schema, &vars, &ctx)
+        .expect("Execution failed");
+
+    assert_eq!(errs, []);
+    assert_eq!(result, Value::null());
+}
+
+#[test]
+fn loaded_values_keep_their_type_in_the_schema() {
+    let schema = RootNode::new(Root, ());
+    let ctx = context(Arc::new(Mutex::new(vec![])));
+
+    let doc = r#"{
+        __type(name: "Person") { fields { name type { kind ofType { kind ofType { kind name } } } } }
+    }"#;
+    let vars = vec![].into_iter().collect();
+
+    let (result, errs) = ::execute(doc, None, &schema, &vars, &ctx)
+        .expect("Execution failed");
+
+    assert_eq!(errs, []);
+
+    let friends = result
+        .as_object_value().and_then(|o| o.get("__type"))
+        .and_then(|t| t.as_object_value()).and_then(|t| t.get("fields"))
+        .and_then(|f| f.as_list_value())
+        .and_then(|f| f.iter().find(|f| f.as_object_value().and_then(|f| f.get("name")) == Some(&Value::string("friends"))))
+        .and_then(|f| f.as_object_value()).and_then(|f| f.get("type"))
+        .expect("friends field missing");
+
+    assert_eq!(
+        friends,
+        &Value::object(vec![
+            ("kind", Value::string("NON_NULL")),
+            ("ofType", Value::object(vec![
+                ("kind", Value::string("LIST")),
+                ("ofType", Value::object(vec![
+                    ("kind", Value::string("NON_NULL")),
+                    ("name", Value::null()),
+                ].into_iter().collect())),
+            ].into_iter().collect())),
+        ].into_iter().collect()));
+}
+
+#[test]
+fn repeated_loaded_fields_merge_their_selections() {
+    let schema = RootNode::new(Root, ());
+    let ctx = context(Arc::new(Mutex::new(vec![])));
+
+    let doc = r#"{
+        person(id: "1") { name }
+        person(id: "1") { friends { name } }
+        people(ids: ["2"]) { friends { name } friends { friends { name } } }
+    }"#;
+    let vars = vec![].into_iter().collect();
+
+    let (result, errs) = ::execute(doc, None, &schema, &vars, &ctx)
+        .expect("Execution failed");
+
+    assert_eq!(errs, []);
+
+    assert_eq!(
+        result,
+        Value::object(vec![
+            ("person", person("Person 1", vec![name("Person 2"), name("Person 3")])),
+            ("people", Value::list(vec![
+                Value::object(vec![
+                    ("friends", Value::list(vec![
+                        person("Person 1", vec![name("Person 2"), name("Person 3")]),
+                    ])),
+                ].into_iter().collect()),
+            ])),
+        ].into_iter().collect()));
+}
+
+#[test]
+fn batch_function_can_use_its_loader() {
+    let slot: Arc<Mutex<Option<Arc<Loader<String, String>>>>> = Arc::new(Mutex::new(None));
+    let inner = slot.clone();
+
+    let loader = Arc::new(Loader::new(move |ids: &[String]| {
+        let loader = inner.lock().unwrap().clone().expect("Loader not set");
+        loader.register(&"b".to_owned());
+
+        Ok(ids.iter().map(|id| (id.clone(), id.to_uppercase())).collect())
+    }));
+    *slot.lock().unwrap() = Some(loader.clone());
+
+    assert_eq!(loader.get(&"a".to_owned()), Ok(Some(Arc::new("A".to_owned()))));
+    assert_eq!(loader.get(&"b".to_owned()), Ok(Some(Arc::new("B".to_owned()))));
+
+    *slot.lock().unwrap() = None;
+}
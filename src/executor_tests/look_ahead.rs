@@ -0,0 +1,195 @@
+use std::cell::RefCell;
+
+use ast::InputValue;
+use executor::LookAheadSelection;
+use schema::model::RootNode;
+
+struct Context {
+    look_ahead: RefCell<Vec<LookAheadSelection>>,
+}
+
+struct Root;
+struct Human;
+struct Droid;
+
+graphql_interface!(Character: Context as "Character" |&self| {
+    field id() -> &str {
+        match *self {
+            Character::Human(_) => "1000",
+            Character::Droid(_) => "2000",
+        }
+    }
+
+    field name() -> &str {
+        match *self {
+            Character::Human(_) => "Luke",
+            Character::Droid(_) => "R2-D2",
+        }
+    }
+
+    instance_resolvers: |_| {
+        &Human => match *self { Character::Human(ref h) => Some(h), _ => None },
+        &Droid => match *self { Character::Droid(ref d) => Some(d), _ => None },
+    }
+});
+
+enum Character {
+    Human(Human),
+    Droid(Droid),
+}
+
+graphql_object!(Human: Context |&self| {
+    field id() -> &str { "1000" }
+    field name() -> &str { "Luke" }
+    field home_planet() -> &str { "Tatooine" }
+    field friends(limit: Option<i64>) -> Vec<&Human> { vec![] }
+
+    interfaces: [&Character]
+});
+
+graphql_object!(Droid: Context |&self| {
+    field id() -> &str { "2000" }
+    field name() -> &str { "R2-D2" }
+    field primary_function() -> &str { "Astromech" }
+
+    interfaces: [&Character]
+});
+
+graphql_object!(Root: Context |&self| {
+    field hero(&mut executor) -> Character {
+        *executor.context().look_ahead.borrow_mut() = executor.look_ahead();
+        Character::Human(Human)
+    }
+});
+
+fn look_ahead_of(query: &str, vars: Vec<(&str, InputValue)>) -> Vec<LookAheadSelection> {
+    let schema = RootNode::new(Root, ());
+    let ctx = Context { look_ahead: RefCell::new(vec![]) };
+    let vars = vars.into_iter().map(|(k, v)| (k.to_owned(), v)).collect();
+
+    let (_, errs) = ::execute(query, None, &schema, &vars, &ctx)
+        .expect("Execution failed");
+
+    assert_eq!(errs, []);
+
+    ctx.look_ahead.into_inner()
+}
+
+#[test]
+fn lists_child_fields() {
+    let fields = look_ahead_of("{ hero { id heroName: name } }", vec![]);
+
+    assert_eq!(fields.len(), 2);
+
+    assert_eq!(fields[0].name(), "id");
+    assert_eq!(fields[0].alias(), None);
+    assert_eq!(fields[0].response_name(), "id");
+
+    assert_eq!(fields[1].name(), "name");
+    assert_eq!(fields[1].alias(), Some("heroName"));
+    assert_eq!(fields[1].response_name(), "heroName");
+}
+
+#[test]
+fn resolves_arguments_against_variables() {
+    let fields = look_ahead_of(
+        "query q($limit: Int) { hero { ... on Human { friends(limit: $limit) { name } } } }",
+        vec![("limit", InputValue::int(3))]);
+
+    assert_eq!(fields.len(), 1);
+
+    let friends = &fields[0];
+    assert_eq!(friends.name(), "friends");
+    assert_eq!(friends.argument("limit"), Some(&InputValue::int(3)));
+    assert_eq!(friends.arguments(), &[("limit".to_owned(), InputValue::int(3))]);
+
+    assert_eq!(friends.children().len(), 1);
+    assert_eq!(friends.child("name").map(|c| c.name()), Some("name"));
+    assert_eq!(friends.child("name").and_then(|c| c.type_condition()), None);
+}
+
+#[test]
+fn flattens_fragments_by_type_condition() {
+    let fields = look_ahead_of(
+        r#"
+        {
+            hero {
+                id
+                ... on Human { homePlanet }
+                ... on Droid { primaryFunction ...CharacterFields }
+                ... { heroName: name }
+            }
+        }
+
+        fragment CharacterFields on Character { name }
+        "#,
+        vec![]);
+
+    let summary = fields.iter()
+        .map(|f| (f.response_name(), f.type_condition()))
+        .collect::<Vec<_>>();
+
+    assert_eq!(summary, vec![
+        ("id", None),
+        ("homePlanet", Some("Human")),
+        ("primaryFunction", Some("Droid")),
+        ("name", Some("Character")),
+        ("heroName", None),
+    ]);
+
+    assert!(fields[1].applies_to("Human"));
+    assert!(!fields[1].applies_to("Droid"));
+
+    assert!(fields[3].applies_to("Droid"));
+    assert!(!fields[3].applies_to("Human"));
+
+    assert!(fields[4].applies_to("Human"));
+    assert!(fields[4].applies_to("Droid"));
+}
+
+#[test]
+fn expands_abstract_type_conditions() {
+    let fields = look_ahead_of(
+        r#"
+        { hero { ...CharacterFields } }
+
+        fragment CharacterFields on Character { name }
+        "#,
+        vec![]);
+
+    assert_eq!(fields.len(), 1);
+    assert_eq!(fields[0].type_condition(), Some("Character"));
+    assert!(fields[0].applies_to("Human"));
+    assert!(fields[0].applies_to("Droid"));
+}
+
+#[test]
+fn merges_repeated_fields() {
+    let fields = look_ahead_of(
+        "{ hero { ... on Human { friends { id } } ... on Human { friends { name } } } }",
+        vec![]);
+
+    assert_eq!(fields.len(), 1);
+
+    let children = fields[0].children().iter().map(|c| c.name()).collect::<Vec<_>>();
+    assert_eq!(children, vec!["id", "name"]);
+}
+
+#[test]
+fn drops_excluded_fields() {
+    let fields = look_ahead_of(
+        r#"
+        query q($skip: Boolean!) {
+            hero {
+                id @include(if: false)
+                name @skip(if: $skip)
+                ... on Human @skip(if: true) { homePlanet }
+            }
+        }
+        "#,
+        vec![("skip", InputValue::boolean(false))]);
+
+    let names = fields.iter().map(|f| f.name()).collect::<Vec<_>>();
+
+    assert_eq!(names, vec!["name"]);
+}
@@ -4,3 +4,9 @@ mod enums;
 mod directives;
 mod executor;
 mod interfaces_unions;
+mod subscriptions;
+mod null_propagation;
+mod look_ahead;
+mod loader;
+mod async_resolvers;
+mod parallel;
@@ -0,0 +1,200 @@
+use std::collections::HashMap;
+
+use value::Value;
+use schema::model::RootNode;
+use executor::{ExecutionError, FieldResult, PathSegment};
+use parser::SourcePosition;
+
+struct Root;
+struct Inner;
+
+graphql_object!(Root: () |&self| {
+    field nullable_obj() -> Option<Inner> { Some(Inner) }
+    field non_null_obj() -> Inner { Inner }
+    field nullable_list() -> Option<Vec<Inner>> { Some(vec![Inner, Inner]) }
+    field non_null_list() -> Vec<Inner> { vec![Inner, Inner] }
+    field list_of_nullable() -> Vec<Option<Inner>> { vec![Some(Inner), None] }
+    field nested_lists() -> Option<Vec<Option<Vec<Inner>>>> { Some(vec![Some(vec![Inner]), None]) }
+});
+
+graphql_object!(Inner: () |&self| {
+    field value() -> &str { "value" }
+    field nullable_error() -> FieldResult<Option<&str>> { Err("Nullable error".to_owned()) }
+    field non_null_error() -> FieldResult<&str> { Err("Non-null error".to_owned()) }
+    field non_null_inner() -> Inner { Inner }
+    field nullable_inner() -> Option<Inner> { Some(Inner) }
+});
+
+fn run_query(query: &str) -> (Value, Vec<ExecutionError>) {
+    let schema = RootNode::new(Root, ());
+    let vars = HashMap::new();
+
+    ::execute(query, None, &schema, &vars, &()).expect("Execution failed")
+}
+
+fn error<P: Into<PathSegment> + Clone>(pos: usize, path: &[P], message: &str) -> ExecutionError {
+    ExecutionError::new(SourcePosition::new(pos, 0, pos), path, message)
+}
+
+#[test]
+fn nullable_field_error_keeps_parent() {
+    let (result, errs) = run_query("{ nullableObj { value nullableError } }");
+
+    assert_eq!(
+        result,
+        Value::object(vec![
+            ("nullableObj", Value::object(vec![
+                ("value", Value::string("value")),
+                ("nullableError", Value::null()),
+            ].into_iter().collect())),
+        ].into_iter().collect()));
+
+    assert_eq!(errs, vec![
+        error(22, &["nullableObj", "nullableError"], "Nullable error"),
+    ]);
+}
+
+#[test]
+fn non_null_field_error_nulls_nullable_parent() {
+    let (result, errs) = run_query("{ value: nullableObj { value } nullableObj { value nonNullError } }");
+
+    assert_eq!(
+        result,
+        Value::object(vec![
+            ("value", Value::object(vec![
+                ("value", Value::string("value")),
+            ].into_iter().collect())),
+            ("nullableObj", Value::null()),
+        ].into_iter().collect()));
+
+    assert_eq!(errs, vec![
+        error(51, &["nullableObj", "nonNullError"], "Non-null error"),
+    ]);
+}
+
+#[test]
+fn non_null_field_error_in_inline_fragment_nulls_nullable_parent() {
+    let (result, errs) = run_query("{ nullableObj { value ... on Inner { nonNullError } } }");
+
+    assert_eq!(
+        result,
+        Value::object(vec![
+            ("nullableObj", Value::null()),
+        ].into_iter().collect()));
+
+    assert_eq!(errs, vec![
+        error(37, &["nullableObj", "nonNullError"], "Non-null error"),
+    ]);
+}
+
+#[test]
+fn non_null_field_error_propagates_through_non_null_objects() {
+    let (result, errs) = run_query(
+        "{ nullableObj { nullableInner { nonNullInner { nonNullInner { nonNullError } } } } }");
+
+    assert_eq!(
+        result,
+        Value::object(vec![
+            ("nullableObj", Value::object(vec![
+                ("nullableInner", Value::null()),
+            ].into_iter().collect())),
+        ].into_iter().collect()));
+
+    assert_eq!(errs, vec![
+        error(62, &["nullableObj", "nullableInner", "nonNullInner", "nonNullInner", "nonNullError"],
+              "Non-null error"),
+    ]);
+}
+
+#[test]
+fn non_null_field_error_nulls_root() {
+    let (result, errs) = run_query("{ nullableObj { value } nonNullObj { nonNullInner { nonNullError } } }");
+
+    assert_eq!(result, Value::null());
+
+    assert_eq!(errs, vec![
+        error(52, &["nonNullObj", "nonNullInner", "nonNullError"], "Non-null error"),
+    ]);
+}
+
+#[test]
+fn error_in_list_of_non_null_items_nulls_nullable_list() {
+    let (result, errs) = run_query("{ nullableList { value nonNullError } }");
+
+    assert_eq!(
+        result,
+        Value::object(vec![
+            ("nullableList", Value::null()),
+        ].into_iter().collect()));
+
+    assert_eq!(errs, vec![
+        error(23, &[PathSegment::from("nullableList"), PathSegment::Index(0), PathSegment::from("nonNullError")],
+              "Non-null error"),
+        error(23, &[PathSegment::from("nullableList"), PathSegment::Index(1), PathSegment::from("nonNullError")],
+              "Non-null error"),
+    ]);
+}
+
+#[test]
+fn error_in_non_null_list_nulls_root() {
+    let (result, errs) = run_query("{ nonNullList { nonNullError } }");
+
+    assert_eq!(result, Value::null());
+    assert_eq!(errs.len(), 2);
+}
+
+#[test]
+fn error_in_list_of_nullable_items_nulls_item() {
+    let (result, errs) = run_query("{ listOfNullable { value nonNullError } }");
+
+    assert_eq!(
+        result,
+        Value::object(vec![
+            ("listOfNullable", Value::list(vec![
+                Value::null(),
+                Value::null(),
+            ])),
+        ].into_iter().collect()));
+
+    assert_eq!(errs, vec![
+        error(25, &[PathSegment::from("listOfNullable"), PathSegment::Index(0), PathSegment::from("nonNullError")],
+              "Non-null error"),
+    ]);
+}
+
+#[test]
+fn error_in_nested_lists_nulls_inner_list() {
+    let (result, errs) = run_query("{ nestedLists { value } }");
+
+    assert_eq!(
+        result,
+        Value::object(vec![
+            ("nestedLists", Value::list(vec![
+                Value::list(vec![
+                    Value::object(vec![
+                        ("value", Value::string("value")),
+                    ].into_iter().collect()),
+                ]),
+                Value::null(),
+            ])),
+        ].into_iter().collect()));
+
+    assert_eq!(errs, vec![]);
+
+    let (result, errs) = run_query("{ nestedLists { nonNullError } }");
+
+    assert_eq!(
+        result,
+        Value::object(vec![
+            ("nestedLists", Value::list(vec![
+                Value::null(),
+                Value::null(),
+            ])),
+        ].into_iter().collect()));
+
+    assert_eq!(errs, vec![
+        error(16, &[PathSegment::from("nestedLists"), PathSegment::Index(0), PathSegment::Index(0),
+                    PathSegment::from("nonNullError")],
+              "Non-null error"),
+    ]);
+}
@@ -0,0 +1,230 @@
+use std::collections::HashMap;
+use std::sync::{Condvar, Mutex};
+use std::time::Duration;
+
+use rayon::{Configuration, ThreadPool};
+
+use value::Value;
+use schema::model::RootNode;
+use executor::{ExecutionError, FieldError, FieldResult, PathSegment};
+use parser::SourcePosition;
+use ::execute_parallel;
+
+/// Lets fields wait until a number of them are being resolved at once
+struct Context {
+    arrived: Mutex<usize>,
+    all_arrived: Condvar,
+    log: Mutex<Vec<String>>,
+}
+
+struct Query;
+struct Mutation;
+
+impl Context {
+    fn new() -> Context {
+        Context {
+            arrived: Mutex::new(0),
+            all_arrived: Condvar::new(),
+            log: Mutex::new(vec![]),
+        }
+    }
+
+    fn meet(&self, count: i64) -> bool {
+        let mut arrived = self.arrived.lock().unwrap();
+        *arrived += 1;
+        self.all_arrived.notify_all();
+
+        while *arrived < count as usize {
+            let (guard, timeout) = self.all_arrived
+                .wait_timeout(arrived, Duration::from_secs(5))
+                .unwrap();
+            arrived = guard;
+
+            if timeout.timed_out() {
+                return false;
+            }
+        }
+
+        true
+    }
+}
+
+graphql_object!(Query: Context |&self| {
+    field meet(&mut executor, count: i64) -> bool {
+        executor.context().meet(count)
+    }
+
+    field failing(message: String) -> FieldResult<Option<String>> {
+        Err(message)
+    }
+
+    field required() -> FieldResult<String> {
+        Err("Required field failed".to_owned())
+    }
+
+    field name() -> &str {
+        "query"
+    }
+
+    field nested() -> Query {
+        Query
+    }
+
+    field logged(&mut executor) -> Query {
+        executor.context().log.lock().unwrap().push("logged".to_owned());
+        Query
+    }
+});
+
+graphql_object!(Mutation: Context |&self| {
+    field append(&mut executor, value: String) -> Vec<String> {
+        let mut log = executor.context().log.lock().unwrap();
+        log.push(value);
+        log.clone()
+    }
+});
+
+fn run(query: &str) -> (Value, Vec<ExecutionError>) {
+    let pool = ThreadPool::new(Configuration::new().num_threads(2))
+        .expect("Could not create thread pool");
+    let schema = RootNode::new(Query, Mutation);
+    let ctx = Context::new();
+
+    pool.install(|| execute_parallel(query, None, &schema, &HashMap::new(), &ctx))
+        .expect("Execution failed")
+}
+
+#[test]
+fn root_fields_are_resolved_concurrently() {
+    let (result, errs) = run(r#"{ a: meet(count: 2) b: meet(count: 2) }"#);
+
+    assert_eq!(errs, []);
+    assert_eq!(
+        result,
+        Value::object(vec![
+            ("a", Value::boolean(true)),
+            ("b", Value::boolean(true)),
+        ].into_iter().collect()));
+}
+
+#[test]
+fn fragments_and_duplicate_fields_are_merged_in_order() {
+    let (result, errs) = run(r#"
+        {
+            first: nested { a: nested { name } }
+            ... on Query { first: nested { b: name } }
+            ...Last
+        }
+        fragment Last on Query { last: name }"#);
+
+    assert_eq!(errs, []);
+    assert_eq!(
+        result,
+        Value::object(vec![
+            ("first", Value::object(vec![
+                ("a", Value::object(vec![
+                    ("name", Value::string("query")),
+                ].into_iter().collect())),
+                ("b", Value::string("query")),
+            ].into_iter().collect())),
+            ("last", Value::string("query")),
+        ].into_iter().collect()));
+}
+
+#[test]
+fn root_fields_with_the_same_response_name_are_resolved_once() {
+    let schema = RootNode::new(Query, Mutation);
+    let ctx = Context::new();
+
+    let (result, errs) = execute_parallel(
+        r#"{ a: logged { name } ... on Query { a: logged { nested { name } } } }"#,
+        None, &schema, &HashMap::new(), &ctx)
+        .expect("Execution failed");
+
+    assert_eq!(errs, []);
+    assert_eq!(
+        result,
+        Value::object(vec![
+            ("a", Value::object(vec![
+                ("name", Value::string("query")),
+                ("nested", Value::object(vec![
+                    ("name", Value::string("query")),
+                ].into_iter().collect())),
+            ].into_iter().collect())),
+        ].into_iter().collect()));
+
+    assert_eq!(*ctx.log.lock().unwrap(), ["logged"]);
+}
+
+#[test]
+fn errors_from_all_fields_are_collected_and_sorted() {
+    let (result, errs) = run(r#"{ b: failing(message: "b") a: failing(message: "a") }"#);
+
+    assert_eq!(
+        result,
+        Value::object(vec![
+            ("b", Value::null()),
+            ("a", Value::null()),
+        ].into_iter().collect()));
+
+    assert_eq!(errs, vec![
+        ExecutionError::new(
+            SourcePosition::new(2, 0, 2),
+            &[PathSegment::from("b")],
+            FieldError::from("b"),
+        ),
+        ExecutionError::new(
+            SourcePosition::new(27, 0, 27),
+            &[PathSegment::from("a")],
+            FieldError::from("a"),
+        ),
+    ]);
+}
+
+#[test]
+fn non_null_root_field_errors_null_the_data() {
+    let (result, errs) = run(r#"{ nested { name } required }"#);
+
+    assert_eq!(result, Value::null());
+
+    assert_eq!(errs, vec![
+        ExecutionError::new(
+            SourcePosition::new(18, 0, 18),
+            &[PathSegment::from("required")],
+            FieldError::from("Required field failed"),
+        ),
+    ]);
+}
+
+#[test]
+fn introspection_works() {
+    let (result, errs) = run(r#"{ __schema { queryType { name } } __type(name: "Mutation") { name } }"#);
+
+    assert_eq!(errs, []);
+    assert_eq!(
+        result,
+        Value::object(vec![
+            ("__schema", Value::object(vec![
+                ("queryType", Value::object(vec![
+                    ("name", Value::string("Query")),
+                ].into_iter().collect())),
+            ].into_iter().collect())),
+            ("__type", Value::object(vec![
+                ("name", Value::string("Mutation")),
+            ].into_iter().collect())),
+        ].into_iter().collect()));
+}
+
+#[test]
+fn mutation_root_fields_are_resolved_serially() {
+    let (result, errs) = run(r#"mutation { a: append(value: "a") b: append(value: "b") c: append(value: "c") }"#);
+
+    assert_eq!(errs, []);
+    assert_eq!(
+        result,
+        Value::object(vec![
+            ("a", Value::list(vec![Value::string("a")])),
+            ("b", Value::list(vec![Value::string("a"), Value::string("b")])),
+            ("c", Value::list(vec![Value::string("a"), Value::string("b"), Value::string("c")])),
+        ].into_iter().collect()));
+}
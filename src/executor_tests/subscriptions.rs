@@ -0,0 +1,238 @@
+use std::collections::HashMap;
+
+use value::Value;
+use ast::InputValue;
+use schema::model::RootNode;
+use types::base::{Arguments, GraphQLSubscriptionType};
+use executor::{ExecutionError, FieldResult, PathSegment};
+use parser::SourcePosition;
+use ::GraphQLError;
+
+struct Inbox {
+    emails: Vec<String>,
+}
+
+struct Query;
+
+struct Subscription {
+    email: Option<String>,
+}
+
+graphql_object!(Query: Inbox |&self| {
+    field count(&mut executor) -> i64 {
+        executor.context().emails.len() as i64
+    }
+});
+
+graphql_object!(Subscription: Inbox |&self| {
+    field important_email(prefix = ("".to_owned()): String) -> Option<String> {
+        self.email.as_ref().map(|e| format!("{}{}", prefix, e))
+    }
+
+    field failing() -> FieldResult<Option<String>> {
+        Err("Can not resolve event".to_owned())
+    }
+});
+
+impl GraphQLSubscriptionType<Inbox> for Subscription {
+    fn subscribe<'a>(&'a self, field_name: &str, args: &Arguments, context: &'a Inbox)
+        -> FieldResult<Box<Iterator<Item = Subscription> + 'a>>
+    {
+        match field_name {
+            "importantEmail" => {
+                let prefix: String = try!(args.try_get("prefix"));
+                if prefix == "fail" {
+                    return Err("Can not subscribe".to_owned());
+                }
+
+                Ok(Box::new(context.emails.iter().map(|e| Subscription { email: Some(e.clone()) })))
+            }
+            "failing" => Ok(Box::new(vec![Subscription { email: None }].into_iter())),
+            _ => Err(format!("Unknown subscription field {}", field_name)),
+        }
+    }
+}
+
+fn inbox() -> Inbox {
+    Inbox {
+        emails: vec!["one".to_owned(), "two".to_owned()],
+    }
+}
+
+fn run_subscription(query: &str, vars: HashMap<String, InputValue>) -> Vec<(Value, Vec<ExecutionError>)> {
+    let schema = RootNode::new_with_subscription(Query, (), Subscription { email: None });
+    let context = inbox();
+
+    let stream = ::subscribe(query, None, &schema, &vars, &context)
+        .expect("Subscription failed");
+
+    stream.collect()
+}
+
+#[test]
+fn yields_one_response_per_event() {
+    let responses = run_subscription(
+        "subscription { importantEmail }",
+        HashMap::new());
+
+    assert_eq!(
+        responses,
+        vec![
+            (Value::object(vec![
+                ("importantEmail", Value::string("one")),
+            ].into_iter().collect()), vec![]),
+            (Value::object(vec![
+                ("importantEmail", Value::string("two")),
+            ].into_iter().collect()), vec![]),
+        ]);
+}
+
+#[test]
+fn passes_arguments_and_variables_to_source_and_resolver() {
+    let responses = run_subscription(
+        "subscription Emails($prefix: String!) { mail: importantEmail(prefix: $prefix) }",
+        vec![
+            ("prefix".to_owned(), InputValue::string("re: ")),
+        ].into_iter().collect());
+
+    assert_eq!(
+        responses,
+        vec![
+            (Value::object(vec![
+                ("mail", Value::string("re: one")),
+            ].into_iter().collect()), vec![]),
+            (Value::object(vec![
+                ("mail", Value::string("re: two")),
+            ].into_iter().collect()), vec![]),
+        ]);
+}
+
+#[test]
+fn finds_root_field_through_fragments() {
+    let responses = run_subscription(
+        "subscription { ...EmailFields } fragment EmailFields on Subscription { importantEmail }",
+        HashMap::new());
+
+    assert_eq!(responses.len(), 2);
+    assert_eq!(
+        responses[1].0,
+        Value::object(vec![
+            ("importantEmail", Value::string("two")),
+        ].into_iter().collect()));
+}
+
+#[test]
+fn source_stream_errors_yield_a_single_response() {
+    let responses = run_subscription(
+        r#"subscription { importantEmail(prefix: "fail") }"#,
+        HashMap::new());
+
+    assert_eq!(
+        responses,
+        vec![
+            (Value::null(), vec![
+                ExecutionError::new(
+                    SourcePosition::new(15, 0, 15),
+                    &["importantEmail"],
+                    "Can not subscribe",
+                ),
+            ]),
+        ]);
+}
+
+#[test]
+fn event_resolution_errors_are_reported_per_event() {
+    let responses = run_subscription("subscription { failing }", HashMap::new());
+
+    assert_eq!(
+        responses,
+        vec![
+            (Value::object(vec![
+                ("failing", Value::null()),
+            ].into_iter().collect()), vec![
+                ExecutionError::new(
+                    SourcePosition::new(15, 0, 15),
+                    &["failing"],
+                    "Can not resolve event",
+                ),
+            ]),
+        ]);
+}
+
+#[test]
+fn execute_rejects_subscription_operations() {
+    let schema = RootNode::new_with_subscription(Query, (), Subscription { email: None });
+    let vars = HashMap::new();
+
+    assert_eq!(
+        ::execute("subscription { importantEmail }", None, &schema, &vars, &inbox()),
+        Err(GraphQLError::IsSubscription));
+}
+
+#[test]
+fn subscribe_rejects_query_operations() {
+    let schema = RootNode::new_with_subscription(Query, (), Subscription { email: None });
+    let vars = HashMap::new();
+    let context = inbox();
+
+    assert!(match ::subscribe("{ count }", None, &schema, &vars, &context) {
+        Err(GraphQLError::NotSubscription) => true,
+        _ => false,
+    });
+}
+
+#[test]
+fn validates_single_root_field() {
+    let schema = RootNode::new_with_subscription(Query, (), Subscription { email: None });
+    let vars = HashMap::new();
+    let context = inbox();
+
+    assert!(match ::subscribe("subscription { importantEmail failing }", None, &schema, &vars, &context) {
+        Err(GraphQLError::ValidationError(_)) => true,
+        _ => false,
+    });
+}
+
+#[test]
+fn schema_without_subscriptions() {
+    let schema = RootNode::new(Query, ());
+    let vars = HashMap::new();
+    let context = inbox();
+
+    let responses = ::subscribe("subscription { count }", None, &schema, &vars, &context)
+        .expect("Subscription failed")
+        .collect::<Vec<_>>();
+
+    assert_eq!(
+        responses,
+        vec![
+            (Value::null(), vec![
+                ExecutionError::new(
+                    SourcePosition::new(0, 0, 0),
+                    &[] as &[PathSegment],
+                    "Schema is not configured for subscriptions",
+                ),
+            ]),
+        ]);
+}
+
+#[test]
+fn introspects_subscription_type() {
+    let schema = RootNode::new_with_subscription(Query, (), Subscription { email: None });
+    let vars = HashMap::new();
+
+    let (result, errs) = ::execute(
+        "{ __schema { subscriptionType { name } } }", None, &schema, &vars, &inbox())
+        .expect("Execution failed");
+
+    assert_eq!(errs, []);
+    assert_eq!(
+        result,
+        Value::object(vec![
+            ("__schema", Value::object(vec![
+                ("subscriptionType", Value::object(vec![
+                    ("name", Value::string("Subscription")),
+                ].into_iter().collect())),
+            ].into_iter().collect())),
+        ].into_iter().collect()));
+}
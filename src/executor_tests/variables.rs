@@ -1,6 +1,6 @@
 use std::collections::HashMap;
 
-use value::Value;
+use value::{Value, Object};
 use ast::InputValue;
 use schema::model::RootNode;
 use ::GraphQLError::ValidationError;
@@ -83,10 +83,18 @@ graphql_object!(TestType: () |&self| {
     field nn_list_nn(input: Vec<String>) -> String {
         format!("{:?}", input)
     }
+
+    field int_input(input: Option<i64>) -> String {
+        format!("{:?}", input)
+    }
+
+    field float_input(input: Option<f64>) -> String {
+        format!("{:?}", input)
+    }
 });
 
 fn run_variable_query<F>(query: &str, vars: HashMap<String, InputValue>, f: F)
-    where F: Fn(&HashMap<String, Value>) -> ()
+    where F: Fn(&Object) -> ()
 {
     let schema = RootNode::new(TestType, ());
 
@@ -103,7 +111,7 @@ fn run_variable_query<F>(query: &str, vars: HashMap<String, InputValue>, f: F)
 }
 
 fn run_query<F>(query: &str, f: F)
-    where F: Fn(&HashMap<String, Value>) -> ()
+    where F: Fn(&Object) -> ()
 {
     run_variable_query(query, HashMap::new(), f);
 }
@@ -308,7 +316,7 @@ fn variable_error_on_additional_field() {
 
     assert_eq!(error, ValidationError(vec![
         RuleError::new(
-            r#"Variable "$input" got invalid value. In field "extra": Unknown field."#,
+            r#"Variable "$input" got invalid value. In field "extra": Unknown field "extra"."#,
             &[SourcePosition::new(8, 0, 8)],
         ),
     ]));
@@ -735,3 +743,124 @@ fn default_argument_when_nullable_variable_set_to_null() {
                 Some(&Value::string(r#""Hello World""#)));
         });
 }
+
+#[test]
+fn default_variable_value_when_not_provided() {
+    run_query(
+        r#"query q($input: String = "Default") { fieldWithNullableStringInput(input: $input) }"#,
+        |result| {
+            assert_eq!(
+                result.get("fieldWithNullableStringInput"),
+                Some(&Value::string(r#"Some("Default")"#)));
+        });
+}
+
+#[test]
+fn default_variable_value_not_used_when_set_to_null() {
+    run_variable_query(
+        r#"query q($input: Int = 10) { intInput(input: $input) }"#,
+        vec![
+            ("input".to_owned(), InputValue::null()),
+        ].into_iter().collect(),
+        |result| {
+            assert_eq!(
+                result.get("intInput"),
+                Some(&Value::string("None")));
+        });
+}
+
+#[test]
+fn default_variable_value_not_used_when_provided() {
+    run_variable_query(
+        r#"query q($input: Int = 10) { intInput(input: $input) }"#,
+        vec![
+            ("input".to_owned(), InputValue::int(20)),
+        ].into_iter().collect(),
+        |result| {
+            assert_eq!(
+                result.get("intInput"),
+                Some(&Value::string("Some(20)")));
+        });
+}
+
+#[test]
+fn default_variable_value_of_complex_input() {
+    run_query(
+        r#"query q($input: TestInputObject = {a: "foo", b: "bar", c: "baz"}) { fieldWithObjectInput(input: $input) }"#,
+        |result| {
+            assert_eq!(
+                result.get("fieldWithObjectInput"),
+                Some(&Value::string(r#"Some(TestInputObject { a: Some("foo"), b: Some([Some("bar")]), c: "baz", d: None })"#)));
+        });
+}
+
+#[test]
+fn variable_coerces_int_to_float() {
+    run_variable_query(
+        r#"query q($input: Float) { floatInput(input: $input) }"#,
+        vec![
+            ("input".to_owned(), InputValue::int(3)),
+        ].into_iter().collect(),
+        |result| {
+            assert_eq!(
+                result.get("floatInput"),
+                Some(&Value::string("Some(3.0)")));
+        });
+}
+
+#[test]
+fn variable_coerces_single_value_to_list() {
+    run_variable_query(
+        r#"query q($input: [String]) { list(input: $input) }"#,
+        vec![
+            ("input".to_owned(), InputValue::string("A")),
+        ].into_iter().collect(),
+        |result| {
+            assert_eq!(
+                result.get("list"),
+                Some(&Value::string(r#"Some([Some("A")])"#)));
+        });
+}
+
+#[test]
+fn variable_error_on_invalid_scalar_value() {
+    let schema = RootNode::new(TestType, ());
+
+    let query = r#"query q($input: Int) { intInput(input: $input) }"#;
+    let vars = vec![
+        ("input".to_owned(), InputValue::string("ten")),
+    ].into_iter().collect();
+
+    let error = ::execute(query, None, &schema, &vars, &())
+        .unwrap_err();
+
+    assert_eq!(error, ValidationError(vec![
+        RuleError::new(
+            r#"Variable "$input" got invalid value. Expected "Int", found "ten"."#,
+            &[SourcePosition::new(8, 0, 8)],
+        ),
+    ]));
+}
+
+#[test]
+fn variable_error_on_invalid_nested_scalar_value() {
+    let schema = RootNode::new(TestType, ());
+
+    let query = r#"query q($input: TestInputObject) { fieldWithObjectInput(input: $input) }"#;
+    let vars = vec![
+        ("input".to_owned(), InputValue::object(vec![
+            ("c", InputValue::string("foo")),
+            ("d", InputValue::string("NotSerializedValue")),
+        ].into_iter().collect())),
+    ].into_iter().collect();
+
+    let error = ::execute(query, None, &schema, &vars, &())
+        .unwrap_err();
+
+    assert_eq!(error, ValidationError(vec![
+        RuleError::new(
+            r#"Variable "$input" got invalid value. In field "d": Expected "TestComplexScalar", found "NotSerializedValue"."#,
+            &[SourcePosition::new(8, 0, 8)],
+        ),
+    ]));
+}
@@ -3,25 +3,55 @@
 use iron::prelude::*;
 use iron::middleware::Handler;
 use iron::mime::Mime;
+use iron::modifiers::Header;
+use iron::headers;
 use iron::status;
 use iron::method;
 
-use std::collections::{HashMap, BTreeMap};
+use std::collections::HashMap;
+use std::sync::Arc;
 
-use rustc_serialize::json::{ToJson, Json};
+use rustc_serialize::{Encodable, Encoder};
+use rustc_serialize::json::{as_pretty_json, ToJson, Json, Object};
 
-use ::{InputValue, GraphQLType, RootNode, execute};
+use ::{InputValue, GraphQLType, GraphQLError, RootNode, Value, ExecutionError, PersistedQueries,
+       PreparedQuery, Document, Definition, OperationType, query_hash};
 
 /// Handler that executes GraphQL queries in the given schema
 ///
 /// The handler responds to GET requests and POST requests only. In GET
 /// requests, the query should be supplied in the `query` URL parameter, e.g.
-/// `http://localhost:3000/graphql?query={hero{name}}`.
+/// `http://localhost:3000/graphql?query={hero{name}}`, and optionally the
+/// variables as a JSON object in the `variables` parameter. Mutations can not
+/// be executed over GET and are answered with 405 Method Not Allowed.
 ///
-/// POST requests support both queries and variables. POST a JSON document to
-/// this endpoint containing the field `"query"` and optionally `"variables"`.
-/// The variables should be a JSON object containing the variable to value
-/// mapping.
+/// Variables that are not a JSON object or `null` are answered with 400 Bad
+/// Request.
+///
+/// POST a JSON document to this endpoint containing the field `"query"` and
+/// optionally `"variables"`. The variables should be a JSON object containing
+/// the variable to value mapping.
+///
+/// Documents with several operations need the name of the operation to
+/// execute, in the `operationName` URL parameter or field.
+///
+/// Several requests can be sent at once by POSTing a JSON array of request
+/// objects. The response is then an array with the result of each request,
+/// in the same order; entries that are not valid requests get an error
+/// object in their place. Empty batches are rejected. See
+/// `with_shared_batch_context` and `with_max_batch_size` to configure
+/// batches.
+///
+/// Queries from the persisted query store can be executed by sending their
+/// id in the `id` field, or their SHA-256 hash in
+/// `extensions.persistedQuery.sha256Hash`, instead of the query itself. In
+/// GET requests, `extensions` is a JSON encoded URL parameter. See
+/// `with_persisted_queries` to set up a store.
+///
+/// This supports the automatic persisted query protocol: a hash that is not
+/// in the store is answered with a `PersistedQueryNotFound` error, after
+/// which the client sends the hash along with the query. The handler checks
+/// that the hash matches the query and stores the query under it.
 pub struct GraphQLHandler<CtxFactory, Query, Mutation, CtxT>
     where CtxFactory: Fn(&mut Request) -> CtxT + Send + Sync + 'static,
           CtxT: Send + Sync + 'static,
@@ -30,6 +60,8 @@ pub struct GraphQLHandler<CtxFactory, Query, Mutation, CtxT>
 {
     context_factory: CtxFactory,
     root_node: RootNode<CtxT, Query, Mutation>,
+    shared_batch_context: bool,
+    max_batch_size: Option<usize>,
 }
 
 /// Handler that renders GraphiQL - a graphical query editor interface
@@ -37,6 +69,20 @@ pub struct GraphiQLHandler {
     graphql_url: String,
 }
 
+/// Where the query of a request comes from
+enum QuerySource {
+    /// A query, with the hash the client claims it has
+    Query(String, Option<String>),
+    Persisted(String),
+}
+
+/// A single request, read from the URL parameters or a JSON object
+struct GraphQLRequest {
+    source: QuerySource,
+    operation_name: Option<String>,
+    variables: HashMap<String, InputValue>,
+}
+
 impl<CtxFactory, Query, Mutation, CtxT>
     GraphQLHandler<CtxFactory, Query, Mutation, CtxT>
     where CtxFactory: Fn(&mut Request) -> CtxT + Send + Sync + 'static,
@@ -54,82 +100,376 @@ impl<CtxFactory, Query, Mutation, CtxT>
         GraphQLHandler {
             context_factory: context_factory,
             root_node: RootNode::new(query, mutation),
+            shared_batch_context: false,
+            max_batch_size: None,
         }
     }
 
+    /// Keep the executed queries in a persisted query store
+    ///
+    /// See `RootNode::with_persisted_queries` for how the store is used.
+    pub fn with_persisted_queries(self, persisted_queries: PersistedQueries) -> Self {
+        GraphQLHandler {
+            root_node: self.root_node.with_persisted_queries(persisted_queries),
+            ..self
+        }
+    }
+
+    /// Execute all requests of a batch with a single context
+    ///
+    /// By default, the context factory is called once for every request in
+    /// a batch.
+    pub fn with_shared_batch_context(self) -> Self {
+        GraphQLHandler {
+            shared_batch_context: true,
+            ..self
+        }
+    }
+
+    /// Reject batches of more than `max_batch_size` requests
+    ///
+    /// By default, batches can be of any size.
+    pub fn with_max_batch_size(self, max_batch_size: usize) -> Self {
+        GraphQLHandler {
+            max_batch_size: Some(max_batch_size),
+            ..self
+        }
+    }
+
+    /// Prepare a query and add it to the persisted query store under an id
+    ///
+    /// See `RootNode::persist_query`.
+    pub fn persist_query<'a>(&self, id: &str, document_source: &'a str) -> Result<(), GraphQLError<'a>> {
+        self.root_node.persist_query(id, document_source)
+    }
 
     fn handle_get(&self, req: &mut Request) -> IronResult<Response> {
         let url = req.url.clone().into_generic_url();
 
         let mut query = None;
-        let variables = HashMap::new();
+        let mut id = None;
+        let mut hash = None;
+        let mut operation_name = None;
+        let mut variables = HashMap::new();
 
         for (k, v) in url.query_pairs() {
             if k == "query" {
                 query = Some(v.into_owned());
             }
+            else if k == "id" {
+                id = Some(v.into_owned());
+            }
+            else if k == "extensions" {
+                hash = persisted_query_hash(&itry!(Json::from_str(&v), status::BadRequest));
+            }
+            else if k == "operationName" {
+                operation_name = Some(v.into_owned());
+            }
+            else if k == "variables" {
+                variables = match variables_from_json(itry!(Json::from_str(&v), status::BadRequest)) {
+                    Some(variables) => variables,
+                    None => return Ok(bad_request(VARIABLES_NOT_AN_OBJECT)),
+                };
+            }
+        }
+
+        let request = GraphQLRequest {
+            source: iexpect!(query_source(query, id, hash)),
+            operation_name: operation_name,
+            variables: variables,
+        };
+
+        // The query is prepared once, both to see whether it selects a
+        // mutation and to execute it
+        let query = self.prepare_request(&request);
+
+        if let Ok(ref query) = query {
+            if is_mutation(query.document(), request.operation_name()) {
+                return Ok(Response::with((status::MethodNotAllowed, Header(headers::Allow(vec![method::Post])))));
+            }
         }
 
-        let query = iexpect!(query);
+        let context = (self.context_factory)(req);
 
-        self.execute(req, &query, &variables)
+        Ok(execution_response(self.execute_prepared(query, &request, &context)))
     }
 
     fn handle_post(&self, req: &mut Request) -> IronResult<Response> {
         let json_data = itry!(Json::from_reader(&mut req.body));
 
-        let json_obj = match json_data {
-            Json::Object(o) => o,
-            _ => return Ok(Response::with((status::BadRequest, "No JSON object was decoded"))),
+        match json_data {
+            Json::Object(o) => match GraphQLRequest::from_json(o) {
+                Ok(request) => self.execute(req, &request),
+                Err(message) => Ok(bad_request(message)),
+            },
+            Json::Array(entries) => {
+                if entries.is_empty() {
+                    return Ok(bad_request("Batch has no requests"));
+                }
+
+                if let Some(max_batch_size) = self.max_batch_size {
+                    if entries.len() > max_batch_size {
+                        return Ok(bad_request(&format!(
+                            "Batch has more than the maximum of {} requests", max_batch_size)));
+                    }
+                }
+
+                let requests = entries.into_iter()
+                    .map(|entry| match entry {
+                        Json::Object(o) => GraphQLRequest::from_json(o),
+                        _ => Err("Batch entries must be JSON objects"),
+                    })
+                    .collect::<Vec<_>>();
+
+                self.execute_batch(req, &requests)
+            }
+            _ => Ok(Response::with((status::BadRequest, "No JSON object or array was decoded"))),
+        }
+    }
+
+    fn execute(&self, req: &mut Request, request: &GraphQLRequest) -> IronResult<Response> {
+        let context = (self.context_factory)(req);
+
+        Ok(execution_response(self.execute_request(request, &context)))
+    }
+
+    /// Execute the requests of a batch in order
+    ///
+    /// The response is a list of the results of all requests, including
+    /// the ones that failed or could not be read.
+    fn execute_batch(&self, req: &mut Request, requests: &[Result<GraphQLRequest, &str>]) -> IronResult<Response> {
+        let results = if self.shared_batch_context {
+            let context = (self.context_factory)(req);
+
+            requests.iter()
+                .map(|request| match *request {
+                    Ok(ref request) => self.execute_request(request, &context),
+                    Err(message) => Err(error_object(message)),
+                })
+                .collect::<Vec<_>>()
+        }
+        else {
+            requests.iter()
+                .map(|request| match *request {
+                    Ok(ref request) => self.execute_request(request, &(self.context_factory)(req)),
+                    Err(message) => Err(error_object(message)),
+                })
+                .collect::<Vec<_>>()
         };
 
+        let content_type = "application/json".parse::<Mime>().unwrap();
+        let response = BatchResponse { results: &results };
+        let json = as_pretty_json(&response);
+
+        Ok(Response::with((content_type, status::Ok, json.to_string())))
+    }
+
+    fn execute_request(&self, request: &GraphQLRequest, context: &CtxT) -> Result<(Value, Vec<ExecutionError>), Json> {
+        self.execute_prepared(self.prepare_request(request), request, context)
+    }
+
+    fn execute_prepared<'a>(
+        &self,
+        query: Result<Arc<PreparedQuery>, GraphQLError<'a>>,
+        request: &GraphQLRequest,
+        context: &CtxT,
+    )
+        -> Result<(Value, Vec<ExecutionError>), Json>
+    {
+        query
+            .and_then(|query| query.execute(
+                request.operation_name(), &self.root_node, &request.variables, context))
+            .map_err(|err| err.to_json())
+    }
+
+    /// Parse and validate the query of a request, or look it up in the
+    /// persisted query store
+    ///
+    /// This follows `juniper::execute` and `juniper::execute_persisted`, so
+    /// that queries sent by source are added to the store.
+    fn prepare_request<'a>(&self, request: &'a GraphQLRequest) -> Result<Arc<PreparedQuery>, GraphQLError<'a>> {
+        match (&request.source, &self.root_node.persisted_queries) {
+            (&QuerySource::Query(ref query, Some(ref hash)), _) if *hash != query_hash(query) =>
+                Err(GraphQLError::PersistedQueryHashMismatch),
+            (&QuerySource::Query(ref query, _), &Some(ref persisted)) =>
+                persisted.prepare(query, &self.root_node),
+            (&QuerySource::Query(ref query, _), &None) =>
+                PreparedQuery::new(query, &self.root_node).map(Arc::new),
+            (&QuerySource::Persisted(ref id), &Some(ref persisted)) =>
+                persisted.get(id).ok_or(GraphQLError::PersistedQueryNotFound),
+            (&QuerySource::Persisted(_), &None) =>
+                Err(GraphQLError::PersistedQueryNotSupported),
+        }
+    }
+}
+
+impl GraphQLRequest {
+    /// Read a request from a JSON object
+    ///
+    /// Fails if the request has no query, id or hash, or if its variables
+    /// are not an object.
+    fn from_json(json_obj: Object) -> Result<GraphQLRequest, &'static str> {
         let mut query = None;
+        let mut id = None;
+        let mut hash = None;
+        let mut operation_name = None;
         let mut variables = HashMap::new();
 
         for (k, v) in json_obj.into_iter() {
             if k == "query" {
                 query = v.as_string().map(|s| s.to_owned());
             }
+            else if k == "id" {
+                id = v.as_string().map(|s| s.to_owned());
+            }
+            else if k == "extensions" {
+                hash = persisted_query_hash(&v);
+            }
+            else if k == "operationName" {
+                operation_name = v.as_string().map(|s| s.to_owned());
+            }
             else if k == "variables" {
-                variables = match InputValue::from_json(v).to_object_value() {
-                    Some(o) => o.into_iter().map(|(k, v)| (k.to_owned(), v.clone())).collect(),
-                    _ => HashMap::new(),
-                };
+                variables = try!(variables_from_json(v).ok_or(VARIABLES_NOT_AN_OBJECT));
             }
         }
 
-        let query = iexpect!(query);
+        match query_source(query, id, hash) {
+            Some(source) => Ok(GraphQLRequest {
+                source: source,
+                operation_name: operation_name,
+                variables: variables,
+            }),
+            None => Err(NO_QUERY),
+        }
+    }
 
-        self.execute(req, &query, &variables)
+    fn operation_name(&self) -> Option<&str> {
+        self.operation_name.as_ref().map(|s| s as &str)
     }
+}
 
-    fn execute(&self, req: &mut Request, query: &str, variables: &HashMap<String, InputValue>) -> IronResult<Response> {
-        let context = (self.context_factory)(req);
-        let result = execute(query, None, &self.root_node, variables, &context);
+const VARIABLES_NOT_AN_OBJECT: &'static str = "Variables must be a JSON object";
+const NO_QUERY: &'static str = "Request has no query, id or persisted query hash";
 
-        let content_type = "application/json".parse::<Mime>().unwrap();
+/// Pick the query of a request, preferring the query itself over an id and
+/// an id over a hash
+fn query_source(query: Option<String>, id: Option<String>, hash: Option<String>) -> Option<QuerySource> {
+    match (query, id, hash) {
+        (Some(query), _, hash) => Some(QuerySource::Query(query, hash)),
+        (None, Some(id), _) | (None, None, Some(id)) => Some(QuerySource::Persisted(id)),
+        (None, None, None) => None,
+    }
+}
 
-        match result {
-            Ok((result, errors)) => {
-                let mut map = BTreeMap::new();
-                map.insert("data".to_owned(), result.to_json());
-                if !errors.is_empty() {
-                    map.insert("errors".to_owned(), errors.to_json());
-                }
+/// Whether the operation to execute in a document is a mutation
+fn is_mutation(document: &Document, operation_name: Option<&str>) -> bool {
+    let mut operations = document.iter().filter_map(|def| match *def {
+        Definition::Operation(ref op) => Some(&op.item),
+        _ => None,
+    });
 
-                let data = Json::Object(map);
-                let json = data.pretty();
+    let operation = match operation_name {
+        Some(name) => operations.find(|op| op.name.as_ref().map(|n| n.item.as_ref()) == Some(name)),
+        None => operations.next(),
+    };
 
-                Ok(Response::with((content_type, status::Ok, json.to_string())))
+    operation.map_or(false, |op| op.operation_type == OperationType::Mutation)
+}
+
+/// The variables of a request, if they are a JSON object or `null`
+fn variables_from_json(json: Json) -> Option<HashMap<String, InputValue>> {
+    match json {
+        Json::Null => Some(HashMap::new()),
+        Json::Object(_) => InputValue::from_json(json).to_object_value()
+            .map(|o| o.into_iter().map(|(k, v)| (k.to_owned(), v.clone())).collect()),
+        _ => None,
+    }
+}
+
+/// The hash in the `persistedQuery` extension of a request, if any
+fn persisted_query_hash(extensions: &Json) -> Option<String> {
+    extensions.find_path(&["persistedQuery", "sha256Hash"])
+        .and_then(|hash| hash.as_string())
+        .map(|hash| hash.to_owned())
+}
+
+/// The response to a single request: the result, or the errors that kept it
+/// from being executed
+fn execution_response(result: Result<(Value, Vec<ExecutionError>), Json>) -> Response {
+    let content_type = "application/json".parse::<Mime>().unwrap();
+
+    match result {
+        Ok((result, errors)) => {
+            let response = ExecutionResponse { data: &result, errors: &errors };
+            let json = as_pretty_json(&response);
+
+            Response::with((content_type, status::Ok, json.to_string()))
+        }
+
+        Err(data) => {
+            let json = data.pretty();
+
+            Response::with((content_type, status::BadRequest, json.to_string()))
+        }
+    }
+}
+
+/// A 400 response for a request that could not be read, with the same JSON
+/// error list as a failed query
+fn bad_request(message: &str) -> Response {
+    execution_response(Err(error_object(message)))
+}
+
+fn error_object(message: &str) -> Json {
+    Json::Object(vec![
+        ("errors".to_owned(), Json::Array(vec![
+            Json::Object(vec![
+                ("message".to_owned(), message.to_json()),
+            ].into_iter().collect()),
+        ])),
+    ].into_iter().collect())
+}
+
+/// Successful response body, encoded directly to keep the field order of
+/// `data` intact
+struct ExecutionResponse<'a> {
+    data: &'a Value,
+    errors: &'a [ExecutionError],
+}
+
+impl<'a> Encodable for ExecutionResponse<'a> {
+    fn encode<S: Encoder>(&self, s: &mut S) -> Result<(), S::Error> {
+        let field_count = if self.errors.is_empty() { 1 } else { 2 };
+
+        s.emit_struct("ExecutionResponse", field_count, |s| {
+            try!(s.emit_struct_field("data", 0, |s| self.data.encode(s)));
+
+            if !self.errors.is_empty() {
+                try!(s.emit_struct_field("errors", 1, |s| self.errors.encode(s)));
             }
 
-            Err(err) => {
-                let data = err.to_json();
-                let json = data.pretty();
+            Ok(())
+        })
+    }
+}
+
+/// Batch response body, a list of the responses of all requests in order
+struct BatchResponse<'a> {
+    results: &'a [Result<(Value, Vec<ExecutionError>), Json>],
+}
 
-                Ok(Response::with((content_type, status::BadRequest, json.to_string())))
+impl<'a> Encodable for BatchResponse<'a> {
+    fn encode<S: Encoder>(&self, s: &mut S) -> Result<(), S::Error> {
+        s.emit_seq(self.results.len(), |s| {
+            for (i, result) in self.results.iter().enumerate() {
+                try!(s.emit_seq_elt(i, |s| match *result {
+                    Ok((ref data, ref errors)) => ExecutionResponse { data: data, errors: errors }.encode(s),
+                    Err(ref json) => json.encode(s),
+                }));
             }
-        }
+
+            Ok(())
+        })
     }
 }
 
@@ -239,6 +579,9 @@ impl Handler for GraphiQLHandler {
 
 #[cfg(test)]
 mod tests {
+    use std::sync::Arc;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
     use rustc_serialize::json::Json;
     
     use iron::prelude::*;
@@ -246,11 +589,21 @@ mod tests {
     use iron::headers;
     use iron_test::{request, response};
     use iron::{Handler, Headers};
+    use iron::method;
 
     use ::tests::model::Database;
+    use ::{PersistedQueries, LruQueryStore};
 
     use super::GraphQLHandler;
 
+    struct Mutation;
+
+    graphql_object!(Mutation: Database |&self| {
+        field noop() -> bool {
+            true
+        }
+    });
+
     fn context_factory(_: &mut Request) -> Database {
         Database::new()
     }
@@ -263,6 +616,26 @@ mod tests {
         ))
     }
 
+    fn make_mutation_handler() -> Box<Handler> {
+        Box::new(GraphQLHandler::new(
+            context_factory,
+            Database::new(),
+            Mutation,
+        ))
+    }
+
+    fn make_persisted_handler() -> Box<Handler> {
+        let handler = GraphQLHandler::new(
+            context_factory,
+            Database::new(),
+            (),
+        ).with_persisted_queries(PersistedQueries::new(LruQueryStore::new(10)));
+
+        handler.persist_query("heroName", "{hero{name}}").expect("Invalid query");
+
+        Box::new(handler)
+    }
+
     fn unwrap_json_response(resp: Response) -> Json {
         let result = response::extract_body_to_string(resp);
 
@@ -320,4 +693,507 @@ mod tests {
 
         assert_eq!(response.status, Some(status::MethodNotAllowed));
     }
+
+    #[test]
+    fn test_persisted_get_by_id() {
+        let response = request::get(
+            "http://localhost:3000/?id=heroName",
+            Headers::new(),
+            &make_persisted_handler())
+            .expect("Unexpected IronError");
+
+        assert_eq!(response.status, Some(status::Ok));
+
+        let json = unwrap_json_response(response);
+
+        assert_eq!(
+            json,
+            Json::from_str(r#"{"data": {"hero": {"name": "R2-D2"}}}"#)
+                .expect("Invalid JSON constant in test"));
+    }
+
+    #[test]
+    fn test_persisted_get_by_hash() {
+        let handler = make_persisted_handler();
+
+        request::get("http://localhost:3000/?query={hero{id}}", Headers::new(), &handler)
+            .expect("Unexpected IronError");
+
+        let response = request::get(
+            &format!("http://localhost:3000/?extensions={}", HERO_ID_EXTENSIONS_PARAM),
+            Headers::new(),
+            &handler)
+            .expect("Unexpected IronError");
+
+        assert_eq!(response.status, Some(status::Ok));
+        assert_eq!(unwrap_json_response(response), hero_id());
+    }
+
+    #[test]
+    fn test_persisted_post_by_id() {
+        let response = request::post(
+            "http://localhost:3000/",
+            Headers::new(),
+            r#"{"id": "heroName"}"#,
+            &make_persisted_handler())
+            .expect("Unexpected IronError");
+
+        assert_eq!(response.status, Some(status::Ok));
+
+        let json = unwrap_json_response(response);
+
+        assert_eq!(
+            json,
+            Json::from_str(r#"{"data": {"hero": {"name": "R2-D2"}}}"#)
+                .expect("Invalid JSON constant in test"));
+    }
+
+    #[test]
+    fn test_persisted_post_by_hash() {
+        let handler = make_persisted_handler();
+
+        request::post("http://localhost:3000/", Headers::new(), r#"{"query": "{hero{id}}"}"#, &handler)
+            .expect("Unexpected IronError");
+
+        let response = request::post(
+            "http://localhost:3000/",
+            Headers::new(),
+            &format!(r#"{{"extensions": {}}}"#, HERO_ID_EXTENSIONS),
+            &handler)
+            .expect("Unexpected IronError");
+
+        assert_eq!(response.status, Some(status::Ok));
+        assert_eq!(unwrap_json_response(response), hero_id());
+    }
+
+    #[test]
+    fn test_unknown_persisted_query() {
+        let response = request::post(
+            "http://localhost:3000/",
+            Headers::new(),
+            r#"{"id": "heroId"}"#,
+            &make_persisted_handler())
+            .expect("Unexpected IronError");
+
+        assert_eq!(response.status, Some(status::BadRequest));
+        assert_eq!(unwrap_json_response(response), persisted_query_not_found());
+    }
+
+    const HERO_ID_EXTENSIONS: &'static str =
+        r#"{"persistedQuery": {"version": 1, "sha256Hash": "15f9d3eba173740ea63d3f6f4cc3f8f728c0cd7fdc48c268f0119df08f90783d"}}"#;
+
+    const HERO_ID_EXTENSIONS_PARAM: &'static str =
+        "%7B%22persistedQuery%22%3A%7B%22version%22%3A1%2C%22sha256Hash%22%3A%22\
+         15f9d3eba173740ea63d3f6f4cc3f8f728c0cd7fdc48c268f0119df08f90783d%22%7D%7D";
+
+    fn persisted_query_not_found() -> Json {
+        Json::from_str(r#"{"errors": [{"message": "PersistedQueryNotFound"}]}"#)
+            .expect("Invalid JSON constant in test")
+    }
+
+    fn hero_id() -> Json {
+        Json::from_str(r#"{"data": {"hero": {"id": "2001"}}}"#)
+            .expect("Invalid JSON constant in test")
+    }
+
+    #[test]
+    fn test_automatic_persisted_query_get() {
+        let handler = make_persisted_handler();
+        let hash_url = format!("http://localhost:3000/?extensions={}", HERO_ID_EXTENSIONS_PARAM);
+        let query_url = format!("http://localhost:3000/?query={{hero{{id}}}}&extensions={}", HERO_ID_EXTENSIONS_PARAM);
+
+        let response = request::get(&hash_url, Headers::new(), &handler)
+            .expect("Unexpected IronError");
+
+        assert_eq!(response.status, Some(status::BadRequest));
+        assert_eq!(unwrap_json_response(response), persisted_query_not_found());
+
+        let response = request::get(&query_url, Headers::new(), &handler)
+            .expect("Unexpected IronError");
+
+        assert_eq!(response.status, Some(status::Ok));
+        assert_eq!(unwrap_json_response(response), hero_id());
+
+        let response = request::get(&hash_url, Headers::new(), &handler)
+            .expect("Unexpected IronError");
+
+        assert_eq!(response.status, Some(status::Ok));
+        assert_eq!(unwrap_json_response(response), hero_id());
+    }
+
+    #[test]
+    fn test_automatic_persisted_query_post() {
+        let handler = make_persisted_handler();
+        let hash_body = format!(r#"{{"extensions": {}}}"#, HERO_ID_EXTENSIONS);
+        let query_body = format!(r#"{{"query": "{{hero{{id}}}}", "extensions": {}}}"#, HERO_ID_EXTENSIONS);
+
+        let response = request::post("http://localhost:3000/", Headers::new(), &hash_body, &handler)
+            .expect("Unexpected IronError");
+
+        assert_eq!(response.status, Some(status::BadRequest));
+        assert_eq!(unwrap_json_response(response), persisted_query_not_found());
+
+        let response = request::post("http://localhost:3000/", Headers::new(), &query_body, &handler)
+            .expect("Unexpected IronError");
+
+        assert_eq!(response.status, Some(status::Ok));
+        assert_eq!(unwrap_json_response(response), hero_id());
+
+        let response = request::post("http://localhost:3000/", Headers::new(), &hash_body, &handler)
+            .expect("Unexpected IronError");
+
+        assert_eq!(response.status, Some(status::Ok));
+        assert_eq!(unwrap_json_response(response), hero_id());
+    }
+
+    #[test]
+    fn test_automatic_persisted_query_hash_mismatch() {
+        let handler = make_persisted_handler();
+        let body = format!(r#"{{"query": "{{hero{{name}}}}", "extensions": {}}}"#, HERO_ID_EXTENSIONS);
+
+        let response = request::post("http://localhost:3000/", Headers::new(), &body, &handler)
+            .expect("Unexpected IronError");
+
+        assert_eq!(response.status, Some(status::BadRequest));
+        assert_eq!(
+            unwrap_json_response(response),
+            Json::from_str(r#"{"errors": [{"message": "provided sha does not match query"}]}"#)
+                .expect("Invalid JSON constant in test"));
+
+        let response = request::post(
+            "http://localhost:3000/",
+            Headers::new(),
+            &format!(r#"{{"extensions": {}}}"#, HERO_ID_EXTENSIONS),
+            &handler)
+            .expect("Unexpected IronError");
+
+        assert_eq!(unwrap_json_response(response), persisted_query_not_found());
+    }
+
+    #[test]
+    fn test_automatic_persisted_query_without_store() {
+        let handler = make_handler();
+
+        let response = request::post(
+            "http://localhost:3000/",
+            Headers::new(),
+            &format!(r#"{{"extensions": {}}}"#, HERO_ID_EXTENSIONS),
+            &handler)
+            .expect("Unexpected IronError");
+
+        assert_eq!(response.status, Some(status::BadRequest));
+        assert_eq!(
+            unwrap_json_response(response),
+            Json::from_str(r#"{"errors": [{"message": "PersistedQueryNotSupported"}]}"#)
+                .expect("Invalid JSON constant in test"));
+
+        let response = request::post(
+            "http://localhost:3000/",
+            Headers::new(),
+            &format!(r#"{{"query": "{{hero{{id}}}}", "extensions": {}}}"#, HERO_ID_EXTENSIONS),
+            &handler)
+            .expect("Unexpected IronError");
+
+        assert_eq!(response.status, Some(status::Ok));
+        assert_eq!(unwrap_json_response(response), hero_id());
+    }
+
+    #[test]
+    fn test_get_with_variables() {
+        let response = request::get(
+            "http://localhost:3000/?query=query%28%24id%3AString%21%29%7Bhuman%28id%3A%24id%29%7Bname%7D%7D\
+             &variables=%7B%22id%22%3A%221000%22%7D",
+            Headers::new(),
+            &make_handler())
+            .expect("Unexpected IronError");
+
+        assert_eq!(response.status, Some(status::Ok));
+
+        let json = unwrap_json_response(response);
+
+        assert_eq!(
+            json,
+            Json::from_str(r#"{"data": {"human": {"name": "Luke Skywalker"}}}"#)
+                .expect("Invalid JSON constant in test"));
+    }
+
+    #[test]
+    fn test_get_with_invalid_variables() {
+        let response = request::get(
+            "http://localhost:3000/?query={hero{name}}&variables=%7B",
+            Headers::new(),
+            &make_handler());
+
+        match response {
+            Err(err) => assert_eq!(err.response.status, Some(status::BadRequest)),
+            Ok(_) => panic!("Expected invalid variables to be rejected"),
+        }
+    }
+
+    #[test]
+    fn test_get_with_non_object_variables() {
+        let response = request::get(
+            "http://localhost:3000/?query={hero{name}}&variables=%5B%5D",
+            Headers::new(),
+            &make_handler())
+            .expect("Unexpected IronError");
+
+        assert_eq!(response.status, Some(status::BadRequest));
+        assert_eq!(unwrap_json_response(response), variables_not_an_object());
+
+        let response = request::get(
+            "http://localhost:3000/?query={hero{name}}&variables=null",
+            Headers::new(),
+            &make_handler())
+            .expect("Unexpected IronError");
+
+        assert_eq!(response.status, Some(status::Ok));
+    }
+
+    #[test]
+    fn test_post_with_non_object_variables() {
+        let response = request::post(
+            "http://localhost:3000/",
+            Headers::new(),
+            r#"{"query": "{hero{name}}", "variables": "{}"}"#,
+            &make_handler())
+            .expect("Unexpected IronError");
+
+        assert_eq!(response.status, Some(status::BadRequest));
+        assert_eq!(unwrap_json_response(response), variables_not_an_object());
+    }
+
+    fn variables_not_an_object() -> Json {
+        Json::from_str(r#"{"errors": [{"message": "Variables must be a JSON object"}]}"#)
+            .expect("Invalid JSON constant in test")
+    }
+
+    #[test]
+    fn test_get_with_operation_name() {
+        let response = request::get(
+            "http://localhost:3000/?query=query%20A%7Bhero%7Bname%7D%7D%20query%20B%7Bhero%7Bid%7D%7D\
+             &operationName=B",
+            Headers::new(),
+            &make_handler())
+            .expect("Unexpected IronError");
+
+        assert_eq!(response.status, Some(status::Ok));
+        assert_eq!(unwrap_json_response(response), hero_id());
+    }
+
+    #[test]
+    fn test_post_with_operation_name() {
+        let response = request::post(
+            "http://localhost:3000/",
+            Headers::new(),
+            r#"{"query": "query A{hero{name}} query B{hero{id}}", "operationName": "B"}"#,
+            &make_handler())
+            .expect("Unexpected IronError");
+
+        assert_eq!(response.status, Some(status::Ok));
+        assert_eq!(unwrap_json_response(response), hero_id());
+    }
+
+    #[test]
+    fn test_operation_selection_errors() {
+        let response = request::post(
+            "http://localhost:3000/",
+            Headers::new(),
+            r#"{"query": "query A{hero{name}} query B{hero{id}}"}"#,
+            &make_handler())
+            .expect("Unexpected IronError");
+
+        assert_eq!(response.status, Some(status::BadRequest));
+        assert_eq!(
+            unwrap_json_response(response),
+            Json::from_str(r#"{"errors": [
+                {"message": "Must provide operation name if query contains multiple operations."}
+            ]}"#)
+                .expect("Invalid JSON constant in test"));
+
+        let response = request::post(
+            "http://localhost:3000/",
+            Headers::new(),
+            r#"{"query": "query A{hero{name}}", "operationName": "C"}"#,
+            &make_handler())
+            .expect("Unexpected IronError");
+
+        assert_eq!(response.status, Some(status::BadRequest));
+        assert_eq!(
+            unwrap_json_response(response),
+            Json::from_str(r#"{"errors": [{"message": "Unknown operation"}]}"#)
+                .expect("Invalid JSON constant in test"));
+    }
+
+    #[test]
+    fn test_get_mutation() {
+        let handler = make_mutation_handler();
+
+        let response = request::get(
+            "http://localhost:3000/?query=mutation%7Bnoop%7D",
+            Headers::new(),
+            &handler)
+            .expect("Unexpected IronError");
+
+        assert_eq!(response.status, Some(status::MethodNotAllowed));
+        assert_eq!(response.headers.get::<headers::Allow>(),
+                   Some(&headers::Allow(vec![method::Post])));
+
+        let response = request::get(
+            "http://localhost:3000/?query=query%20Q%7Bhero%7Bname%7D%7D%20mutation%20M%7Bnoop%7D\
+             &operationName=M",
+            Headers::new(),
+            &handler)
+            .expect("Unexpected IronError");
+
+        assert_eq!(response.status, Some(status::MethodNotAllowed));
+
+        let response = request::get(
+            "http://localhost:3000/?query=query%20Q%7Bhero%7Bname%7D%7D%20mutation%20M%7Bnoop%7D\
+             &operationName=Q",
+            Headers::new(),
+            &handler)
+            .expect("Unexpected IronError");
+
+        assert_eq!(response.status, Some(status::Ok));
+    }
+
+    #[test]
+    fn test_post_mutation() {
+        let response = request::post(
+            "http://localhost:3000/",
+            Headers::new(),
+            r#"{"query": "mutation{noop}"}"#,
+            &make_mutation_handler())
+            .expect("Unexpected IronError");
+
+        assert_eq!(response.status, Some(status::Ok));
+
+        let json = unwrap_json_response(response);
+
+        assert_eq!(
+            json,
+            Json::from_str(r#"{"data": {"noop": true}}"#)
+                .expect("Invalid JSON constant in test"));
+    }
+
+    fn counting_handler(contexts: Arc<AtomicUsize>) -> GraphQLHandler<Box<Fn(&mut Request) -> Database + Send + Sync>, Database, (), Database> {
+        GraphQLHandler::new(
+            Box::new(move |_: &mut Request| {
+                contexts.fetch_add(1, Ordering::SeqCst);
+                Database::new()
+            }),
+            Database::new(),
+            (),
+        )
+    }
+
+    #[test]
+    fn test_batch_post() {
+        let response = request::post(
+            "http://localhost:3000/",
+            Headers::new(),
+            r#"[{"query": "{hero{name}}"}, {"query": "{hero{unknown}}"}, {"query": "{hero{id}}"}]"#,
+            &make_handler())
+            .expect("Unexpected IronError");
+
+        assert_eq!(response.status, Some(status::Ok));
+
+        let json = unwrap_json_response(response);
+
+        assert_eq!(
+            json,
+            Json::from_str(r#"[
+                {"data": {"hero": {"name": "R2-D2"}}},
+                {"errors": [{"message": "Unknown field \"unknown\" on type \"Character\"", "locations": [{"line": 1, "column": 7}]}]},
+                {"data": {"hero": {"id": "2001"}}}
+            ]"#)
+                .expect("Invalid JSON constant in test"));
+    }
+
+    #[test]
+    fn test_batch_with_invalid_entries() {
+        let response = request::post(
+            "http://localhost:3000/",
+            Headers::new(),
+            r#"[{"query": "{hero{name}}"}, {"variables": {}}, 1, {"query": "{hero{id}}"}]"#,
+            &make_handler())
+            .expect("Unexpected IronError");
+
+        assert_eq!(response.status, Some(status::Ok));
+        assert_eq!(
+            unwrap_json_response(response),
+            Json::from_str(r#"[
+                {"data": {"hero": {"name": "R2-D2"}}},
+                {"errors": [{"message": "Request has no query, id or persisted query hash"}]},
+                {"errors": [{"message": "Batch entries must be JSON objects"}]},
+                {"data": {"hero": {"id": "2001"}}}
+            ]"#)
+                .expect("Invalid JSON constant in test"));
+    }
+
+    #[test]
+    fn test_empty_batch() {
+        let response = request::post(
+            "http://localhost:3000/",
+            Headers::new(),
+            "[]",
+            &make_handler())
+            .expect("Unexpected IronError");
+
+        assert_eq!(response.status, Some(status::BadRequest));
+        assert_eq!(
+            unwrap_json_response(response),
+            Json::from_str(r#"{"errors": [{"message": "Batch has no requests"}]}"#)
+                .expect("Invalid JSON constant in test"));
+    }
+
+    #[test]
+    fn test_batch_contexts() {
+        let body = r#"[{"query": "{hero{name}}"}, {"query": "{hero{id}}"}]"#;
+
+        let contexts = Arc::new(AtomicUsize::new(0));
+        let handler = counting_handler(contexts.clone());
+
+        request::post("http://localhost:3000/", Headers::new(), body, &handler)
+            .expect("Unexpected IronError");
+
+        assert_eq!(contexts.load(Ordering::SeqCst), 2);
+
+        let contexts = Arc::new(AtomicUsize::new(0));
+        let handler = counting_handler(contexts.clone()).with_shared_batch_context();
+
+        request::post("http://localhost:3000/", Headers::new(), body, &handler)
+            .expect("Unexpected IronError");
+
+        assert_eq!(contexts.load(Ordering::SeqCst), 1);
+    }
+
+    #[test]
+    fn test_max_batch_size() {
+        let handler = GraphQLHandler::new(context_factory, Database::new(), ())
+            .with_max_batch_size(2);
+
+        let response = request::post(
+            "http://localhost:3000/",
+            Headers::new(),
+            r#"[{"query": "{hero{name}}"}, {"query": "{hero{id}}"}]"#,
+            &handler)
+            .expect("Unexpected IronError");
+
+        assert_eq!(response.status, Some(status::Ok));
+
+        let response = request::post(
+            "http://localhost:3000/",
+            Headers::new(),
+            r#"[{"query": "{hero{name}}"}, {"query": "{hero{id}}"}, {"query": "{hero{name}}"}]"#,
+            &handler)
+            .expect("Unexpected IronError");
+
+        assert_eq!(response.status, Some(status::BadRequest));
+        assert_eq!(
+            unwrap_json_response(response),
+            Json::from_str(r#"{"errors": [{"message": "Batch has more than the maximum of 2 requests"}]}"#)
+                .expect("Invalid JSON constant in test"));
+    }
 }
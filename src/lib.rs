@@ -179,6 +179,9 @@ built-in [GraphiQL][6] handler included.
 #![warn(missing_docs)]
 
 extern crate rustc_serialize;
+extern crate futures;
+extern crate rayon;
+extern crate sha2;
 
 #[cfg(feature="nightly")] extern crate test;
 #[cfg(feature="iron-handlers")] #[macro_use(itry, iexpect)] extern crate iron;
@@ -192,6 +195,9 @@ mod types;
 mod schema;
 pub mod validation;
 mod executor;
+mod loader;
+mod prepared;
+mod persisted;
 mod integrations;
 
 #[cfg(all(test, not(feature="expose-test-schema")))] mod tests;
@@ -201,20 +207,37 @@ mod integrations;
 
 use std::collections::HashMap;
 
+use rustc_serialize::{Encodable, Encoder};
 use rustc_serialize::json::{ToJson, Json};
 
 use parser::{parse_document_source, ParseError, Spanning, SourcePosition};
-use validation::{RuleError, ValidatorContext, visit_all_rules, validate_input_values};
-use executor::execute_validated_query;
+use validation::{RuleError, ValidatorContext, QueryComplexity, visit_all_rules, validate_input_values};
+use executor::{
+    execute_validated_query, execute_validated_query_async, execute_validated_query_parallel,
+    execute_validated_subscription,
+};
 
 pub use ast::{ToInputValue, FromInputValue, InputValue, Type, Selection};
-pub use value::Value;
-pub use types::base::{Arguments, GraphQLType, TypeKind};
+pub use ast::{Document, Definition, SchemaDefinition, TypeDefinition, DirectiveDefinition,
+              ScalarTypeDefinition, ObjectTypeDefinition, InterfaceTypeDefinition,
+              UnionTypeDefinition, EnumTypeDefinition, InputObjectTypeDefinition,
+              FieldDefinition, InputValueDefinition, EnumValueDefinition};
+pub use ast::{Operation, OperationType, Fragment, VariableDefinition, Field, FragmentSpread,
+              InlineFragment, Directive};
+pub use value::{Value, Object};
+pub use types::base::{Arguments, GraphQLType, GraphQLSubscriptionType, TypeKind};
 pub use executor::{
-    Executor, Registry, ExecutionResult, ExecutionError, FieldResult, IntoFieldResult,
+    Executor, Registry, ExecutionResult, ExecutionError, FieldError, FieldResult, IntoFieldResult,
+    PathSegment, SubscriptionStream, LookAheadSelection, ExecutionFuture,
 };
 pub use types::scalars::ID;
+pub use types::future::FieldFuture;
+pub use loader::{Loader, LoaderSource, Load};
+pub use prepared::PreparedQuery;
+pub use persisted::{PersistedQueries, PersistedQueryStore, AllowListStore, MemoryQueryStore, LruQueryStore,
+                    query_hash};
 pub use schema::model::RootNode;
+pub use schema::diff::{compare_schemas, SchemaChange, ChangeCriticality};
 
 pub use schema::meta;
 
@@ -229,41 +252,304 @@ pub enum GraphQLError<'a> {
     NoOperationProvided,
     MultipleOperationsProvided,
     UnknownOperationName,
+    IsSubscription,
+    NotSubscription,
+    QueryTooComplex { cost: usize, max_cost: usize },
+    PersistedQueryNotFound,
+    PersistedQueryNotSupported,
+    PersistedQueryHashMismatch,
+    QueryNotAllowed,
+    SchemaMismatch,
 }
 
 /// Execute a query in a provided schema
-pub fn execute<'a, CtxT, QueryT, MutationT>(
+pub fn execute<'a, CtxT, QueryT, MutationT, SubscriptionT>(
     document_source: &'a str,
     operation_name: Option<&str>,
-    root_node: &RootNode<CtxT, QueryT, MutationT>,
+    root_node: &RootNode<CtxT, QueryT, MutationT, SubscriptionT>,
+    variables: &HashMap<String, InputValue>,
+    context: &CtxT,
+)
+    -> Result<(Value, Vec<ExecutionError>), GraphQLError<'a>>
+    where QueryT: GraphQLType<CtxT>,
+          MutationT: GraphQLType<CtxT>,
+          SubscriptionT: GraphQLType<CtxT>,
+{
+    if let Some(ref persisted) = root_node.persisted_queries {
+        let query = try!(persisted.prepare(document_source, root_node));
+
+        return query.execute(operation_name, root_node, variables, context);
+    }
+
+    let document = try!(parse_and_validate(document_source, operation_name, root_node, variables));
+
+    execute_validated_query(&document, operation_name, root_node, variables, context)
+}
+
+/// Execute a query from the schema's persisted query store
+///
+/// The query is looked up by the id it was stored under, or by the hash of
+/// its source if it was stored by `execute`. Fails with
+/// `GraphQLError::PersistedQueryNotFound` if there is no such query, and with
+/// `GraphQLError::PersistedQueryNotSupported` if the schema has no store.
+pub fn execute_persisted<'a, CtxT, QueryT, MutationT, SubscriptionT>(
+    id: &str,
+    operation_name: Option<&str>,
+    root_node: &RootNode<CtxT, QueryT, MutationT, SubscriptionT>,
     variables: &HashMap<String, InputValue>,
     context: &CtxT,
 )
     -> Result<(Value, Vec<ExecutionError>), GraphQLError<'a>>
     where QueryT: GraphQLType<CtxT>,
           MutationT: GraphQLType<CtxT>,
+          SubscriptionT: GraphQLType<CtxT>,
+{
+    let persisted = match root_node.persisted_queries {
+        Some(ref persisted) => persisted,
+        None => return Err(GraphQLError::PersistedQueryNotSupported),
+    };
+
+    match persisted.get(id) {
+        Some(query) => query.execute(operation_name, root_node, variables, context),
+        None => Err(GraphQLError::PersistedQueryNotFound),
+    }
+}
+
+/// Execute a query in a provided schema, resolving root fields in parallel
+///
+/// The root fields of a query are spread across the current rayon thread
+/// pool; run this inside `ThreadPool::install` to use a pool of your own.
+/// Root fields with the same response name are merged and resolved once.
+/// The root fields of a mutation are still resolved one after another.
+///
+/// Only root fields run in parallel: each root field and everything below it
+/// is resolved on a single worker, including its `Load` values. Loads are
+/// therefore batched per root field rather than across the whole query; use
+/// `execute` if a query relies on batching loads between root fields.
+pub fn execute_parallel<'a, CtxT, QueryT, MutationT, SubscriptionT>(
+    document_source: &'a str,
+    operation_name: Option<&str>,
+    root_node: &RootNode<CtxT, QueryT, MutationT, SubscriptionT>,
+    variables: &HashMap<String, InputValue>,
+    context: &CtxT,
+)
+    -> Result<(Value, Vec<ExecutionError>), GraphQLError<'a>>
+    where QueryT: GraphQLType<CtxT> + Sync,
+          MutationT: GraphQLType<CtxT> + Sync,
+          SubscriptionT: GraphQLType<CtxT> + Sync,
+          CtxT: Sync,
+{
+    if let Some(ref persisted) = root_node.persisted_queries {
+        let query = try!(persisted.prepare(document_source, root_node));
+
+        return query.execute_parallel(operation_name, root_node, variables, context);
+    }
+
+    let document = try!(parse_and_validate(document_source, operation_name, root_node, variables));
+
+    execute_validated_query_parallel(&document, operation_name, root_node, variables, context)
+}
+
+/// Execute a query asynchronously in a provided schema
+///
+/// Only fields returning `FieldFuture` values are asynchronous: their futures
+/// are waited for concurrently with the futures of all other fields on the
+/// same level, while the root fields of a mutation are still resolved one
+/// after another. Resolvers themselves are still called synchronously while
+/// the returned future is polled, so a resolver that blocks on I/O instead of
+/// returning a `FieldFuture` blocks the thread polling the future.
+///
+/// Parsing, validation and variable coercion happen up front; no resolver
+/// runs before the returned future is polled.
+pub fn execute_async<'a, CtxT, QueryT, MutationT, SubscriptionT>(
+    document_source: &'a str,
+    operation_name: Option<&str>,
+    root_node: &'a RootNode<CtxT, QueryT, MutationT, SubscriptionT>,
+    variables: &HashMap<String, InputValue>,
+    context: &'a CtxT,
+)
+    -> ExecutionFuture<'a>
+    where QueryT: GraphQLType<CtxT>,
+          MutationT: GraphQLType<CtxT>,
+          SubscriptionT: GraphQLType<CtxT>,
+{
+    if let Some(ref persisted) = root_node.persisted_queries {
+        return match persisted.prepare(document_source, root_node) {
+            Ok(query) => query.execute_async(operation_name, root_node, variables, context),
+            Err(e) => Box::new(futures::future::err(e)),
+        };
+    }
+
+    match parse_and_validate(document_source, operation_name, root_node, variables) {
+        Ok(document) => execute_validated_query_async(&document, operation_name, root_node, variables, context),
+        Err(e) => Box::new(futures::future::err(e)),
+    }
+}
+
+/// Execute a subscription operation in a provided schema
+///
+/// The subscription root field creates a stream of events, each of which is
+/// resolved like a regular query. Errors in parsing, validation, or operation
+/// selection are returned up front; the resulting stream yields one response
+/// per event.
+pub fn subscribe<'a, 'b, CtxT, QueryT, MutationT, SubscriptionT>(
+    document_source: &'a str,
+    operation_name: Option<&str>,
+    root_node: &'b RootNode<CtxT, QueryT, MutationT, SubscriptionT>,
+    variables: &HashMap<String, InputValue>,
+    context: &'b CtxT,
+)
+    -> Result<SubscriptionStream<'b, CtxT, SubscriptionT>, GraphQLError<'a>>
+    where QueryT: GraphQLType<CtxT>,
+          MutationT: GraphQLType<CtxT>,
+          SubscriptionT: GraphQLSubscriptionType<CtxT>,
+{
+    if let Some(ref persisted) = root_node.persisted_queries {
+        let query = try!(persisted.prepare(document_source, root_node));
+
+        return query.subscribe(operation_name, root_node, variables, context);
+    }
+
+    let document = try!(parse_and_validate(document_source, operation_name, root_node, variables));
+
+    execute_validated_subscription(&document, operation_name, root_node, variables, context)
+}
+
+/// Compute the complexity of a query in a provided schema
+///
+/// The query is parsed and validated first. The cost is computed with the
+/// schema's `QueryComplexity` if it has one, and with the default costs
+/// otherwise. Unlike `execute`, this does not fail when the cost exceeds the
+/// maximum, so that the cost of rejected queries can be logged too.
+pub fn query_complexity<'a, CtxT, QueryT, MutationT, SubscriptionT>(
+    document_source: &'a str,
+    operation_name: Option<&str>,
+    root_node: &RootNode<CtxT, QueryT, MutationT, SubscriptionT>,
+    variables: &HashMap<String, InputValue>,
+)
+    -> Result<usize, GraphQLError<'a>>
+{
+    let default_complexity;
+    let complexity = match root_node.complexity {
+        Some(ref complexity) => complexity,
+        None => {
+            default_complexity = QueryComplexity::new();
+            &default_complexity
+        }
+    };
+
+    let document = try!(parse_and_validate_document(document_source, root_node, variables));
+
+    Ok(complexity.cost(&document, operation_name, &root_node.schema, variables))
+}
+
+fn parse_and_validate<'a, CtxT, QueryT, MutationT, SubscriptionT>(
+    document_source: &'a str,
+    operation_name: Option<&str>,
+    root_node: &RootNode<CtxT, QueryT, MutationT, SubscriptionT>,
+    variables: &HashMap<String, InputValue>,
+)
+    -> Result<Document, GraphQLError<'a>>
+{
+    let document = try!(parse_and_validate_document(document_source, root_node, variables));
+
+    try!(check_limits(&document, root_node));
+    try!(check_complexity(&document, operation_name, root_node, variables));
+
+    Ok(document)
+}
+
+fn parse_and_validate_document<'a, CtxT, QueryT, MutationT, SubscriptionT>(
+    document_source: &'a str,
+    root_node: &RootNode<CtxT, QueryT, MutationT, SubscriptionT>,
+    variables: &HashMap<String, InputValue>,
+)
+    -> Result<Document, GraphQLError<'a>>
 {
     let document = try!(parse_document_source(document_source));
 
-    {
-        let errors = validate_input_values(variables, &document, &root_node.schema);
+    try!(validate_variables(&document, root_node, variables));
+    try!(validate_document(&document, root_node));
 
-        if !errors.is_empty() {
-            return Err(GraphQLError::ValidationError(errors));
+    Ok(document)
+}
+
+fn validate_variables<'a, CtxT, QueryT, MutationT, SubscriptionT>(
+    document: &Document,
+    root_node: &RootNode<CtxT, QueryT, MutationT, SubscriptionT>,
+    variables: &HashMap<String, InputValue>,
+)
+    -> Result<(), GraphQLError<'a>>
+{
+    let errors = validate_input_values(variables, document, &root_node.schema);
+
+    if !errors.is_empty() {
+        return Err(GraphQLError::ValidationError(errors));
+    }
+
+    Ok(())
+}
+
+fn validate_document<'a, CtxT, QueryT, MutationT, SubscriptionT>(
+    document: &Document,
+    root_node: &RootNode<CtxT, QueryT, MutationT, SubscriptionT>,
+)
+    -> Result<(), GraphQLError<'a>>
+{
+    let schema = &root_node.schema;
+
+    let errors = match root_node.validator {
+        Some(ref validator) => validator.validate(schema, document),
+        None => {
+            let mut ctx = ValidatorContext::new(schema, document);
+            visit_all_rules(&mut ctx, document);
+            ctx.into_errors()
         }
+    };
+
+    if !errors.is_empty() {
+        return Err(GraphQLError::ValidationError(errors));
     }
 
-    {
-        let mut ctx = ValidatorContext::new(&root_node.schema, &document);
-        visit_all_rules(&mut ctx, &document);
+    Ok(())
+}
+
+fn check_limits<'a, CtxT, QueryT, MutationT, SubscriptionT>(
+    document: &Document,
+    root_node: &RootNode<CtxT, QueryT, MutationT, SubscriptionT>,
+)
+    -> Result<(), GraphQLError<'a>>
+{
+    if let Some(ref limits) = root_node.limits {
+        let errors = limits.check(document);
 
-        let errors = ctx.into_errors();
         if !errors.is_empty() {
             return Err(GraphQLError::ValidationError(errors));
         }
     }
 
-    execute_validated_query(document, operation_name, root_node, variables, context)
+    Ok(())
+}
+
+fn check_complexity<'a, CtxT, QueryT, MutationT, SubscriptionT>(
+    document: &Document,
+    operation_name: Option<&str>,
+    root_node: &RootNode<CtxT, QueryT, MutationT, SubscriptionT>,
+    variables: &HashMap<String, InputValue>,
+)
+    -> Result<(), GraphQLError<'a>>
+{
+    if let Some(ref complexity) = root_node.complexity {
+        if let Some(max_cost) = complexity.max() {
+            let cost = complexity.cost(document, operation_name, &root_node.schema, variables);
+
+            if cost > max_cost {
+                return Err(GraphQLError::QueryTooComplex { cost: cost, max_cost: max_cost });
+            }
+        }
+    }
+
+    Ok(())
 }
 
 impl<'a> From<Spanning<ParseError<'a>>> for GraphQLError<'a> {
@@ -272,17 +558,62 @@ impl<'a> From<Spanning<ParseError<'a>>> for GraphQLError<'a> {
     }
 }
 
+impl<'a> GraphQLError<'a> {
+    /// The spans of the query source that the error points to
+    ///
+    /// Parse errors have a single span and validation errors one per
+    /// location. Errors about the operation as a whole have none.
+    pub fn spans(&self, source: &str) -> Vec<Spanning<()>> {
+        match *self {
+            GraphQLError::ParseError(ref err) => vec![Spanning::start_end(&err.start, &err.end, ())],
+            GraphQLError::ValidationError(ref errs) => errs.iter().flat_map(|e| e.spans(source)).collect(),
+            _ => Vec::new(),
+        }
+    }
+
+    /// Render the error with code frames of the query source it points to
+    ///
+    /// Meant for command line tools and logs; `source` must be the query the
+    /// error was produced for.
+    pub fn render(&self, source: &str) -> String {
+        match *self {
+            GraphQLError::ParseError(ref err) =>
+                format!("error: {}\n{}", self.message(), parser::render_code_frame(source, err)),
+            GraphQLError::ValidationError(ref errs) =>
+                errs.iter().map(|e| e.render(source)).collect::<Vec<_>>().join("\n"),
+            _ => format!("error: {}\n", self.message()),
+        }
+    }
+
+    /// The error message, with one line per validation error
+    pub fn message(&self) -> String {
+        match *self {
+            GraphQLError::MultipleOperationsProvided =>
+                "Must provide operation name if query contains multiple operations.".to_owned(),
+            GraphQLError::NoOperationProvided => "Must provide an operation".to_owned(),
+            GraphQLError::UnknownOperationName => "Unknown operation".to_owned(),
+            GraphQLError::IsSubscription => "Subscription operations can not be executed as queries".to_owned(),
+            GraphQLError::NotSubscription => "Operation is not a subscription".to_owned(),
+            GraphQLError::QueryTooComplex { cost, max_cost } =>
+                format!("Query has a complexity of {}, which exceeds the maximum of {}", cost, max_cost),
+            GraphQLError::PersistedQueryNotFound => "PersistedQueryNotFound".to_owned(),
+            GraphQLError::PersistedQueryNotSupported => "PersistedQueryNotSupported".to_owned(),
+            GraphQLError::PersistedQueryHashMismatch => "provided sha does not match query".to_owned(),
+            GraphQLError::QueryNotAllowed => "Only persisted queries are allowed".to_owned(),
+            GraphQLError::SchemaMismatch => "Query was prepared for a different schema".to_owned(),
+            GraphQLError::ParseError(ref err) => format!("{}", err.item),
+            GraphQLError::ValidationError(ref errs) =>
+                errs.iter().map(|e| e.message()).collect::<Vec<_>>().join("\n"),
+        }
+    }
+}
+
 impl<'a> ToJson for GraphQLError<'a> {
     fn to_json(&self) -> Json {
         let errs = match *self {
             GraphQLError::ParseError(ref err) => parse_error_to_json(err),
             GraphQLError::ValidationError(ref errs) => errs.to_json(),
-            GraphQLError::MultipleOperationsProvided => Json::String(
-                "Must provide operation name if query contains multiple operations.".to_owned()),
-            GraphQLError::NoOperationProvided => Json::String(
-                "Must provide an operation".to_owned()),
-            GraphQLError::UnknownOperationName => Json::String(
-                "Unknown operation".to_owned()),
+            _ => message_to_json(&self.message()),
         };
 
         Json::Object(vec![
@@ -291,6 +622,16 @@ impl<'a> ToJson for GraphQLError<'a> {
     }
 }
 
+// Errors without a location are still a list of error objects, which is
+// also where automatic persisted query clients look for them by message
+fn message_to_json(message: &str) -> Json {
+    Json::Array(vec![
+        Json::Object(vec![
+            ("message".to_owned(), message.to_json()),
+        ].into_iter().collect()),
+    ])
+}
+
 fn parse_error_to_json(err: &Spanning<ParseError>) -> Json {
     Json::Array(vec![
         Json::Object(vec![
@@ -314,6 +655,15 @@ impl ToJson for RuleError {
     }
 }
 
+impl ToJson for PathSegment {
+    fn to_json(&self) -> Json {
+        match *self {
+            PathSegment::Field(ref name) => name.to_json(),
+            PathSegment::Index(index) => index.to_json(),
+        }
+    }
+}
+
 impl ToJson for SourcePosition {
     fn to_json(&self) -> Json {
         Json::Object(vec![
@@ -323,13 +673,23 @@ impl ToJson for SourcePosition {
     }
 }
 
-impl ToJson for ExecutionError {
-    fn to_json(&self) -> Json {
-        Json::Object(vec![
-            ("message".to_owned(), self.message().to_json()),
-            ("locations".to_owned(), vec![self.location().clone()].to_json()),
-            ("path".to_owned(), self.path().to_json()),
-        ].into_iter().collect())
+/// Encode the error as a JSON error object, keeping the field order of its
+/// extensions
+impl Encodable for ExecutionError {
+    fn encode<S: Encoder>(&self, s: &mut S) -> Result<(), S::Error> {
+        let field_count = if self.extensions().is_null() { 3 } else { 4 };
+
+        s.emit_struct("ExecutionError", field_count, |s| {
+            try!(s.emit_struct_field("message", 0, |s| self.message().encode(s)));
+            try!(s.emit_struct_field("locations", 1, |s| vec![self.location().clone()].to_json().encode(s)));
+            try!(s.emit_struct_field("path", 2, |s| self.path().to_json().encode(s)));
+
+            if !self.extensions().is_null() {
+                try!(s.emit_struct_field("extensions", 3, |s| self.extensions().encode(s)));
+            }
+
+            Ok(())
+        })
     }
 }
 
@@ -0,0 +1,252 @@
+/*!
+
+Batched, per-request data loading
+
+Resolving a list of objects that each look up related data, e.g. the friends
+of every character in a list, would normally make one lookup per item. A
+`Loader` collects the keys requested by sibling fields and fetches them with a
+single call to a batch function.
+
+Resolvers return `Load` values instead of the loaded data. The executor puts
+off resolving them until the rest of the current level has been resolved, at
+which point every key requested on that level is known. The first load then
+runs the batch function once for all of them, and the results are cached by
+key for the rest of the request.
+
+The loaders live in the context, so that a new, empty cache is created for
+each request. The context tells the `Load` values where to find the loader by
+implementing `LoaderSource`:
+
+```rust
+# #[macro_use] extern crate juniper;
+use std::collections::HashMap;
+use juniper::{Loader, LoaderSource, Load};
+
+struct User { id: String, friend_ids: Vec<String> }
+
+struct Context { users: Loader<String, User> }
+
+impl LoaderSource<String, User> for Context {
+    fn loader(&self) -> &Loader<String, User> {
+        &self.users
+    }
+}
+
+graphql_object!(User: Context |&self| {
+    field id() -> &String {
+        &self.id
+    }
+
+    field friends() -> Vec<Load<String, User>> {
+        self.friend_ids.iter().cloned().map(Load::new).collect()
+    }
+});
+
+fn context_factory() -> Context {
+    Context {
+        users: Loader::new(|ids: &[String]| {
+            // Fetch all users with the given ids in a single query
+            Ok(ids.iter()
+                .map(|id| (id.clone(), User { id: id.clone(), friend_ids: vec![] }))
+                .collect::<HashMap<_, _>>())
+        }),
+    }
+}
+
+# fn main() { }
+```
+
+A `Load<K, V>` has the same type in the schema as `V`. A key that the batch
+function does not return resolves to `null`, so return `Option<Load<K, V>>`
+from fields where the value may be missing. Otherwise the `null` propagates
+to the closest nullable parent, just like a non-null field that resolved to
+`null`.
+
+*/
+
+use std::collections::{HashMap, HashSet};
+use std::hash::Hash;
+use std::marker::PhantomData;
+use std::mem;
+use std::sync::{Arc, Mutex};
+
+use ast::Selection;
+use value::Value;
+use schema::meta::MetaType;
+
+use executor::{Executor, Registry, DeferredResolve, FieldError, IntoFieldResult};
+use types::base::GraphQLType;
+
+/// A per-request cache of values fetched in batches
+///
+/// Keys are registered as `Load` values are resolved. The first lookup of a
+/// key that is not yet cached fetches all registered keys with one call to
+/// the batch function.
+pub struct Loader<K, V> {
+    batch_fn: Box<Fn(&[K]) -> Result<HashMap<K, V>, FieldError> + Send + Sync>,
+    state: Mutex<LoaderState<K, V>>,
+}
+
+struct LoaderState<K, V> {
+    pending: Vec<K>,
+    pending_set: HashSet<K>,
+    cache: HashMap<K, Result<Option<Arc<V>>, FieldError>>,
+}
+
+/// Access to a loader from the execution context
+///
+/// Implement this for your context type once for every loader it holds.
+pub trait LoaderSource<K, V> {
+    /// The loader for values of type `V` with keys of type `K`
+    fn loader(&self) -> &Loader<K, V>;
+}
+
+/// A value of type `V` that will be loaded in a batch
+///
+/// Return this from a field instead of the value itself. The key is
+/// registered with the loader when the field is resolved, and the value is
+/// fetched and resolved after its siblings have registered their keys.
+pub struct Load<K, V> {
+    key: K,
+    loaded: PhantomData<V>,
+}
+
+struct DeferredLoad<K, V> {
+    key: K,
+    loaded: PhantomData<V>,
+}
+
+impl<K, V> Loader<K, V> where K: Eq + Hash + Clone {
+    /// Create a loader with a batch function
+    ///
+    /// The batch function receives the distinct keys requested since the last
+    /// batch, and returns the values it found. If it fails, the error is
+    /// reported for every field that requested one of the keys.
+    pub fn new<F>(batch_fn: F) -> Loader<K, V>
+        where F: Fn(&[K]) -> Result<HashMap<K, V>, FieldError> + Send + Sync + 'static
+    {
+        Loader {
+            batch_fn: Box::new(batch_fn),
+            state: Mutex::new(LoaderState {
+                pending: Vec::new(),
+                pending_set: HashSet::new(),
+                cache: HashMap::new(),
+            }),
+        }
+    }
+
+    /// Register a key to be fetched in the next batch
+    ///
+    /// Keys that are already cached or registered are ignored.
+    pub fn register(&self, key: &K) {
+        let mut state = self.state.lock().unwrap();
+
+        if !state.cache.contains_key(key) && !state.pending_set.contains(key) {
+            state.pending.push(key.clone());
+            state.pending_set.insert(key.clone());
+        }
+    }
+
+    /// Get the value for a key, fetching the pending batch if needed
+    ///
+    /// Returns `Ok(None)` if the batch function did not return a value for
+    /// the key.
+    ///
+    /// The batch function runs without holding the loader's lock, so it may
+    /// use the loader itself. A key that another thread is fetching at the
+    /// same time is fetched again as part of this batch.
+    pub fn get(&self, key: &K) -> Result<Option<Arc<V>>, FieldError> {
+        let mut keys = {
+            let mut state = self.state.lock().unwrap();
+
+            if let Some(result) = state.cache.get(key) {
+                return result.clone();
+            }
+
+            state.pending_set.clear();
+            mem::replace(&mut state.pending, Vec::new())
+        };
+
+        if !keys.contains(key) {
+            keys.push(key.clone());
+        }
+
+        let result = (self.batch_fn)(&keys);
+
+        let mut state = self.state.lock().unwrap();
+
+        match result {
+            Ok(mut values) => for k in keys {
+                let value = values.remove(&k).map(Arc::new);
+                state.cache.entry(k).or_insert(Ok(value));
+            },
+            Err(e) => for k in keys {
+                state.cache.entry(k).or_insert_with(|| Err(e.clone()));
+            },
+        }
+
+        state.cache[key].clone()
+    }
+}
+
+impl<K, V> Load<K, V> {
+    /// Load the value with the given key
+    pub fn new(key: K) -> Load<K, V> {
+        Load {
+            key: key,
+            loaded: PhantomData,
+        }
+    }
+
+    /// The key of the value to load
+    pub fn key(&self) -> &K {
+        &self.key
+    }
+}
+
+impl<K, V, CtxT> GraphQLType<CtxT> for Load<K, V>
+    where K: Eq + Hash + Clone + 'static,
+          V: GraphQLType<CtxT> + 'static,
+          CtxT: LoaderSource<K, V>,
+{
+    fn name() -> Option<&'static str> {
+        V::name()
+    }
+
+    fn meta(registry: &mut Registry<CtxT>) -> MetaType {
+        V::meta(registry)
+    }
+
+    fn resolve(&self, _: Option<Vec<Selection>>, executor: &mut Executor<CtxT>) -> Value {
+        executor.context().loader().register(&self.key);
+
+        executor.defer(Box::new(DeferredLoad::<K, V> {
+            key: self.key.clone(),
+            loaded: PhantomData,
+        }))
+    }
+}
+
+impl<K, V> IntoFieldResult<Load<K, V>> for Load<K, V> {
+    fn into(self) -> Result<Load<K, V>, FieldError> {
+        Ok(self)
+    }
+}
+
+impl<K, V, CtxT> DeferredResolve<CtxT> for DeferredLoad<K, V>
+    where K: Eq + Hash + Clone,
+          V: GraphQLType<CtxT>,
+          CtxT: LoaderSource<K, V>,
+{
+    fn resolve(self: Box<Self>, executor: &mut Executor<CtxT>) -> Value {
+        match executor.context().loader().get(&self.key) {
+            Ok(Some(value)) => executor.resolve_into_value(&*value),
+            Ok(None) => Value::null(),
+            Err(e) => {
+                let location = executor.location().clone();
+                executor.push_error(e, location);
+                Value::null()
+            }
+        }
+    }
+}
@@ -26,9 +26,10 @@ macro_rules! __graphql__args {
         $args:ident, $executorvar:ident,
         $name:ident $(= $default:tt)* : $ty:ty $(as $desc:tt)*, $($rest:tt)*
     ) => {
-        let $name: $ty = $args
-            .get(&$crate::to_snake_case(stringify!($name)))
-            .expect("Argument missing - validation must have failed");
+        let $name: $ty = match $args.try_get(&$crate::to_snake_case(stringify!($name))) {
+            Ok(v) => v,
+            Err(e) => return Err($crate::FieldError::from(e)),
+        };
         __graphql__args!(@assign_arg_vars, $args, $executorvar, $($rest)*);
     };
 
@@ -37,9 +38,10 @@ macro_rules! __graphql__args {
         $args:ident, $executorvar:ident,
         $name:ident  $(= $default:tt)* : $ty:ty $(as $desc:expr)*
     ) => {
-        let $name: $ty = $args
-            .get(&$crate::to_snake_case(stringify!($name)))
-            .expect("Argument missing - validation must have failed");
+        let $name: $ty = match $args.try_get(&$crate::to_snake_case(stringify!($name))) {
+            Ok(v) => v,
+            Err(e) => return Err($crate::FieldError::from(e)),
+        };
     };
 
     ( @apply_args, $reg:expr, $base:expr, ( ) ) => {
@@ -40,22 +40,22 @@ macro_rules! graphql_input_object {
     ( @apply_description, , $val:expr ) => { $val };
     ( @apply_description, $descr:tt , $val:expr ) => { $val.description($descr) };
 
-    // Generate the FromInputValue::from method body, provided a
+    // Generate the FromInputValue::try_from_input method body, provided a
     // HashMap<&str, &InputValue> in $var
     (
         @generate_from_input_value,
         $name:tt, $var:tt,
         ( $($field_name:ident : $field_type:ty $(as $descr:tt)* $(,)* ),* )
     ) => {
-        Some($name {
+        Ok($name {
             $( $field_name: {
                 let n: String = $crate::to_snake_case(stringify!($field_name));
-                let v: Option<&&$crate::InputValue> = $var.get(&n[..]);
+                let null = $crate::InputValue::null();
+                let v: &$crate::InputValue = $var.get(&n[..]).map(|v| *v).unwrap_or(&null);
 
-                if let Some(v) = v {
-                    $crate::FromInputValue::from(v).unwrap()
-                } else {
-                    $crate::FromInputValue::from(&$crate::InputValue::null()).unwrap()
+                match $crate::FromInputValue::try_from_input(v) {
+                    Ok(v) => v,
+                    Err(e) => return Err(format!("In field \"{}\": {}", n, e)),
                 }
             } ),*
         })
@@ -139,11 +139,15 @@ macro_rules! graphql_input_object {
 
         impl $crate::FromInputValue for $name {
             fn from(value: &$crate::InputValue) -> Option<$name> {
+                <$name as $crate::FromInputValue>::try_from_input(value).ok()
+            }
+
+            fn try_from_input(value: &$crate::InputValue) -> Result<$name, String> {
                 if let Some(obj) = value.to_object_value() {
                     graphql_input_object!(@generate_from_input_value, $name, obj, $fields)
                 }
                 else {
-                   None
+                    Err(format!("Unexpected value {}", value))
                 }
             }
         }
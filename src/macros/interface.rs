@@ -279,7 +279,7 @@ macro_rules! graphql_interface {
         });
 
         impl<$($lifetime)*> $crate::IntoFieldResult<$name> for $name {
-            fn into(self) -> $crate::FieldResult<$name> {
+            fn into(self) -> Result<$name, $crate::FieldError> {
                 Ok(self)
             }
         }
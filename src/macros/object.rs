@@ -120,14 +120,17 @@ even have to be backed by a trait!
 
 ## Emitting errors
 
-`FieldResult<T>` is a simple type alias for `Result<T, String>`. In the end,
-errors that fields emit are serialized into strings in the response. However,
-the execution system will keep track of the source of all errors, and will
-continue executing despite some fields failing.
+`FieldResult<T>` is a simple type alias for `Result<T, String>`. Fields can
+also return `Result<T, E>` for any error `E` that converts into a
+`FieldError`, e.g. a `FieldError` created with `FieldError::new` to attach
+additional data such as an error code. In the end, errors that fields emit
+are serialized into the response. However, the execution system
+will keep track of the source of all errors, and will continue executing
+despite some fields failing.
 
 ```
 # #[macro_use] extern crate juniper;
-# use juniper::FieldResult;
+# use juniper::{FieldError, FieldResult, Value};
 struct User { id: String }
 
 graphql_object!(User: () |&self| {
@@ -138,6 +141,12 @@ graphql_object!(User: () |&self| {
     field name() -> FieldResult<&String> {
         Err("Does not have a name".to_owned())
     }
+
+    field email() -> Result<&String, FieldError> {
+        Err(FieldError::new(
+            "Not authorized",
+            Value::object(vec![("code", Value::string("FORBIDDEN"))].into_iter().collect())))
+    }
 });
 
 # fn main() { }
@@ -394,7 +403,7 @@ macro_rules! graphql_object {
         });
 
         impl<$($lifetime)*> $crate::IntoFieldResult<$name> for $name {
-            fn into(self) -> $crate::FieldResult<$name> {
+            fn into(self) -> Result<$name, $crate::FieldError> {
                 Ok(self)
             }
         }
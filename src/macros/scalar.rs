@@ -94,7 +94,7 @@ macro_rules! graphql_scalar {
         }
 
         impl $crate::IntoFieldResult<$name> for $name {
-            fn into(self) -> $crate::FieldResult<$name> {
+            fn into(self) -> Result<$name, $crate::FieldError> {
                 Ok(self)
             }
         }
@@ -1,6 +1,6 @@
 use std::collections::HashMap;
 
-use value::Value;
+use value::{Value, Object};
 use schema::model::RootNode;
 
 
@@ -66,7 +66,7 @@ graphql_object!(Root: () |&self| {
     field enum_deprecation() -> EnumDeprecation { EnumDeprecation::Foo }
 });
 
-fn run_type_info_query<F>(doc: &str, f: F) where F: Fn((&HashMap<String, Value>, &Vec<Value>)) -> () {
+fn run_type_info_query<F>(doc: &str, f: F) where F: Fn((&Object, &Vec<Value>)) -> () {
     let schema = RootNode::new(Root {}, ());
 
     let (result, errs) = ::execute(doc, None, &schema, &HashMap::new(), &())
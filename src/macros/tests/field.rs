@@ -1,6 +1,4 @@
-use std::collections::HashMap;
-
-use value::Value;
+use value::{Value, Object};
 use ast::InputValue;
 use schema::model::RootNode;
 
@@ -48,7 +46,7 @@ graphql_interface!(Interface: () |&self| {
 });
 
 fn run_field_info_query<F>(type_name: &str, field_name: &str, f: F)
-    where F: Fn(&HashMap<String, Value>) -> ()
+    where F: Fn(&Object) -> ()
 {
     let doc = r#"
     query ($typeName: String!) {
@@ -1,7 +1,7 @@
 use std::collections::HashMap;
 
 use ast::{InputValue, FromInputValue};
-use value::Value;
+use value::{Value, Object};
 use schema::model::RootNode;
 
 struct Root;
@@ -48,6 +48,12 @@ graphql_input_object!(
     }
 );
 
+graphql_input_object!(
+    struct WithList {
+        field_one: Vec<String>,
+    }
+);
+
 graphql_object!(Root: () |&self| {
     field test_field(
         a1: DefaultName,
@@ -61,7 +67,7 @@ graphql_object!(Root: () |&self| {
     }
 });
 
-fn run_type_info_query<F>(doc: &str, f: F) where F: Fn(&HashMap<String, Value>, &Vec<Value>) -> () {
+fn run_type_info_query<F>(doc: &str, f: F) where F: Fn(&Object, &Vec<Value>) -> () {
     let schema = RootNode::new(Root {}, ());
 
     let (result, errs) = ::execute(doc, None, &schema, &HashMap::new(), &())
@@ -151,6 +157,56 @@ fn default_name_input_value() {
     assert_eq!(dv.field_two, "number two");
 }
 
+#[test]
+fn default_name_invalid_input_value() {
+    let iv = InputValue::object(vec![
+        ("fieldOne", InputValue::string("number one")),
+        ("fieldTwo", InputValue::int(2)),
+    ].into_iter().collect());
+
+    let dv: Option<DefaultName> = FromInputValue::from(&iv);
+
+    assert!(dv.is_none());
+
+    let err = <DefaultName as FromInputValue>::try_from_input(&iv).err();
+
+    assert_eq!(err, Some(r#"In field "fieldTwo": Unexpected value 2"#.to_owned()));
+}
+
+#[test]
+fn default_name_missing_input_value() {
+    let iv = InputValue::object(vec![
+        ("fieldOne", InputValue::string("number one")),
+    ].into_iter().collect());
+
+    let err = <DefaultName as FromInputValue>::try_from_input(&iv).err();
+
+    assert_eq!(err, Some(r#"In field "fieldTwo": Unexpected value null"#.to_owned()));
+}
+
+#[test]
+fn non_object_input_value() {
+    let iv = InputValue::string("number one");
+
+    let err = <DefaultName as FromInputValue>::try_from_input(&iv).err();
+
+    assert_eq!(err, Some(r#"Unexpected value "number one""#.to_owned()));
+}
+
+#[test]
+fn invalid_list_element_input_value() {
+    let iv = InputValue::object(vec![
+        ("fieldOne", InputValue::list(vec![
+            InputValue::string("one"),
+            InputValue::int(2),
+        ])),
+    ].into_iter().collect());
+
+    let err = <WithList as FromInputValue>::try_from_input(&iv).err();
+
+    assert_eq!(err, Some(r#"In field "fieldOne": In element #1: Unexpected value 2"#.to_owned()));
+}
+
 #[test]
 fn no_trailing_comma_introspection() {
     let doc = r#"
@@ -1,8 +1,7 @@
-use std::collections::HashMap;
 use std::marker::PhantomData;
 
 use ast::InputValue;
-use value::Value;
+use value::{Value, Object};
 use schema::model::RootNode;
 
 /*
@@ -113,7 +112,7 @@ graphql_object!(<'a> Root: () as "Root" |&self| {
 
 
 fn run_type_info_query<F>(type_name: &str, f: F)
-    where F: Fn(&HashMap<String, Value>, &Vec<Value>) -> ()
+    where F: Fn(&Object, &Vec<Value>) -> ()
 {
     let doc = r#"
     query ($typeName: String!) {
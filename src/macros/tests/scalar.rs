@@ -1,6 +1,6 @@
 use std::collections::HashMap;
 
-use value::Value;
+use value::{Value, Object};
 use schema::model::RootNode;
 
 struct DefaultName(i64);
@@ -68,7 +68,7 @@ graphql_object!(Root: () |&self| {
     field scalar_description() -> ScalarDescription { ScalarDescription(0) }
 });
 
-fn run_type_info_query<F>(doc: &str, f: F) where F: Fn(&HashMap<String, Value>) -> () {
+fn run_type_info_query<F>(doc: &str, f: F) where F: Fn(&Object) -> () {
     let schema = RootNode::new(Root {}, ());
 
     let (result, errs) = ::execute(doc, None, &schema, &HashMap::new(), &())
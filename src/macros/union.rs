@@ -148,7 +148,7 @@ macro_rules! graphql_union {
         });
 
         impl<$($lifetime)*> $crate::IntoFieldResult<$name> for $name {
-            fn into(self) -> $crate::FieldResult<$name> {
+            fn into(self) -> Result<$name, $crate::FieldError> {
                 Ok(self)
             }
         }
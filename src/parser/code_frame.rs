@@ -0,0 +1,92 @@
+use std::fmt;
+
+use parser::{Lexer, SourcePosition, Spanning};
+
+/// Find the span of the token starting at a position in the source
+///
+/// Positions that do not start a token, e.g. because they point into a
+/// comment or past the end of the source, get a zero-width span.
+pub fn token_span(source: &str, position: &SourcePosition) -> Spanning<()> {
+    for token in Lexer::new(source) {
+        match token {
+            Ok(ref token) if token.start.index() == position.index() =>
+                return Spanning::start_end(&token.start, &token.end, ()),
+            Ok(ref token) if token.start.index() < position.index() => {}
+            _ => break,
+        }
+    }
+
+    Spanning::zero_width(position, ())
+}
+
+/// Render the lines of the source covered by a span
+///
+/// The location of the span is printed first, followed by the source lines
+/// with the first character of the span marked by a caret and the rest
+/// underlined:
+///
+/// ```text
+///  --> 1:10
+///   |
+/// 1 | { hero { nmae } }
+///   |          ^~~~
+/// ```
+///
+/// Line and column numbers start at one.
+pub fn render_code_frame<T: fmt::Debug>(source: &str, span: &Spanning<T>) -> String {
+    let lines = source.split('\n')
+        .map(|line| line.trim_end_matches('\r'))
+        .collect::<Vec<_>>();
+
+    let start = &span.start;
+    let end = if span.end.index() > start.index() { &span.end } else { start };
+
+    let first_line = start.line();
+    let last_line = if end.line() > first_line && end.column() == 0 {
+        end.line() - 1
+    }
+    else {
+        end.line()
+    };
+    let last_line = if last_line < lines.len() { last_line } else { lines.len() - 1 };
+    let first_line = if first_line < last_line { first_line } else { last_line };
+
+    let width = format!("{}", last_line + 1).len();
+    let gutter = " ".repeat(width);
+
+    let mut frame = format!("{}--> {}:{}\n{} |\n", gutter, start.line() + 1, start.column() + 1, gutter);
+
+    for line_number in first_line..last_line + 1 {
+        let line = lines[line_number].chars().collect::<Vec<_>>();
+
+        let from = if line_number == start.line() {
+            start.column()
+        }
+        else {
+            line.iter().position(|c| !c.is_whitespace()).unwrap_or(line.len())
+        };
+        let to = if line_number == end.line() { end.column() } else { line.len() };
+
+        let mut underline = (0..from)
+            .map(|i| if line.get(i) == Some(&'\t') { '\t' } else { ' ' })
+            .collect::<String>();
+
+        if line_number == start.line() {
+            underline.push('^');
+            underline.extend((from + 1..to).map(|_| '~'));
+        }
+        else {
+            underline.extend((from..to).map(|_| '~'));
+        }
+
+        let source_line = format!("{:>width$} | {}", line_number + 1, line.iter().collect::<String>(), width = width);
+        let underline_line = format!("{} | {}", gutter, underline);
+
+        frame.push_str(source_line.trim_end());
+        frame.push('\n');
+        frame.push_str(underline_line.trim_end());
+        frame.push('\n');
+    }
+
+    frame
+}
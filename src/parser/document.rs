@@ -1,12 +1,19 @@
 use ast::{Definition, Document, OperationType,
           VariableDefinitions, VariableDefinition, InputValue,
           Operation, Fragment, Selection, Directive, Field, Arguments,
-          FragmentSpread, InlineFragment, Type};
+          FragmentSpread, InlineFragment, Type,
+          SchemaDefinition, TypeDefinition, DirectiveDefinition,
+          ScalarTypeDefinition, ObjectTypeDefinition, InterfaceTypeDefinition,
+          UnionTypeDefinition, EnumTypeDefinition, InputObjectTypeDefinition,
+          FieldDefinition, InputValueDefinition, EnumValueDefinition};
 
-use parser::{Lexer, Parser, Spanning, UnlocatedParseResult, OptionParseResult, ParseResult, ParseError, Token};
+use parser::{Lexer, Parser, Spanning, SourcePosition, UnlocatedParseResult, OptionParseResult, ParseResult, ParseError, Token};
 use parser::value::parse_value_literal;
 
-#[doc(hidden)]
+/// Parse a GraphQL document
+///
+/// Accepts both executable definitions (operations and fragments) and type
+/// system definitions and extensions, as found in `.graphql` schema files.
 pub fn parse_document_source(s: &str) -> UnlocatedParseResult<Document> {
     let mut lexer = Lexer::new(s);
     let mut parser = try!(Parser::new(&mut lexer).map_err(|s| s.map(ParseError::LexerError)));
@@ -27,10 +34,432 @@ fn parse_document<'a>(parser: &mut Parser<'a>) -> UnlocatedParseResult<'a, Docum
 
 fn parse_definition<'a>(parser: &mut Parser<'a>) -> UnlocatedParseResult<'a, Definition> {
     match parser.peek().item {
-        Token::CurlyOpen | Token::Name("query") | Token::Name("mutation") =>
+        Token::CurlyOpen | Token::Name("query") | Token::Name("mutation") |
+        Token::Name("subscription") =>
             Ok(Definition::Operation(try!(parse_operation_definition(parser)))),
         Token::Name("fragment") =>
             Ok(Definition::Fragment(try!(parse_fragment_definition(parser)))),
+        Token::String(_) |
+        Token::Name("schema") | Token::Name("scalar") | Token::Name("type") |
+        Token::Name("interface") | Token::Name("union") | Token::Name("enum") |
+        Token::Name("input") | Token::Name("directive") =>
+            parse_type_system_definition(parser),
+        Token::Name("extend") =>
+            parse_type_system_extension(parser),
+        _ => Err(parser.next().map(ParseError::UnexpectedToken)),
+    }
+}
+
+fn parse_type_system_definition<'a>(parser: &mut Parser<'a>) -> UnlocatedParseResult<'a, Definition> {
+    let start_pos = parser.peek().start.clone();
+    let description = try!(parse_description(parser));
+
+    match parser.peek().item {
+        Token::Name("schema") =>
+            Ok(Definition::Schema(try!(parse_schema_definition(parser, start_pos, description, false)))),
+        Token::Name("directive") =>
+            Ok(Definition::Directive(try!(parse_directive_definition(parser, start_pos, description)))),
+        Token::Name("scalar") | Token::Name("type") | Token::Name("interface") |
+        Token::Name("union") | Token::Name("enum") | Token::Name("input") =>
+            Ok(Definition::Type(try!(parse_type_definition(parser, start_pos, description)))),
+        _ => Err(parser.next().map(ParseError::UnexpectedToken)),
+    }
+}
+
+fn parse_type_system_extension<'a>(parser: &mut Parser<'a>) -> UnlocatedParseResult<'a, Definition> {
+    let Spanning { start: start_pos, .. } = try!(parser.expect(&Token::Name("extend")));
+
+    let definition = match parser.peek().item {
+        Token::Name("schema") =>
+            Definition::SchemaExtension(try!(parse_schema_definition(parser, start_pos, None, true))),
+        Token::Name("scalar") | Token::Name("type") | Token::Name("interface") |
+        Token::Name("union") | Token::Name("enum") | Token::Name("input") =>
+            Definition::TypeExtension(try!(parse_type_definition(parser, start_pos, None))),
+        _ => return Err(parser.next().map(ParseError::UnexpectedToken)),
+    };
+
+    // An extension has to add at least one directive or member
+    if is_empty_extension(&definition) {
+        return match parser.peek().item {
+            Token::EndOfFile =>
+                Err(Spanning::zero_width(&parser.peek().start, ParseError::UnexpectedEndOfFile)),
+            _ => Err(parser.next().map(ParseError::UnexpectedToken)),
+        };
+    }
+
+    Ok(definition)
+}
+
+fn is_empty_extension(definition: &Definition) -> bool {
+    match *definition {
+        Definition::SchemaExtension(ref schema) =>
+            schema.item.directives.is_none() && schema.item.operation_types.is_empty(),
+        Definition::TypeExtension(ref type_def) => match type_def.item {
+            TypeDefinition::Scalar(ref t) => t.directives.is_none(),
+            TypeDefinition::Object(ref t) =>
+                t.directives.is_none() && t.interfaces.is_empty() && t.fields.is_empty(),
+            TypeDefinition::Interface(ref t) =>
+                t.directives.is_none() && t.interfaces.is_empty() && t.fields.is_empty(),
+            TypeDefinition::Union(ref t) => t.directives.is_none() && t.members.is_empty(),
+            TypeDefinition::Enum(ref t) => t.directives.is_none() && t.values.is_empty(),
+            TypeDefinition::InputObject(ref t) => t.directives.is_none() && t.fields.is_empty(),
+        },
+        _ => false,
+    }
+}
+
+fn parse_description<'a>(parser: &mut Parser<'a>) -> OptionParseResult<'a, String> {
+    match parser.peek().item {
+        Token::String(_) =>
+            Ok(Some(parser.next().map(|token|
+                if let Token::String(s) = token {
+                    s
+                }
+                else {
+                    panic!("Internal parse error in `parse_description`");
+                }))),
+        _ => Ok(None),
+    }
+}
+
+fn parse_schema_definition<'a>(
+    parser: &mut Parser<'a>,
+    start_pos: SourcePosition,
+    description: Option<Spanning<String>>,
+    is_extension: bool,
+)
+    -> ParseResult<'a, SchemaDefinition>
+{
+    let Spanning { end: mut end_pos, .. } = try!(parser.expect(&Token::Name("schema")));
+    let directives = try!(parse_directives(parser));
+
+    if let Some(ref directives) = directives {
+        end_pos = directives.end.clone();
+    }
+
+    let operation_types = if !is_extension || parser.peek().item == Token::CurlyOpen {
+        let operation_types = try!(parser.delimited_nonempty_list(
+            &Token::CurlyOpen,
+            parse_operation_type_definition,
+            &Token::CurlyClose));
+        end_pos = operation_types.end.clone();
+        operation_types.item.into_iter().map(|s| s.item).collect()
+    }
+    else {
+        Vec::new()
+    };
+
+    Ok(Spanning::start_end(
+        &start_pos,
+        &end_pos,
+        SchemaDefinition {
+            description: description,
+            directives: directives.map(|s| s.item),
+            operation_types: operation_types,
+        }))
+}
+
+fn parse_operation_type_definition<'a>(parser: &mut Parser<'a>)
+    -> ParseResult<'a, (Spanning<OperationType>, Spanning<String>)>
+{
+    let operation_type = try!(parse_operation_type(parser));
+    try!(parser.expect(&Token::Colon));
+    let type_name = try!(parser.expect_name());
+
+    Ok(Spanning::start_end(
+        &operation_type.start.clone(),
+        &type_name.end.clone(),
+        (operation_type, type_name)))
+}
+
+fn parse_type_definition<'a>(
+    parser: &mut Parser<'a>,
+    start_pos: SourcePosition,
+    description: Option<Spanning<String>>,
+)
+    -> ParseResult<'a, TypeDefinition>
+{
+    let keyword = try!(parser.expect_name());
+    let name = try!(parser.expect_name());
+    let mut end_pos = name.end.clone();
+
+    let interfaces = match keyword.item.as_str() {
+        "type" | "interface" => try!(parse_implements_interfaces(parser)),
+        _ => None,
+    };
+
+    if let Some(ref interfaces) = interfaces {
+        end_pos = interfaces.end.clone();
+    }
+
+    let directives = try!(parse_directives(parser));
+
+    if let Some(ref directives) = directives {
+        end_pos = directives.end.clone();
+    }
+
+    let directives = directives.map(|s| s.item);
+    let interfaces = interfaces.map_or_else(Vec::new, |s| s.item);
+
+    let type_def = match keyword.item.as_str() {
+        "scalar" => TypeDefinition::Scalar(ScalarTypeDefinition {
+            description: description,
+            name: name,
+            directives: directives,
+        }),
+        "type" | "interface" => {
+            let fields = try!(parse_optional_definition_list(parser, &Token::CurlyOpen, parse_field_definition, &Token::CurlyClose));
+
+            if let Some(ref fields) = fields {
+                end_pos = fields.end.clone();
+            }
+
+            let fields = fields.map_or_else(Vec::new, |s| s.item);
+
+            if keyword.item == "type" {
+                TypeDefinition::Object(ObjectTypeDefinition {
+                    description: description,
+                    name: name,
+                    interfaces: interfaces,
+                    directives: directives,
+                    fields: fields,
+                })
+            }
+            else {
+                TypeDefinition::Interface(InterfaceTypeDefinition {
+                    description: description,
+                    name: name,
+                    interfaces: interfaces,
+                    directives: directives,
+                    fields: fields,
+                })
+            }
+        },
+        "union" => {
+            let members = try!(parse_union_members(parser));
+
+            if let Some(ref members) = members {
+                end_pos = members.end.clone();
+            }
+
+            TypeDefinition::Union(UnionTypeDefinition {
+                description: description,
+                name: name,
+                directives: directives,
+                members: members.map_or_else(Vec::new, |s| s.item),
+            })
+        },
+        "enum" => {
+            let values = try!(parse_optional_definition_list(parser, &Token::CurlyOpen, parse_enum_value_definition, &Token::CurlyClose));
+
+            if let Some(ref values) = values {
+                end_pos = values.end.clone();
+            }
+
+            TypeDefinition::Enum(EnumTypeDefinition {
+                description: description,
+                name: name,
+                directives: directives,
+                values: values.map_or_else(Vec::new, |s| s.item),
+            })
+        },
+        "input" => {
+            let fields = try!(parse_optional_definition_list(parser, &Token::CurlyOpen, parse_input_value_definition, &Token::CurlyClose));
+
+            if let Some(ref fields) = fields {
+                end_pos = fields.end.clone();
+            }
+
+            TypeDefinition::InputObject(InputObjectTypeDefinition {
+                description: description,
+                name: name,
+                directives: directives,
+                fields: fields.map_or_else(Vec::new, |s| s.item),
+            })
+        },
+        _ => return Err(keyword.map(|_| ParseError::UnexpectedToken(Token::Name("type")))),
+    };
+
+    Ok(Spanning::start_end(&start_pos, &end_pos, type_def))
+}
+
+fn parse_optional_definition_list<'a, T, F>(parser: &mut Parser<'a>, opening: &Token, parse: F, closing: &Token)
+    -> OptionParseResult<'a, Vec<Spanning<T>>>
+    where T: ::std::fmt::Debug, F: Fn(&mut Parser<'a>) -> ParseResult<'a, T>
+{
+    if &parser.peek().item == opening {
+        Ok(Some(try!(parser.delimited_nonempty_list(opening, parse, closing))))
+    }
+    else {
+        Ok(None)
+    }
+}
+
+fn parse_implements_interfaces<'a>(parser: &mut Parser<'a>) -> OptionParseResult<'a, Vec<Spanning<String>>> {
+    if parser.peek().item != Token::Name("implements") {
+        return Ok(None);
+    }
+
+    let Spanning { start: start_pos, .. } = parser.next();
+    try!(parser.skip(&Token::Amp));
+
+    let mut names = vec![try!(parser.expect_name())];
+
+    while try!(parser.skip(&Token::Amp)).is_some() {
+        names.push(try!(parser.expect_name()));
+    }
+
+    let end_pos = names.last().expect("Interface list is never empty").end.clone();
+
+    Ok(Some(Spanning::start_end(&start_pos, &end_pos, names)))
+}
+
+fn parse_union_members<'a>(parser: &mut Parser<'a>) -> OptionParseResult<'a, Vec<Spanning<String>>> {
+    let start_pos = match try!(parser.skip(&Token::Equals)) {
+        Some(Spanning { start, .. }) => start,
+        None => return Ok(None),
+    };
+
+    try!(parser.skip(&Token::Pipe));
+
+    let mut names = vec![try!(parser.expect_name())];
+
+    while try!(parser.skip(&Token::Pipe)).is_some() {
+        names.push(try!(parser.expect_name()));
+    }
+
+    let end_pos = names.last().expect("Union member list is never empty").end.clone();
+
+    Ok(Some(Spanning::start_end(&start_pos, &end_pos, names)))
+}
+
+fn parse_field_definition<'a>(parser: &mut Parser<'a>) -> ParseResult<'a, FieldDefinition> {
+    let description = try!(parse_description(parser));
+    let name = try!(parser.expect_name());
+    let arguments = try!(parse_optional_definition_list(parser, &Token::ParenOpen, parse_input_value_definition, &Token::ParenClose));
+    try!(parser.expect(&Token::Colon));
+    let field_type = try!(parse_type(parser));
+    let directives = try!(parse_directives(parser));
+
+    Ok(Spanning::start_end(
+        &description.as_ref().unwrap_or(&name).start.clone(),
+        &directives.as_ref().map_or(&field_type.end, |s| &s.end).clone(),
+        FieldDefinition {
+            description: description,
+            name: name,
+            arguments: arguments.map(|s| s.item),
+            field_type: field_type,
+            directives: directives.map(|s| s.item),
+        }))
+}
+
+fn parse_input_value_definition<'a>(parser: &mut Parser<'a>) -> ParseResult<'a, InputValueDefinition> {
+    let description = try!(parse_description(parser));
+    let name = try!(parser.expect_name());
+    try!(parser.expect(&Token::Colon));
+    let value_type = try!(parse_type(parser));
+
+    let default_value = if try!(parser.skip(&Token::Equals)).is_some() {
+            Some(try!(parse_value_literal(parser, true)))
+        }
+        else {
+            None
+        };
+
+    let directives = try!(parse_directives(parser));
+
+    Ok(Spanning::start_end(
+        &description.as_ref().unwrap_or(&name).start.clone(),
+        &directives.as_ref().map(|s| &s.end)
+            .or_else(|| default_value.as_ref().map(|s| &s.end))
+            .unwrap_or(&value_type.end)
+            .clone(),
+        InputValueDefinition {
+            description: description,
+            name: name,
+            value_type: value_type,
+            default_value: default_value,
+            directives: directives.map(|s| s.item),
+        }))
+}
+
+fn parse_enum_value_definition<'a>(parser: &mut Parser<'a>) -> ParseResult<'a, EnumValueDefinition> {
+    let description = try!(parse_description(parser));
+
+    match parser.peek().item {
+        Token::Name("true") | Token::Name("false") | Token::Name("null") =>
+            return Err(parser.next().map(ParseError::UnexpectedToken)),
+        _ => (),
+    }
+
+    let name = try!(parser.expect_name());
+    let directives = try!(parse_directives(parser));
+
+    Ok(Spanning::start_end(
+        &description.as_ref().unwrap_or(&name).start.clone(),
+        &directives.as_ref().map_or(&name.end, |s| &s.end).clone(),
+        EnumValueDefinition {
+            description: description,
+            name: name,
+            directives: directives.map(|s| s.item),
+        }))
+}
+
+fn parse_directive_definition<'a>(
+    parser: &mut Parser<'a>,
+    start_pos: SourcePosition,
+    description: Option<Spanning<String>>,
+)
+    -> ParseResult<'a, DirectiveDefinition>
+{
+    try!(parser.expect(&Token::Name("directive")));
+    try!(parser.expect(&Token::At));
+    let name = try!(parser.expect_name());
+    let arguments = try!(parse_optional_definition_list(parser, &Token::ParenOpen, parse_input_value_definition, &Token::ParenClose));
+
+    let repeatable = if parser.peek().item == Token::Name("repeatable") {
+            parser.next();
+            true
+        }
+        else {
+            false
+        };
+
+    try!(parser.expect(&Token::Name("on")));
+    try!(parser.skip(&Token::Pipe));
+
+    let mut locations = vec![try!(parse_directive_location(parser))];
+
+    while try!(parser.skip(&Token::Pipe)).is_some() {
+        locations.push(try!(parse_directive_location(parser)));
+    }
+
+    let end_pos = locations.last().expect("Directive location list is never empty").end.clone();
+
+    Ok(Spanning::start_end(
+        &start_pos,
+        &end_pos,
+        DirectiveDefinition {
+            description: description,
+            name: name,
+            arguments: arguments.map(|s| s.item),
+            repeatable: repeatable,
+            locations: locations,
+        }))
+}
+
+fn parse_directive_location<'a>(parser: &mut Parser<'a>) -> ParseResult<'a, String> {
+    match parser.peek().item {
+        Token::Name("QUERY") | Token::Name("MUTATION") | Token::Name("SUBSCRIPTION") |
+        Token::Name("FIELD") | Token::Name("FRAGMENT_DEFINITION") | Token::Name("FRAGMENT_SPREAD") |
+        Token::Name("INLINE_FRAGMENT") | Token::Name("VARIABLE_DEFINITION") |
+        Token::Name("SCHEMA") | Token::Name("SCALAR") | Token::Name("OBJECT") |
+        Token::Name("FIELD_DEFINITION") | Token::Name("ARGUMENT_DEFINITION") |
+        Token::Name("INTERFACE") | Token::Name("UNION") | Token::Name("ENUM") |
+        Token::Name("ENUM_VALUE") | Token::Name("INPUT_OBJECT") |
+        Token::Name("INPUT_FIELD_DEFINITION") => parser.expect_name(),
+        Token::EndOfFile =>
+            Err(Spanning::zero_width(&parser.peek().start, ParseError::UnexpectedEndOfFile)),
         _ => Err(parser.next().map(ParseError::UnexpectedToken)),
     }
 }
@@ -246,6 +675,7 @@ fn parse_operation_type<'a>(parser: &mut Parser<'a>) -> ParseResult<'a, Operatio
     match parser.peek().item {
         Token::Name("query") => Ok(parser.next().map(|_| OperationType::Query)),
         Token::Name("mutation") => Ok(parser.next().map(|_| OperationType::Mutation)),
+        Token::Name("subscription") => Ok(parser.next().map(|_| OperationType::Subscription)),
         _ => Err(parser.next().map(ParseError::UnexpectedToken))
     }
 }
@@ -37,6 +37,7 @@ pub enum Token<'a> {
     Equals,
     At,
     Pipe,
+    Amp,
     EndOfFile,
 }
 
@@ -199,6 +200,12 @@ impl<'a> Lexer<'a> {
     }
 
     fn scan_string(&mut self) -> LexerResult<'a> {
+        if let Some((idx, _)) = self.peek_char() {
+            if self.source[idx..].starts_with("\"\"\"") {
+                return self.scan_block_string();
+            }
+        }
+
         let start_pos = self.position.clone();
         let (_, start_ch) = try!(self.next_char().ok_or(
             Spanning::zero_width(&self.position, LexerError::UnexpectedEndOfFile)));
@@ -275,6 +282,46 @@ impl<'a> Lexer<'a> {
         Err(Spanning::zero_width(&self.position, LexerError::UnterminatedString))
     }
 
+    fn scan_block_string(&mut self) -> LexerResult<'a> {
+        let start_pos = self.position.clone();
+
+        for _ in 0..3 {
+            self.next_char();
+        }
+
+        let mut raw = String::new();
+
+        while let Some((idx, ch)) = self.peek_char() {
+            if self.source[idx..].starts_with("\"\"\"") {
+                for _ in 0..3 {
+                    self.next_char();
+                }
+
+                return Ok(Spanning::start_end(
+                    &start_pos,
+                    &self.position,
+                    Token::String(block_string_value(&raw))));
+            }
+            else if self.source[idx..].starts_with("\\\"\"\"") {
+                for _ in 0..4 {
+                    self.next_char();
+                }
+                raw.push_str("\"\"\"");
+            }
+            else if !is_source_char(ch) {
+                return Err(Spanning::zero_width(
+                    &self.position,
+                    LexerError::UnknownCharacterInString(ch)));
+            }
+            else {
+                self.next_char();
+                raw.push(ch);
+            }
+        }
+
+        Err(Spanning::zero_width(&self.position, LexerError::UnterminatedString))
+    }
+
     fn scan_escaped_unicode(&mut self, start_pos: &SourcePosition) -> Result<char, Spanning<LexerError>> {
         let (start_idx, _) = try!(self.peek_char().ok_or(
             Spanning::zero_width(&self.position, LexerError::UnterminatedString)));
@@ -444,6 +491,7 @@ impl<'a> Iterator for Lexer<'a> {
             Some('=') => Ok(self.emit_single_char(Token::Equals)),
             Some('@') => Ok(self.emit_single_char(Token::At)),
             Some('|') => Ok(self.emit_single_char(Token::Pipe)),
+            Some('&') => Ok(self.emit_single_char(Token::Amp)),
             Some('.') => self.scan_ellipsis(),
             Some('"') => self.scan_string(),
             Some(ch) => {
@@ -486,11 +534,46 @@ impl<'a> fmt::Display for Token<'a> {
             Token::Equals => write!(f, "="),
             Token::At => write!(f, "@"),
             Token::Pipe => write!(f, "|"),
+            Token::Amp => write!(f, "&"),
             Token::EndOfFile => write!(f, "End of file"),
         }
     }
 }
 
+/// Strip the common indentation and surrounding blank lines from the raw
+/// contents of a block string, as described by the `BlockStringValue`
+/// algorithm in the specification.
+fn block_string_value(raw: &str) -> String {
+    let normalized = raw.replace("\r\n", "\n").replace('\r', "\n");
+    let mut lines: Vec<&str> = normalized.split('\n').collect();
+
+    let is_indent = |c: char| c == ' ' || c == '\t';
+
+    let common_indent = lines.iter()
+        .skip(1)
+        .filter_map(|l| {
+            let indent = l.len() - l.trim_start_matches(is_indent).len();
+            if indent < l.len() { Some(indent) } else { None }
+        })
+        .min();
+
+    if let Some(common_indent) = common_indent {
+        for line in lines.iter_mut().skip(1) {
+            *line = if line.len() >= common_indent { &line[common_indent..] } else { "" };
+        }
+    }
+
+    while lines.first().map_or(false, |l| l.chars().all(is_indent)) {
+        lines.remove(0);
+    }
+
+    while lines.last().map_or(false, |l| l.chars().all(is_indent)) {
+        lines.pop();
+    }
+
+    lines.join("\n")
+}
+
 fn is_source_char(c: char) -> bool {
     c == '\t' || c == '\n' || c == '\r' || c >= ' '
 }
@@ -5,6 +5,7 @@ mod lexer;
 mod parser;
 mod value;
 mod document;
+mod code_frame;
 
 #[cfg(test)]
 mod tests;
@@ -14,3 +15,4 @@ pub use self::document::parse_document_source;
 pub use self::parser::{Parser, ParseError, ParseResult, UnlocatedParseResult, OptionParseResult};
 pub use self::lexer::{Token, Lexer, LexerError};
 pub use self::utils::{Spanning, SourcePosition};
+pub use self::code_frame::{token_span, render_code_frame};
@@ -0,0 +1,79 @@
+use parser::{token_span, render_code_frame, SourcePosition, Spanning};
+
+#[test]
+fn token_span_covers_the_token_at_a_position() {
+    let source = "{ hero { name } }";
+
+    assert_eq!(
+        token_span(source, &SourcePosition::new(9, 0, 9)),
+        Spanning::start_end(&SourcePosition::new(9, 0, 9), &SourcePosition::new(13, 0, 13), ()));
+    assert_eq!(
+        token_span(source, &SourcePosition::new(0, 0, 0)),
+        Spanning::start_end(&SourcePosition::new(0, 0, 0), &SourcePosition::new(1, 0, 1), ()));
+}
+
+#[test]
+fn token_span_is_empty_between_tokens() {
+    let source = "{ hero }";
+
+    assert_eq!(
+        token_span(source, &SourcePosition::new(1, 0, 1)),
+        Spanning::zero_width(&SourcePosition::new(1, 0, 1), ()));
+    assert_eq!(
+        token_span(source, &SourcePosition::new(3, 0, 3)),
+        Spanning::zero_width(&SourcePosition::new(3, 0, 3), ()));
+}
+
+#[test]
+fn renders_a_single_line_span() {
+    let source = "{ hero { nmae } }";
+    let span = token_span(source, &SourcePosition::new(9, 0, 9));
+
+    assert_eq!(render_code_frame(source, &span), concat!(
+        " --> 1:10\n",
+        "  |\n",
+        "1 | { hero { nmae } }\n",
+        "  |          ^~~~\n"));
+}
+
+#[test]
+fn renders_a_zero_width_span_as_a_caret() {
+    let source = "{ hero {";
+    let span = Spanning::zero_width(&SourcePosition::new(8, 0, 8), ());
+
+    assert_eq!(render_code_frame(source, &span), concat!(
+        " --> 1:9\n",
+        "  |\n",
+        "1 | { hero {\n",
+        "  |         ^\n"));
+}
+
+#[test]
+fn renders_only_the_lines_of_the_span() {
+    let source = "query Q {\n  hero {\n\tname\n  }\n}\n";
+    let span = Spanning::start_end(&SourcePosition::new(12, 1, 2), &SourcePosition::new(28, 3, 3), ());
+
+    assert_eq!(render_code_frame(source, &span), concat!(
+        " --> 2:3\n",
+        "  |\n",
+        "2 |   hero {\n",
+        "  |   ^~~~~~\n",
+        "3 | \tname\n",
+        "  | \t~~~~\n",
+        "4 |   }\n",
+        "  |   ~\n"));
+}
+
+#[test]
+fn pads_line_numbers_to_the_same_width() {
+    let source = "{\n\n\n\n\n\n\n\n\n  hero\n  nmae\n}";
+    let span = Spanning::start_end(&SourcePosition::new(16, 9, 6), &SourcePosition::new(24, 11, 0), ());
+
+    assert_eq!(render_code_frame(source, &span), concat!(
+        "  --> 10:7\n",
+        "   |\n",
+        "10 |   hero\n",
+        "   |       ^\n",
+        "11 |   nmae\n",
+        "   |   ~~~~\n"));
+}
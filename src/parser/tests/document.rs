@@ -1,4 +1,6 @@
-use ast::{Definition, Operation, Document, OperationType, Field, Selection, InputValue, Arguments};
+use ast::{Definition, Operation, Document, OperationType, Field, Selection, InputValue, Arguments,
+          Type, TypeDefinition, ObjectTypeDefinition, FieldDefinition, InputValueDefinition,
+          EnumTypeDefinition, UnionTypeDefinition, SchemaDefinition, DirectiveDefinition};
 use parser::{Spanning, SourcePosition, ParseError, Token};
 use parser::document::parse_document_source;
 
@@ -121,3 +123,237 @@ fn errors() {
             &SourcePosition::new(9, 0, 9),
             ParseError::UnexpectedToken(Token::CurlyClose)));
 }
+
+#[test]
+fn object_type_definition() {
+    assert_eq!(
+        parse_document(r#"type Droid { name(upper: Boolean): String! }"#),
+        vec![
+            Definition::Type(Spanning::start_end(
+                &SourcePosition::new(0, 0, 0),
+                &SourcePosition::new(44, 0, 44),
+                TypeDefinition::Object(ObjectTypeDefinition {
+                    description: None,
+                    name: Spanning::start_end(
+                        &SourcePosition::new(5, 0, 5),
+                        &SourcePosition::new(10, 0, 10),
+                        "Droid".to_owned()),
+                    interfaces: vec![],
+                    directives: None,
+                    fields: vec![
+                        Spanning::start_end(
+                            &SourcePosition::new(13, 0, 13),
+                            &SourcePosition::new(42, 0, 42),
+                            FieldDefinition {
+                                description: None,
+                                name: Spanning::start_end(
+                                    &SourcePosition::new(13, 0, 13),
+                                    &SourcePosition::new(17, 0, 17),
+                                    "name".to_owned()),
+                                arguments: Some(vec![
+                                    Spanning::start_end(
+                                        &SourcePosition::new(18, 0, 18),
+                                        &SourcePosition::new(32, 0, 32),
+                                        InputValueDefinition {
+                                            description: None,
+                                            name: Spanning::start_end(
+                                                &SourcePosition::new(18, 0, 18),
+                                                &SourcePosition::new(23, 0, 23),
+                                                "upper".to_owned()),
+                                            value_type: Spanning::start_end(
+                                                &SourcePosition::new(25, 0, 25),
+                                                &SourcePosition::new(32, 0, 32),
+                                                Type::Named("Boolean".to_owned())),
+                                            default_value: None,
+                                            directives: None,
+                                        }),
+                                ]),
+                                field_type: Spanning::start_end(
+                                    &SourcePosition::new(35, 0, 35),
+                                    &SourcePosition::new(42, 0, 42),
+                                    Type::NonNullNamed("String".to_owned())),
+                                directives: None,
+                            }),
+                    ],
+                })))
+        ])
+}
+
+#[test]
+fn type_system_definitions() {
+    let doc = parse_document(r#"
+        schema { query: Query mutation: Mutation }
+
+        "A scalar"
+        scalar Date
+
+        """
+        Something with
+          an indented description
+        """
+        type Human implements Node & Character @key(fields: "id") {
+            "The id" id: ID!
+            friends(first: Int = 10, after: String): [Character]
+            oldName: String @deprecated(reason: "Use name")
+        }
+
+        interface Character implements Node { id: ID! }
+        union SearchResult = | Human | Droid
+        enum Episode { NEWHOPE "Empire" EMPIRE JEDI @deprecated }
+        input ReviewInput { stars: Int! commentary: String = "none" }
+        directive @key(fields: String!) repeatable on | OBJECT | INTERFACE
+
+        extend schema @link
+        extend type Query { me: Human }
+        extend union SearchResult = Starship
+        extend scalar Date @format
+    "#);
+
+    assert_eq!(doc.len(), 12);
+    assert!(doc.iter().all(|d| !d.is_executable()));
+
+    match doc[0] {
+        Definition::Schema(Spanning { item: SchemaDefinition { ref operation_types, .. }, .. }) => {
+            assert_eq!(operation_types.len(), 2);
+            assert_eq!(operation_types[0].0.item, OperationType::Query);
+            assert_eq!(operation_types[0].1.item, "Query");
+            assert_eq!(operation_types[1].0.item, OperationType::Mutation);
+            assert_eq!(operation_types[1].1.item, "Mutation");
+        },
+        ref d => panic!("Expected schema definition, got {:#?}", d),
+    }
+
+    match doc[1] {
+        Definition::Type(Spanning { item: ref t, ref start, .. }) => {
+            assert_eq!(t.name().item, "Date");
+            assert_eq!(start, &SourcePosition::new(61, 3, 8));
+        },
+        ref d => panic!("Expected type definition, got {:#?}", d),
+    }
+
+    match doc[2] {
+        Definition::Type(Spanning { item: TypeDefinition::Object(ref o), .. }) => {
+            assert_eq!(o.description.as_ref().map(|s| s.item.as_str()),
+                       Some("Something with\n  an indented description"));
+            assert_eq!(o.interfaces.iter().map(|i| i.item.as_str()).collect::<Vec<_>>(),
+                       vec!["Node", "Character"]);
+            assert_eq!(o.directives.as_ref().map(|d| d[0].item.name.item.as_str()), Some("key"));
+            assert_eq!(o.fields.len(), 3);
+            assert_eq!(o.fields[0].item.description.as_ref().map(|s| s.item.as_str()), Some("The id"));
+
+            let args = o.fields[1].item.arguments.as_ref().unwrap();
+            assert_eq!(args.len(), 2);
+            assert!(args[0].item.default_value.as_ref().unwrap().item.unlocated_eq(&InputValue::int(10)));
+            assert_eq!(o.fields[1].item.field_type.item,
+                       Type::List(Box::new(Type::Named("Character".to_owned()))));
+        },
+        ref d => panic!("Expected object type definition, got {:#?}", d),
+    }
+
+    match doc[4] {
+        Definition::Type(Spanning { item: TypeDefinition::Union(UnionTypeDefinition { ref members, .. }), .. }) =>
+            assert_eq!(members.iter().map(|m| m.item.as_str()).collect::<Vec<_>>(), vec!["Human", "Droid"]),
+        ref d => panic!("Expected union type definition, got {:#?}", d),
+    }
+
+    match doc[5] {
+        Definition::Type(Spanning { item: TypeDefinition::Enum(EnumTypeDefinition { ref values, .. }), .. }) => {
+            assert_eq!(values.len(), 3);
+            assert_eq!(values[1].item.description.as_ref().map(|s| s.item.as_str()), Some("Empire"));
+            assert!(values[2].item.directives.is_some());
+        },
+        ref d => panic!("Expected enum type definition, got {:#?}", d),
+    }
+
+    match doc[7] {
+        Definition::Directive(Spanning { item: DirectiveDefinition { ref name, repeatable, ref locations, .. }, .. }) => {
+            assert_eq!(name.item, "key");
+            assert!(repeatable);
+            assert_eq!(locations.iter().map(|l| l.item.as_str()).collect::<Vec<_>>(), vec!["OBJECT", "INTERFACE"]);
+        },
+        ref d => panic!("Expected directive definition, got {:#?}", d),
+    }
+
+    match doc[8] {
+        Definition::SchemaExtension(Spanning { item: SchemaDefinition { ref operation_types, ref directives, .. }, .. }) => {
+            assert!(operation_types.is_empty());
+            assert!(directives.is_some());
+        },
+        ref d => panic!("Expected schema extension, got {:#?}", d),
+    }
+
+    match doc[9] {
+        Definition::TypeExtension(Spanning { item: TypeDefinition::Object(ref o), ref start, .. }) => {
+            assert_eq!(o.name.item, "Query");
+            assert_eq!(o.fields.len(), 1);
+            assert_eq!(start.line(), 23);
+        },
+        ref d => panic!("Expected type extension, got {:#?}", d),
+    }
+}
+
+#[test]
+fn mixed_executable_and_type_system_definitions() {
+    let doc = parse_document("type Query { a: Int }\n{ a }");
+
+    assert_eq!(doc.len(), 2);
+    assert!(!doc[0].is_executable());
+    assert!(doc[1].is_executable());
+}
+
+#[test]
+fn type_system_errors() {
+    assert_eq!(
+        parse_document_error(r#""Description" query { a }"#),
+        Spanning::start_end(
+            &SourcePosition::new(14, 0, 14),
+            &SourcePosition::new(19, 0, 19),
+            ParseError::UnexpectedToken(Token::Name("query"))));
+
+    assert_eq!(
+        parse_document_error("directive @foo on FIELD | NOWHERE"),
+        Spanning::start_end(
+            &SourcePosition::new(26, 0, 26),
+            &SourcePosition::new(33, 0, 33),
+            ParseError::UnexpectedToken(Token::Name("NOWHERE"))));
+
+    assert_eq!(
+        parse_document_error("enum Bool { true false }"),
+        Spanning::start_end(
+            &SourcePosition::new(12, 0, 12),
+            &SourcePosition::new(16, 0, 16),
+            ParseError::UnexpectedToken(Token::Name("true"))));
+
+    assert_eq!(
+        parse_document_error("extend fragment Foo on Bar { a }"),
+        Spanning::start_end(
+            &SourcePosition::new(7, 0, 7),
+            &SourcePosition::new(15, 0, 15),
+            ParseError::UnexpectedToken(Token::Name("fragment"))));
+
+    assert_eq!(
+        parse_document_error("extend type Foo"),
+        Spanning::zero_width(
+            &SourcePosition::new(15, 0, 15),
+            ParseError::UnexpectedEndOfFile));
+
+    assert_eq!(
+        parse_document_error("extend schema type Query { a: Int }"),
+        Spanning::start_end(
+            &SourcePosition::new(14, 0, 14),
+            &SourcePosition::new(18, 0, 18),
+            ParseError::UnexpectedToken(Token::Name("type"))));
+
+    assert_eq!(
+        parse_document_error("extend union U extend enum E"),
+        Spanning::start_end(
+            &SourcePosition::new(15, 0, 15),
+            &SourcePosition::new(21, 0, 21),
+            ParseError::UnexpectedToken(Token::Name("extend"))));
+
+    assert_eq!(
+        parse_document_error("type Foo {"),
+        Spanning::zero_width(
+            &SourcePosition::new(10, 0, 10),
+            ParseError::UnexpectedEndOfFile));
+}
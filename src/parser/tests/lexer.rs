@@ -179,6 +179,59 @@ fn strings() {
             Token::String("unicode \u{1234}\u{5678}\u{90ab}\u{cdef}".to_owned())));
 }
 
+#[test]
+fn block_strings() {
+    assert_eq!(
+        tokenize_single(r#""""simple""""#),
+        Spanning::start_end(
+            &SourcePosition::new(0, 0, 0),
+            &SourcePosition::new(12, 0, 12),
+            Token::String("simple".to_owned())));
+
+    assert_eq!(
+        tokenize_single(r#""""contains " quote and \n""""#),
+        Spanning::start_end(
+            &SourcePosition::new(0, 0, 0),
+            &SourcePosition::new(29, 0, 29),
+            Token::String("contains \" quote and \\n".to_owned())));
+
+    assert_eq!(
+        tokenize_single(r#""""escaped \""" quotes""""#),
+        Spanning::start_end(
+            &SourcePosition::new(0, 0, 0),
+            &SourcePosition::new(25, 0, 25),
+            Token::String("escaped \"\"\" quotes".to_owned())));
+
+    assert_eq!(
+        tokenize_single("\"\"\"\n\n    first\n      second\n    third\n\n  \"\"\""),
+        Spanning::start_end(
+            &SourcePosition::new(0, 0, 0),
+            &SourcePosition::new(44, 6, 5),
+            Token::String("first\n  second\nthird".to_owned())));
+
+    assert_eq!(
+        tokenize_single(r#""""""""#),
+        Spanning::start_end(
+            &SourcePosition::new(0, 0, 0),
+            &SourcePosition::new(6, 0, 6),
+            Token::String("".to_owned())));
+}
+
+#[test]
+fn block_string_errors() {
+    assert_eq!(
+        tokenize_error("\"\"\"no end quote"),
+        Spanning::zero_width(
+            &SourcePosition::new(15, 0, 15),
+            LexerError::UnterminatedString));
+
+    assert_eq!(
+        tokenize_error("\"\"\"contains \u{0007} control char\"\"\""),
+        Spanning::zero_width(
+            &SourcePosition::new(12, 0, 12),
+            LexerError::UnknownCharacterInString('\u{0007}')));
+}
+
 #[test]
 fn string_errors() {
     assert_eq!(
@@ -525,6 +578,12 @@ fn punctuation() {
         Spanning::single_width(
             &SourcePosition::new(0, 0, 0),
             Token::Pipe));
+
+    assert_eq!(
+        tokenize_single("&"),
+        Spanning::single_width(
+            &SourcePosition::new(0, 0, 0),
+            Token::Amp));
 }
 
 #[test]
@@ -594,4 +653,5 @@ fn display() {
     assert_eq!(format!("{}", Token::Equals), "=");
     assert_eq!(format!("{}", Token::At), "@");
     assert_eq!(format!("{}", Token::Pipe), "|");
+    assert_eq!(format!("{}", Token::Amp), "&");
 }
@@ -0,0 +1,287 @@
+/*!
+
+Persisted queries, looked up by id or by the hash of their source
+
+Clients that send the same queries over and over can send a short id instead
+of the full query. The server keeps the queries in a `PersistedQueryStore`,
+already parsed and validated, so that stored queries are executed without
+being parsed or validated again.
+
+Attach a store to a schema with `RootNode::with_persisted_queries`. Queries
+are then stored under the SHA-256 hash of their source the first time they
+are executed with `juniper::execute`, and can be executed by id with
+`juniper::execute_persisted`:
+
+```rust
+# #[macro_use] extern crate juniper;
+use std::collections::HashMap;
+use juniper::{RootNode, PersistedQueries, LruQueryStore, execute_persisted, query_hash};
+
+struct Query;
+
+graphql_object!(Query: () |&self| {
+    field hello() -> &str {
+        "world"
+    }
+});
+
+# fn main() {
+let schema = RootNode::new(Query, ())
+    .with_persisted_queries(PersistedQueries::new(LruQueryStore::new(1000)));
+
+let source = "{ hello }";
+schema.persist_query(&query_hash(source), source).expect("Invalid query");
+
+let result = execute_persisted(&query_hash(source), None, &schema, &HashMap::new(), &());
+assert!(result.is_ok());
+# }
+```
+
+A store can also serve as an allow-list: with `PersistedQueries::allow_list`,
+queries that are not already in the store are rejected instead of being
+added to it. Allow-lists need a store that never drops queries, marked by
+the `AllowListStore` trait, like `MemoryQueryStore`.
+
+Stored queries are bound to the schema they were validated against.
+Executing them with another schema fails with `GraphQLError::SchemaMismatch`.
+
+*/
+
+use std::collections::{BTreeMap, HashMap};
+use std::sync::{Arc, Mutex, RwLock};
+
+use rustc_serialize::hex::ToHex;
+use sha2::{Digest, Sha256};
+
+use prepared::PreparedQuery;
+use schema::model::RootNode;
+use GraphQLError;
+
+/// Storage for prepared queries, keyed by id
+///
+/// The store is shared between all requests, so it needs to handle
+/// synchronization itself.
+pub trait PersistedQueryStore: Send + Sync {
+    /// Look up the query stored under an id
+    fn get(&self, id: &str) -> Option<Arc<PreparedQuery>>;
+
+    /// Store a query under an id, replacing any query already stored there
+    fn insert(&self, id: String, query: Arc<PreparedQuery>);
+}
+
+/// A store that keeps every query added to it, so that it can serve as an
+/// allow-list
+///
+/// Stores that evict queries, like `LruQueryStore`, must not implement this:
+/// allowed queries would start being rejected once they are evicted.
+pub trait AllowListStore: PersistedQueryStore {}
+
+/// The persisted query configuration of a schema
+pub struct PersistedQueries {
+    store: Box<PersistedQueryStore>,
+    allow_list: bool,
+}
+
+/// An in-memory store that keeps every query added to it
+///
+/// Use it for allow-lists, or when all queries are known up front.
+pub struct MemoryQueryStore {
+    queries: RwLock<HashMap<String, Arc<PreparedQuery>>>,
+}
+
+/// An in-memory store that keeps the most recently used queries
+///
+/// When the store is full, adding a query evicts the query that was used
+/// least recently.
+pub struct LruQueryStore {
+    capacity: usize,
+    entries: Mutex<LruEntries>,
+}
+
+struct LruEntries {
+    queries: HashMap<String, (Arc<PreparedQuery>, u64)>,
+    recency: BTreeMap<u64, String>,
+    clock: u64,
+}
+
+/// The lowercase, hex encoded SHA-256 hash of a query source
+///
+/// This is the key queries are stored under when they are executed by
+/// source, and the hash used by automatic persisted query clients.
+pub fn query_hash(document_source: &str) -> String {
+    Sha256::digest(document_source.as_bytes()).as_slice().to_hex()
+}
+
+impl PersistedQueries {
+    /// Use a store for the persisted queries of a schema
+    pub fn new<S: PersistedQueryStore + 'static>(store: S) -> PersistedQueries {
+        PersistedQueries {
+            store: Box::new(store),
+            allow_list: false,
+        }
+    }
+
+    /// Use a store as an allow-list, only executing queries already in it
+    ///
+    /// Queries that are executed by source and not found in the store are
+    /// rejected with `GraphQLError::QueryNotAllowed`.
+    pub fn allow_list<S: AllowListStore + 'static>(store: S) -> PersistedQueries {
+        PersistedQueries {
+            store: Box::new(store),
+            allow_list: true,
+        }
+    }
+
+    /// Whether queries that are not in the store are rejected
+    pub fn is_allow_list(&self) -> bool {
+        self.allow_list
+    }
+
+    /// Look up the query stored under an id
+    pub fn get(&self, id: &str) -> Option<Arc<PreparedQuery>> {
+        self.store.get(id)
+    }
+
+    /// Prepare a query and store it under an id
+    pub fn insert<'a, CtxT, QueryT, MutationT, SubscriptionT>(
+        &self,
+        id: &str,
+        document_source: &'a str,
+        root_node: &RootNode<CtxT, QueryT, MutationT, SubscriptionT>,
+    )
+        -> Result<Arc<PreparedQuery>, GraphQLError<'a>>
+    {
+        let query = Arc::new(try!(PreparedQuery::new(document_source, root_node)));
+
+        self.store.insert(id.to_owned(), query.clone());
+
+        Ok(query)
+    }
+
+    /// Look up a query by the hash of its source
+    ///
+    /// Queries that are not in the store yet are prepared and stored, unless
+    /// the store is an allow-list.
+    pub fn prepare<'a, CtxT, QueryT, MutationT, SubscriptionT>(
+        &self,
+        document_source: &'a str,
+        root_node: &RootNode<CtxT, QueryT, MutationT, SubscriptionT>,
+    )
+        -> Result<Arc<PreparedQuery>, GraphQLError<'a>>
+    {
+        let hash = query_hash(document_source);
+
+        if let Some(query) = self.store.get(&hash) {
+            return Ok(query);
+        }
+
+        if self.allow_list {
+            return Err(GraphQLError::QueryNotAllowed);
+        }
+
+        self.insert(&hash, document_source, root_node)
+    }
+}
+
+impl MemoryQueryStore {
+    /// Create an empty store
+    pub fn new() -> MemoryQueryStore {
+        MemoryQueryStore {
+            queries: RwLock::new(HashMap::new()),
+        }
+    }
+
+    /// The number of queries in the store
+    pub fn len(&self) -> usize {
+        self.queries.read().unwrap().len()
+    }
+
+    /// Whether the store is empty
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+impl AllowListStore for MemoryQueryStore {}
+
+impl Default for MemoryQueryStore {
+    fn default() -> MemoryQueryStore {
+        MemoryQueryStore::new()
+    }
+}
+
+impl PersistedQueryStore for MemoryQueryStore {
+    fn get(&self, id: &str) -> Option<Arc<PreparedQuery>> {
+        self.queries.read().unwrap().get(id).cloned()
+    }
+
+    fn insert(&self, id: String, query: Arc<PreparedQuery>) {
+        self.queries.write().unwrap().insert(id, query);
+    }
+}
+
+impl LruQueryStore {
+    /// Create a store that holds at most `capacity` queries
+    pub fn new(capacity: usize) -> LruQueryStore {
+        LruQueryStore {
+            capacity: capacity,
+            entries: Mutex::new(LruEntries {
+                queries: HashMap::new(),
+                recency: BTreeMap::new(),
+                clock: 0,
+            }),
+        }
+    }
+
+    /// The number of queries in the store
+    pub fn len(&self) -> usize {
+        self.entries.lock().unwrap().queries.len()
+    }
+
+    /// Whether the store is empty
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+impl PersistedQueryStore for LruQueryStore {
+    fn get(&self, id: &str) -> Option<Arc<PreparedQuery>> {
+        let mut entries = self.entries.lock().unwrap();
+        let LruEntries { ref mut queries, ref mut recency, ref mut clock } = *entries;
+
+        match queries.get_mut(id) {
+            Some(&mut (ref query, ref mut last_used)) => {
+                *clock += 1;
+                recency.remove(last_used);
+                recency.insert(*clock, id.to_owned());
+                *last_used = *clock;
+
+                Some(query.clone())
+            }
+            None => None,
+        }
+    }
+
+    fn insert(&self, id: String, query: Arc<PreparedQuery>) {
+        let mut entries = self.entries.lock().unwrap();
+        let LruEntries { ref mut queries, ref mut recency, ref mut clock } = *entries;
+
+        *clock += 1;
+
+        if let Some((_, last_used)) = queries.insert(id.clone(), (query, *clock)) {
+            recency.remove(&last_used);
+        }
+        recency.insert(*clock, id);
+
+        while queries.len() > self.capacity {
+            let oldest = match recency.keys().next() {
+                Some(&oldest) => oldest,
+                None => break,
+            };
+
+            if let Some(id) = recency.remove(&oldest) {
+                queries.remove(&id);
+            }
+        }
+    }
+}
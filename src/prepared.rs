@@ -0,0 +1,174 @@
+use std::collections::HashMap;
+
+use futures;
+
+use ast::{Document, InputValue};
+use executor::{
+    ExecutionError, ExecutionFuture, SubscriptionStream,
+    execute_validated_query, execute_validated_query_async, execute_validated_query_parallel,
+    execute_validated_subscription,
+};
+use parser::parse_document_source;
+use schema::model::RootNode;
+use types::base::{GraphQLType, GraphQLSubscriptionType};
+use value::Value;
+use {GraphQLError, validate_document, validate_variables, check_limits, check_complexity};
+
+/// A query that has been parsed and validated once, to be executed many times
+///
+/// Preparing a query parses it, runs the schema's validation rules and
+/// checks its limits. Executing it only checks the variables and the
+/// complexity of the selected operation, which both depend on the request:
+///
+/// ```rust
+/// # #[macro_use] extern crate juniper;
+/// use juniper::{PreparedQuery, RootNode, InputValue};
+///
+/// struct Query;
+///
+/// graphql_object!(Query: () |&self| {
+///     field hello(name: String) -> String {
+///         format!("Hello, {}!", name)
+///     }
+/// });
+///
+/// # fn main() {
+/// let schema = RootNode::new(Query, ());
+/// let query = PreparedQuery::new("query Hello($name: String!) { hello(name: $name) }", &schema)
+///     .expect("Invalid query");
+///
+/// for name in &["Alice", "Bob"] {
+///     let variables = vec![("name".to_owned(), InputValue::string(name))]
+///         .into_iter().collect();
+///
+///     let (_, errors) = query.execute(None, &schema, &variables, &()).unwrap();
+///     assert!(errors.is_empty());
+/// }
+/// # }
+/// ```
+///
+/// A prepared query can only be executed with the schema it was prepared
+/// for; executing it with any other schema fails with
+/// `GraphQLError::SchemaMismatch`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct PreparedQuery {
+    document: Document,
+    schema_id: usize,
+}
+
+impl PreparedQuery {
+    /// Parse and validate a query against a schema
+    pub fn new<'a, CtxT, QueryT, MutationT, SubscriptionT>(
+        document_source: &'a str,
+        root_node: &RootNode<CtxT, QueryT, MutationT, SubscriptionT>,
+    )
+        -> Result<PreparedQuery, GraphQLError<'a>>
+    {
+        let document = try!(parse_document_source(document_source));
+
+        try!(validate_document(&document, root_node));
+        try!(check_limits(&document, root_node));
+
+        Ok(PreparedQuery {
+            document: document,
+            schema_id: root_node.schema.id(),
+        })
+    }
+
+    /// Access the validated document
+    pub fn document(&self) -> &Document {
+        &self.document
+    }
+
+    /// Execute the query, like `juniper::execute`
+    pub fn execute<'a, CtxT, QueryT, MutationT, SubscriptionT>(
+        &self,
+        operation_name: Option<&str>,
+        root_node: &RootNode<CtxT, QueryT, MutationT, SubscriptionT>,
+        variables: &HashMap<String, InputValue>,
+        context: &CtxT,
+    )
+        -> Result<(Value, Vec<ExecutionError>), GraphQLError<'a>>
+        where QueryT: GraphQLType<CtxT>,
+              MutationT: GraphQLType<CtxT>,
+              SubscriptionT: GraphQLType<CtxT>,
+    {
+        try!(self.check_request(operation_name, root_node, variables));
+
+        execute_validated_query(&self.document, operation_name, root_node, variables, context)
+    }
+
+    /// Execute the query, resolving root fields in parallel like
+    /// `juniper::execute_parallel`
+    pub fn execute_parallel<'a, CtxT, QueryT, MutationT, SubscriptionT>(
+        &self,
+        operation_name: Option<&str>,
+        root_node: &RootNode<CtxT, QueryT, MutationT, SubscriptionT>,
+        variables: &HashMap<String, InputValue>,
+        context: &CtxT,
+    )
+        -> Result<(Value, Vec<ExecutionError>), GraphQLError<'a>>
+        where QueryT: GraphQLType<CtxT> + Sync,
+              MutationT: GraphQLType<CtxT> + Sync,
+              SubscriptionT: GraphQLType<CtxT> + Sync,
+              CtxT: Sync,
+    {
+        try!(self.check_request(operation_name, root_node, variables));
+
+        execute_validated_query_parallel(&self.document, operation_name, root_node, variables, context)
+    }
+
+    /// Execute the query asynchronously, like `juniper::execute_async`
+    pub fn execute_async<'a, CtxT, QueryT, MutationT, SubscriptionT>(
+        &self,
+        operation_name: Option<&str>,
+        root_node: &'a RootNode<CtxT, QueryT, MutationT, SubscriptionT>,
+        variables: &HashMap<String, InputValue>,
+        context: &'a CtxT,
+    )
+        -> ExecutionFuture<'a>
+        where QueryT: GraphQLType<CtxT>,
+              MutationT: GraphQLType<CtxT>,
+              SubscriptionT: GraphQLType<CtxT>,
+    {
+        match self.check_request(operation_name, root_node, variables) {
+            Ok(()) => execute_validated_query_async(
+                &self.document, operation_name, root_node, variables, context),
+            Err(e) => Box::new(futures::future::err(e)),
+        }
+    }
+
+    /// Execute the query as a subscription, like `juniper::subscribe`
+    pub fn subscribe<'a, 'b, CtxT, QueryT, MutationT, SubscriptionT>(
+        &self,
+        operation_name: Option<&str>,
+        root_node: &'b RootNode<CtxT, QueryT, MutationT, SubscriptionT>,
+        variables: &HashMap<String, InputValue>,
+        context: &'b CtxT,
+    )
+        -> Result<SubscriptionStream<'b, CtxT, SubscriptionT>, GraphQLError<'a>>
+        where QueryT: GraphQLType<CtxT>,
+              MutationT: GraphQLType<CtxT>,
+              SubscriptionT: GraphQLSubscriptionType<CtxT>,
+    {
+        try!(self.check_request(operation_name, root_node, variables));
+
+        execute_validated_subscription(&self.document, operation_name, root_node, variables, context)
+    }
+
+    fn check_request<'a, CtxT, QueryT, MutationT, SubscriptionT>(
+        &self,
+        operation_name: Option<&str>,
+        root_node: &RootNode<CtxT, QueryT, MutationT, SubscriptionT>,
+        variables: &HashMap<String, InputValue>,
+    )
+        -> Result<(), GraphQLError<'a>>
+    {
+        if root_node.schema.id() != self.schema_id {
+            return Err(GraphQLError::SchemaMismatch);
+        }
+
+        try!(validate_variables(&self.document, root_node, variables));
+        check_complexity(&self.document, operation_name, root_node, variables)
+    }
+}
@@ -0,0 +1,531 @@
+//! Detect changes between two versions of a schema
+
+use std::fmt;
+
+use ast::{InputValue, Type};
+use types::base::TypeKind;
+use schema::meta::{MetaType, ObjectMeta, EnumMeta, InterfaceMeta, UnionMeta,
+                   InputObjectMeta, Field, Argument, EnumValue};
+use schema::model::SchemaType;
+
+/// How much a schema change affects existing clients
+#[derive(Clone, Copy, Eq, PartialEq, Ord, PartialOrd, Debug)]
+pub enum ChangeCriticality {
+    /// Queries that were valid against the old schema might fail or return
+    /// data that no longer matches the client's expectations.
+    Breaking,
+
+    /// Existing queries keep working, but clients might observe values they
+    /// are not prepared for, e.g. a new enum value or union member.
+    Dangerous,
+
+    /// The change can not affect any existing client.
+    Safe,
+}
+
+/// A single difference between two schemas
+#[derive(Clone, PartialEq, Debug)]
+#[allow(missing_docs)]
+pub enum SchemaChange {
+    TypeRemoved { type_name: String },
+    TypeAdded { type_name: String },
+    TypeKindChanged { type_name: String, old_kind: TypeKind, new_kind: TypeKind },
+    QueryTypeChanged { old_type_name: String, new_type_name: String },
+    MutationTypeChanged { old_type_name: Option<String>, new_type_name: Option<String> },
+    SubscriptionTypeChanged { old_type_name: Option<String>, new_type_name: Option<String> },
+    FieldRemoved { type_name: String, field_name: String },
+    FieldAdded { type_name: String, field_name: String },
+    FieldTypeChanged { type_name: String, field_name: String, old_type: Type, new_type: Type },
+    FieldDeprecated { type_name: String, field_name: String },
+    ArgumentRemoved { type_name: String, field_name: String, argument_name: String },
+    ArgumentAdded { type_name: String, field_name: String, argument_name: String, required: bool },
+    ArgumentTypeChanged {
+        type_name: String, field_name: String, argument_name: String,
+        old_type: Type, new_type: Type,
+    },
+    ArgumentDefaultValueChanged {
+        type_name: String, field_name: String, argument_name: String,
+        old_value: Option<InputValue>, new_value: Option<InputValue>,
+    },
+    InputFieldRemoved { type_name: String, field_name: String },
+    InputFieldAdded { type_name: String, field_name: String, required: bool },
+    InputFieldTypeChanged { type_name: String, field_name: String, old_type: Type, new_type: Type },
+    EnumValueRemoved { type_name: String, value: String },
+    EnumValueAdded { type_name: String, value: String },
+    EnumValueDeprecated { type_name: String, value: String },
+    UnionMemberRemoved { type_name: String, member_name: String },
+    UnionMemberAdded { type_name: String, member_name: String },
+    InterfaceRemoved { type_name: String, interface_name: String },
+    InterfaceAdded { type_name: String, interface_name: String },
+    DirectiveRemoved { directive_name: String },
+    DirectiveAdded { directive_name: String },
+}
+
+impl SchemaChange {
+    /// Classify the change as breaking, dangerous, or safe
+    pub fn criticality(&self) -> ChangeCriticality {
+        use self::SchemaChange::*;
+
+        match *self {
+            MutationTypeChanged { old_type_name: None, .. } |
+            SubscriptionTypeChanged { old_type_name: None, .. } => ChangeCriticality::Safe,
+
+            TypeRemoved { .. } |
+            TypeKindChanged { .. } |
+            QueryTypeChanged { .. } |
+            MutationTypeChanged { .. } |
+            SubscriptionTypeChanged { .. } |
+            FieldRemoved { .. } |
+            ArgumentRemoved { .. } |
+            InputFieldRemoved { .. } |
+            EnumValueRemoved { .. } |
+            UnionMemberRemoved { .. } |
+            InterfaceRemoved { .. } |
+            DirectiveRemoved { .. } => ChangeCriticality::Breaking,
+
+            FieldTypeChanged { ref old_type, ref new_type, .. } =>
+                if is_safe_output_type_change(old_type, new_type) {
+                    ChangeCriticality::Safe
+                } else {
+                    ChangeCriticality::Breaking
+                },
+
+            ArgumentTypeChanged { ref old_type, ref new_type, .. } |
+            InputFieldTypeChanged { ref old_type, ref new_type, .. } =>
+                if is_safe_input_type_change(old_type, new_type) {
+                    ChangeCriticality::Safe
+                } else {
+                    ChangeCriticality::Breaking
+                },
+
+            ArgumentAdded { required, .. } |
+            InputFieldAdded { required, .. } =>
+                if required { ChangeCriticality::Breaking } else { ChangeCriticality::Dangerous },
+
+            ArgumentDefaultValueChanged { .. } |
+            EnumValueAdded { .. } |
+            UnionMemberAdded { .. } |
+            InterfaceAdded { .. } => ChangeCriticality::Dangerous,
+
+            TypeAdded { .. } |
+            FieldAdded { .. } |
+            FieldDeprecated { .. } |
+            EnumValueDeprecated { .. } |
+            DirectiveAdded { .. } => ChangeCriticality::Safe,
+        }
+    }
+
+    /// Returns true if the change can break existing clients
+    pub fn is_breaking(&self) -> bool {
+        self.criticality() == ChangeCriticality::Breaking
+    }
+}
+
+impl fmt::Display for SchemaChange {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        use self::SchemaChange::*;
+
+        match *self {
+            TypeRemoved { ref type_name } =>
+                write!(f, "{} was removed.", type_name),
+            TypeAdded { ref type_name } =>
+                write!(f, "{} was added.", type_name),
+            TypeKindChanged { ref type_name, ref old_kind, ref new_kind } =>
+                write!(f, "{} changed from {} to {}.", type_name, kind_name(old_kind), kind_name(new_kind)),
+            QueryTypeChanged { ref old_type_name, ref new_type_name } =>
+                write!(f, "Query root type changed from {} to {}.", old_type_name, new_type_name),
+            MutationTypeChanged { old_type_name: Some(ref old), new_type_name: Some(ref new) } =>
+                write!(f, "Mutation root type changed from {} to {}.", old, new),
+            MutationTypeChanged { old_type_name: Some(ref old), .. } =>
+                write!(f, "Mutation root type {} was removed.", old),
+            MutationTypeChanged { new_type_name: Some(ref new), .. } =>
+                write!(f, "Mutation root type {} was added.", new),
+            MutationTypeChanged { .. } =>
+                write!(f, "Mutation root type changed."),
+            SubscriptionTypeChanged { old_type_name: Some(ref old), new_type_name: Some(ref new) } =>
+                write!(f, "Subscription root type changed from {} to {}.", old, new),
+            SubscriptionTypeChanged { old_type_name: Some(ref old), .. } =>
+                write!(f, "Subscription root type {} was removed.", old),
+            SubscriptionTypeChanged { new_type_name: Some(ref new), .. } =>
+                write!(f, "Subscription root type {} was added.", new),
+            SubscriptionTypeChanged { .. } =>
+                write!(f, "Subscription root type changed."),
+            FieldRemoved { ref type_name, ref field_name } =>
+                write!(f, "{}.{} was removed.", type_name, field_name),
+            FieldAdded { ref type_name, ref field_name } =>
+                write!(f, "{}.{} was added.", type_name, field_name),
+            FieldTypeChanged { ref type_name, ref field_name, ref old_type, ref new_type } =>
+                write!(f, "{}.{} changed type from {} to {}.", type_name, field_name, old_type, new_type),
+            FieldDeprecated { ref type_name, ref field_name } =>
+                write!(f, "{}.{} was deprecated.", type_name, field_name),
+            ArgumentRemoved { ref type_name, ref field_name, ref argument_name } =>
+                write!(f, "Argument {} was removed from {}.{}.", argument_name, type_name, field_name),
+            ArgumentAdded { ref type_name, ref field_name, ref argument_name, required } =>
+                write!(f, "{} argument {} was added to {}.{}.",
+                    if required { "Required" } else { "Optional" }, argument_name, type_name, field_name),
+            ArgumentTypeChanged { ref type_name, ref field_name, ref argument_name, ref old_type, ref new_type } =>
+                write!(f, "Argument {} on {}.{} changed type from {} to {}.",
+                    argument_name, type_name, field_name, old_type, new_type),
+            ArgumentDefaultValueChanged { ref type_name, ref field_name, ref argument_name, .. } =>
+                write!(f, "Argument {} on {}.{} has changed its default value.",
+                    argument_name, type_name, field_name),
+            InputFieldRemoved { ref type_name, ref field_name } =>
+                write!(f, "{}.{} was removed.", type_name, field_name),
+            InputFieldAdded { ref type_name, ref field_name, required } =>
+                write!(f, "{} input field {} was added to {}.",
+                    if required { "Required" } else { "Optional" }, field_name, type_name),
+            InputFieldTypeChanged { ref type_name, ref field_name, ref old_type, ref new_type } =>
+                write!(f, "{}.{} changed type from {} to {}.", type_name, field_name, old_type, new_type),
+            EnumValueRemoved { ref type_name, ref value } =>
+                write!(f, "{} was removed from enum type {}.", value, type_name),
+            EnumValueAdded { ref type_name, ref value } =>
+                write!(f, "{} was added to enum type {}.", value, type_name),
+            EnumValueDeprecated { ref type_name, ref value } =>
+                write!(f, "{} was deprecated in enum type {}.", value, type_name),
+            UnionMemberRemoved { ref type_name, ref member_name } =>
+                write!(f, "{} was removed from union type {}.", member_name, type_name),
+            UnionMemberAdded { ref type_name, ref member_name } =>
+                write!(f, "{} was added to union type {}.", member_name, type_name),
+            InterfaceRemoved { ref type_name, ref interface_name } =>
+                write!(f, "{} no longer implements interface {}.", type_name, interface_name),
+            InterfaceAdded { ref type_name, ref interface_name } =>
+                write!(f, "{} added to interfaces implemented by {}.", interface_name, type_name),
+            DirectiveRemoved { ref directive_name } =>
+                write!(f, "Directive @{} was removed.", directive_name),
+            DirectiveAdded { ref directive_name } =>
+                write!(f, "Directive @{} was added.", directive_name),
+        }
+    }
+}
+
+/// Compare two schemas and list all changes from `old` to `new`
+///
+/// Changes are reported type by type in alphabetical order. Use
+/// `SchemaChange::criticality` to find the changes that would break clients.
+pub fn compare_schemas(old: &SchemaType, new: &SchemaType) -> Vec<SchemaChange> {
+    let mut changes = Vec::new();
+
+    compare_root_types(old, new, &mut changes);
+
+    let mut type_names = old.concrete_type_list()
+        .into_iter()
+        .chain(new.concrete_type_list())
+        .filter_map(|t| t.name())
+        .filter(|n| !n.starts_with("__"))
+        .collect::<Vec<_>>();
+    type_names.sort();
+    type_names.dedup();
+
+    for type_name in type_names {
+        match (old.concrete_type_by_name(type_name), new.concrete_type_by_name(type_name)) {
+            (Some(old_type), Some(new_type)) => compare_types(type_name, old_type, new_type, &mut changes),
+            (Some(_), None) => changes.push(SchemaChange::TypeRemoved { type_name: type_name.to_owned() }),
+            (None, Some(_)) => changes.push(SchemaChange::TypeAdded { type_name: type_name.to_owned() }),
+            (None, None) => (),
+        }
+    }
+
+    let mut directive_names = old.directive_list()
+        .into_iter()
+        .chain(new.directive_list())
+        .map(|d| d.name.as_str())
+        .collect::<Vec<_>>();
+    directive_names.sort();
+    directive_names.dedup();
+
+    for name in directive_names {
+        match (old.directive_by_name(name), new.directive_by_name(name)) {
+            (Some(_), None) => changes.push(SchemaChange::DirectiveRemoved { directive_name: name.to_owned() }),
+            (None, Some(_)) => changes.push(SchemaChange::DirectiveAdded { directive_name: name.to_owned() }),
+            _ => (),
+        }
+    }
+
+    changes
+}
+
+fn compare_root_types(old: &SchemaType, new: &SchemaType, changes: &mut Vec<SchemaChange>) {
+    let old_query = old.concrete_query_type().name().unwrap_or("");
+    let new_query = new.concrete_query_type().name().unwrap_or("");
+
+    if old_query != new_query {
+        changes.push(SchemaChange::QueryTypeChanged {
+            old_type_name: old_query.to_owned(),
+            new_type_name: new_query.to_owned(),
+        });
+    }
+
+    let old_mutation = old.concrete_mutation_type().and_then(|t| t.name());
+    let new_mutation = new.concrete_mutation_type().and_then(|t| t.name());
+
+    if old_mutation != new_mutation {
+        changes.push(SchemaChange::MutationTypeChanged {
+            old_type_name: old_mutation.map(|s| s.to_owned()),
+            new_type_name: new_mutation.map(|s| s.to_owned()),
+        });
+    }
+
+    let old_subscription = old.concrete_subscription_type().and_then(|t| t.name());
+    let new_subscription = new.concrete_subscription_type().and_then(|t| t.name());
+
+    if old_subscription != new_subscription {
+        changes.push(SchemaChange::SubscriptionTypeChanged {
+            old_type_name: old_subscription.map(|s| s.to_owned()),
+            new_type_name: new_subscription.map(|s| s.to_owned()),
+        });
+    }
+}
+
+fn compare_types(type_name: &str, old: &MetaType, new: &MetaType, changes: &mut Vec<SchemaChange>) {
+    match (old, new) {
+        (&MetaType::Object(ObjectMeta { fields: ref old_fields, interface_names: ref old_ifaces, .. }),
+         &MetaType::Object(ObjectMeta { fields: ref new_fields, interface_names: ref new_ifaces, .. })) => {
+            compare_fields(type_name, old_fields, new_fields, changes);
+            compare_names(old_ifaces, new_ifaces, changes,
+                |n| SchemaChange::InterfaceRemoved { type_name: type_name.to_owned(), interface_name: n.to_owned() },
+                |n| SchemaChange::InterfaceAdded { type_name: type_name.to_owned(), interface_name: n.to_owned() });
+        },
+        (&MetaType::Interface(InterfaceMeta { fields: ref old_fields, .. }),
+         &MetaType::Interface(InterfaceMeta { fields: ref new_fields, .. })) =>
+            compare_fields(type_name, old_fields, new_fields, changes),
+        (&MetaType::Union(UnionMeta { of_type_names: ref old_members, .. }),
+         &MetaType::Union(UnionMeta { of_type_names: ref new_members, .. })) =>
+            compare_names(old_members, new_members, changes,
+                |n| SchemaChange::UnionMemberRemoved { type_name: type_name.to_owned(), member_name: n.to_owned() },
+                |n| SchemaChange::UnionMemberAdded { type_name: type_name.to_owned(), member_name: n.to_owned() }),
+        (&MetaType::Enum(EnumMeta { values: ref old_values, .. }),
+         &MetaType::Enum(EnumMeta { values: ref new_values, .. })) =>
+            compare_enum_values(type_name, old_values, new_values, changes),
+        (&MetaType::InputObject(InputObjectMeta { input_fields: ref old_fields, .. }),
+         &MetaType::InputObject(InputObjectMeta { input_fields: ref new_fields, .. })) =>
+            compare_input_fields(type_name, old_fields, new_fields, changes),
+        (&MetaType::Scalar(_), &MetaType::Scalar(_)) => (),
+        _ => changes.push(SchemaChange::TypeKindChanged {
+            type_name: type_name.to_owned(),
+            old_kind: old.type_kind(),
+            new_kind: new.type_kind(),
+        }),
+    }
+}
+
+fn compare_fields(type_name: &str, old_fields: &[Field], new_fields: &[Field], changes: &mut Vec<SchemaChange>) {
+    for old_field in old_fields.iter().filter(|f| !f.name.starts_with("__")) {
+        let new_field = match new_fields.iter().find(|f| f.name == old_field.name) {
+            Some(f) => f,
+            None => {
+                changes.push(SchemaChange::FieldRemoved {
+                    type_name: type_name.to_owned(),
+                    field_name: old_field.name.clone(),
+                });
+                continue;
+            }
+        };
+
+        if old_field.field_type != new_field.field_type {
+            changes.push(SchemaChange::FieldTypeChanged {
+                type_name: type_name.to_owned(),
+                field_name: old_field.name.clone(),
+                old_type: old_field.field_type.clone(),
+                new_type: new_field.field_type.clone(),
+            });
+        }
+
+        if old_field.deprecation_reason.is_none() && new_field.deprecation_reason.is_some() {
+            changes.push(SchemaChange::FieldDeprecated {
+                type_name: type_name.to_owned(),
+                field_name: old_field.name.clone(),
+            });
+        }
+
+        compare_arguments(
+            type_name, &old_field.name,
+            old_field.arguments.as_ref().map_or(&[][..], |a| &a[..]),
+            new_field.arguments.as_ref().map_or(&[][..], |a| &a[..]),
+            changes);
+    }
+
+    for new_field in new_fields.iter().filter(|f| !f.name.starts_with("__")) {
+        if !old_fields.iter().any(|f| f.name == new_field.name) {
+            changes.push(SchemaChange::FieldAdded {
+                type_name: type_name.to_owned(),
+                field_name: new_field.name.clone(),
+            });
+        }
+    }
+}
+
+fn compare_arguments(
+    type_name: &str,
+    field_name: &str,
+    old_args: &[Argument],
+    new_args: &[Argument],
+    changes: &mut Vec<SchemaChange>,
+) {
+    for old_arg in old_args {
+        let new_arg = match new_args.iter().find(|a| a.name == old_arg.name) {
+            Some(a) => a,
+            None => {
+                changes.push(SchemaChange::ArgumentRemoved {
+                    type_name: type_name.to_owned(),
+                    field_name: field_name.to_owned(),
+                    argument_name: old_arg.name.clone(),
+                });
+                continue;
+            }
+        };
+
+        if old_arg.arg_type != new_arg.arg_type {
+            changes.push(SchemaChange::ArgumentTypeChanged {
+                type_name: type_name.to_owned(),
+                field_name: field_name.to_owned(),
+                argument_name: old_arg.name.clone(),
+                old_type: old_arg.arg_type.clone(),
+                new_type: new_arg.arg_type.clone(),
+            });
+        }
+
+        let default_changed = match (&old_arg.default_value, &new_arg.default_value) {
+            (&Some(ref old_value), &Some(ref new_value)) => !old_value.unlocated_eq(new_value),
+            (&None, &None) => false,
+            _ => true,
+        };
+
+        if default_changed {
+            changes.push(SchemaChange::ArgumentDefaultValueChanged {
+                type_name: type_name.to_owned(),
+                field_name: field_name.to_owned(),
+                argument_name: old_arg.name.clone(),
+                old_value: old_arg.default_value.clone(),
+                new_value: new_arg.default_value.clone(),
+            });
+        }
+    }
+
+    for new_arg in new_args {
+        if !old_args.iter().any(|a| a.name == new_arg.name) {
+            changes.push(SchemaChange::ArgumentAdded {
+                type_name: type_name.to_owned(),
+                field_name: field_name.to_owned(),
+                argument_name: new_arg.name.clone(),
+                required: is_required(new_arg),
+            });
+        }
+    }
+}
+
+fn compare_input_fields(type_name: &str, old_fields: &[Argument], new_fields: &[Argument], changes: &mut Vec<SchemaChange>) {
+    for old_field in old_fields {
+        match new_fields.iter().find(|f| f.name == old_field.name) {
+            Some(new_field) => if old_field.arg_type != new_field.arg_type {
+                changes.push(SchemaChange::InputFieldTypeChanged {
+                    type_name: type_name.to_owned(),
+                    field_name: old_field.name.clone(),
+                    old_type: old_field.arg_type.clone(),
+                    new_type: new_field.arg_type.clone(),
+                });
+            },
+            None => changes.push(SchemaChange::InputFieldRemoved {
+                type_name: type_name.to_owned(),
+                field_name: old_field.name.clone(),
+            }),
+        }
+    }
+
+    for new_field in new_fields {
+        if !old_fields.iter().any(|f| f.name == new_field.name) {
+            changes.push(SchemaChange::InputFieldAdded {
+                type_name: type_name.to_owned(),
+                field_name: new_field.name.clone(),
+                required: is_required(new_field),
+            });
+        }
+    }
+}
+
+fn compare_enum_values(type_name: &str, old_values: &[EnumValue], new_values: &[EnumValue], changes: &mut Vec<SchemaChange>) {
+    for old_value in old_values {
+        match new_values.iter().find(|v| v.name == old_value.name) {
+            Some(new_value) =>
+                if old_value.deprecation_reason.is_none() && new_value.deprecation_reason.is_some() {
+                    changes.push(SchemaChange::EnumValueDeprecated {
+                        type_name: type_name.to_owned(),
+                        value: old_value.name.clone(),
+                    });
+                },
+            None => changes.push(SchemaChange::EnumValueRemoved {
+                type_name: type_name.to_owned(),
+                value: old_value.name.clone(),
+            }),
+        }
+    }
+
+    for new_value in new_values {
+        if !old_values.iter().any(|v| v.name == new_value.name) {
+            changes.push(SchemaChange::EnumValueAdded {
+                type_name: type_name.to_owned(),
+                value: new_value.name.clone(),
+            });
+        }
+    }
+}
+
+fn compare_names<R, A>(old_names: &[String], new_names: &[String], changes: &mut Vec<SchemaChange>, removed: R, added: A)
+    where R: Fn(&str) -> SchemaChange, A: Fn(&str) -> SchemaChange
+{
+    for name in old_names {
+        if !new_names.contains(name) {
+            changes.push(removed(name));
+        }
+    }
+
+    for name in new_names {
+        if !old_names.contains(name) {
+            changes.push(added(name));
+        }
+    }
+}
+
+fn is_required(arg: &Argument) -> bool {
+    arg.arg_type.is_non_null() && arg.default_value.is_none()
+}
+
+/// A field may become stricter: nullable types can turn non-null, but not
+/// the other way around.
+fn is_safe_output_type_change(old: &Type, new: &Type) -> bool {
+    match (old, new) {
+        (&Type::Named(ref o), &Type::Named(ref n)) |
+        (&Type::Named(ref o), &Type::NonNullNamed(ref n)) |
+        (&Type::NonNullNamed(ref o), &Type::NonNullNamed(ref n)) => o == n,
+        (&Type::List(ref o), &Type::List(ref n)) |
+        (&Type::List(ref o), &Type::NonNullList(ref n)) |
+        (&Type::NonNullList(ref o), &Type::NonNullList(ref n)) => is_safe_output_type_change(o, n),
+        _ => false,
+    }
+}
+
+/// An argument or input field may become more lenient: non-null types can
+/// turn nullable, but not the other way around.
+fn is_safe_input_type_change(old: &Type, new: &Type) -> bool {
+    match (old, new) {
+        (&Type::Named(ref o), &Type::Named(ref n)) |
+        (&Type::NonNullNamed(ref o), &Type::Named(ref n)) |
+        (&Type::NonNullNamed(ref o), &Type::NonNullNamed(ref n)) => o == n,
+        (&Type::List(ref o), &Type::List(ref n)) |
+        (&Type::NonNullList(ref o), &Type::List(ref n)) |
+        (&Type::NonNullList(ref o), &Type::NonNullList(ref n)) => is_safe_input_type_change(o, n),
+        _ => false,
+    }
+}
+
+fn kind_name(kind: &TypeKind) -> &'static str {
+    match *kind {
+        TypeKind::Scalar => "a scalar type",
+        TypeKind::Object => "an object type",
+        TypeKind::Interface => "an interface type",
+        TypeKind::Union => "a union type",
+        TypeKind::Enum => "an enum type",
+        TypeKind::InputObject => "an input object type",
+        TypeKind::List => "a list type",
+        TypeKind::NonNull => "a non-null type",
+    }
+}
@@ -1,34 +1,53 @@
 use std::collections::HashMap;
 use std::marker::PhantomData;
 use std::fmt;
+use std::sync::atomic::{AtomicUsize, Ordering};
 
 use types::base::{GraphQLType};
 use executor::Registry;
 use ast::Type;
 use schema::meta::{MetaType, ObjectMeta, PlaceholderMeta, UnionMeta, InterfaceMeta, Argument};
+use schema::printer::print_schema;
+use validation::{QueryComplexity, QueryLimits, Validator};
+use persisted::PersistedQueries;
+use GraphQLError;
 
 /// Root query node of a schema
 ///
-/// This brings the mutatino and query types together, and provides the
-/// predefined metadata fields.
-pub struct RootNode<InnerT, QueryT, MutationT=()> {
+/// This brings the query, mutation, and subscription types together, and
+/// provides the predefined metadata fields.
+pub struct RootNode<InnerT, QueryT, MutationT=(), SubscriptionT=()> {
     #[doc(hidden)]
     pub query_type: QueryT,
     #[doc(hidden)]
     pub mutation_type: MutationT,
     #[doc(hidden)]
+    pub subscription_type: SubscriptionT,
+    #[doc(hidden)]
     pub schema: SchemaType,
+    #[doc(hidden)]
+    pub complexity: Option<QueryComplexity>,
+    #[doc(hidden)]
+    pub limits: Option<QueryLimits>,
+    #[doc(hidden)]
+    pub validator: Option<Validator>,
+    #[doc(hidden)]
+    pub persisted_queries: Option<PersistedQueries>,
     phantom_wrapped: PhantomData<InnerT>,
 }
 
 /// Metadata for a schema
 pub struct SchemaType {
+    id: usize,
     types: HashMap<String, MetaType>,
     query_type_name: String,
     mutation_type_name: Option<String>,
+    subscription_type_name: Option<String>,
     directives: HashMap<String, DirectiveType>,
 }
 
+static NEXT_SCHEMA_ID: AtomicUsize = AtomicUsize::new(0);
+
 pub enum TypeType<'a> {
     Concrete(&'a MetaType),
     NonNull(Box<TypeType<'a>>),
@@ -46,6 +65,7 @@ pub struct DirectiveType {
 pub enum DirectiveLocation {
     Query,
     Mutation,
+    Subscription,
     Field,
     FragmentDefinition,
     FragmentSpread,
@@ -61,29 +81,116 @@ impl<InnerT, QueryT, MutationT> RootNode<InnerT, QueryT, MutationT>
     /// If the schema should not support mutations, you can pass in `()` to
     /// remove the mutation type from the schema.
     pub fn new(query_obj: QueryT, mutation_obj: MutationT) -> RootNode<InnerT, QueryT, MutationT> {
+        RootNode::new_with_subscription(query_obj, mutation_obj, ())
+    }
+}
+
+impl<InnerT, QueryT, MutationT, SubscriptionT> RootNode<InnerT, QueryT, MutationT, SubscriptionT>
+    where QueryT: GraphQLType<InnerT>,
+          MutationT: GraphQLType<InnerT>,
+          SubscriptionT: GraphQLType<InnerT>,
+{
+    /// Construct a new root node from query, mutation, and subscription nodes
+    ///
+    /// The subscription node should implement `GraphQLSubscriptionType` in
+    /// order to be usable with `juniper::subscribe`.
+    pub fn new_with_subscription(
+        query_obj: QueryT,
+        mutation_obj: MutationT,
+        subscription_obj: SubscriptionT,
+    )
+        -> RootNode<InnerT, QueryT, MutationT, SubscriptionT>
+    {
         RootNode {
             query_type: query_obj,
             mutation_type: mutation_obj,
-            schema: SchemaType::new::<InnerT, QueryT, MutationT>(),
+            subscription_type: subscription_obj,
+            schema: SchemaType::new::<InnerT, QueryT, MutationT, SubscriptionT>(),
+            complexity: None,
+            limits: None,
+            validator: None,
+            persisted_queries: None,
             phantom_wrapped: PhantomData,
         }
     }
+
+    /// Limit the complexity of queries executed against this schema
+    ///
+    /// Queries whose cost exceeds the maximum cost of `complexity` are
+    /// rejected with `GraphQLError::QueryTooComplex` before execution.
+    pub fn with_complexity(mut self, complexity: QueryComplexity) -> RootNode<InnerT, QueryT, MutationT, SubscriptionT> {
+        self.complexity = Some(complexity);
+        self
+    }
+
+    /// Limit the structure of queries executed against this schema
+    ///
+    /// Documents that break any of the `limits` are rejected with a
+    /// validation error before execution.
+    pub fn with_limits(mut self, limits: QueryLimits) -> RootNode<InnerT, QueryT, MutationT, SubscriptionT> {
+        self.limits = Some(limits);
+        self
+    }
+
+    /// Validate queries executed against this schema with a custom validator
+    ///
+    /// By default, queries are validated with the rules from the
+    /// specification.
+    pub fn with_validator(mut self, validator: Validator) -> RootNode<InnerT, QueryT, MutationT, SubscriptionT> {
+        self.validator = Some(validator);
+        self
+    }
+
+    /// Keep the queries executed against this schema in a persisted query
+    /// store
+    ///
+    /// Queries executed by source are looked up by the hash of their source
+    /// and added to the store if they are not in it, unless the store is an
+    /// allow-list. Stored queries can be executed by id with
+    /// `juniper::execute_persisted`.
+    pub fn with_persisted_queries(mut self, persisted_queries: PersistedQueries) -> RootNode<InnerT, QueryT, MutationT, SubscriptionT> {
+        self.persisted_queries = Some(persisted_queries);
+        self
+    }
+
+    /// Prepare a query and add it to the persisted query store under an id
+    ///
+    /// Store queries under `juniper::query_hash` of their source to have
+    /// them found when they are executed by source as well, e.g. to fill an
+    /// allow-list. Fails with `GraphQLError::PersistedQueryNotSupported` if
+    /// the schema has no store.
+    pub fn persist_query<'a>(&self, id: &str, document_source: &'a str) -> Result<(), GraphQLError<'a>> {
+        match self.persisted_queries {
+            Some(ref persisted) => persisted.insert(id, document_source, self).map(|_| ()),
+            None => Err(GraphQLError::PersistedQueryNotSupported),
+        }
+    }
+
+    /// Render the schema in the GraphQL schema definition language
+    ///
+    /// See `SchemaType::as_schema_language` for details on the output.
+    pub fn as_schema_language(&self) -> String {
+        self.schema.as_schema_language()
+    }
 }
 
 impl SchemaType {
-    pub fn new<CtxT, QueryT, MutationT>() -> SchemaType
+    pub fn new<CtxT, QueryT, MutationT, SubscriptionT>() -> SchemaType
         where QueryT: GraphQLType<CtxT>,
               MutationT: GraphQLType<CtxT>,
+              SubscriptionT: GraphQLType<CtxT>,
     {
         let mut types = HashMap::new();
         let mut directives = HashMap::new();
         let query_type_name: String;
         let mutation_type_name: String;
+        let subscription_type_name: String;
 
         {
             let mut registry = Registry::<CtxT>::new(types);
             query_type_name = registry.get_type::<QueryT>().innermost_name().to_owned();
             mutation_type_name = registry.get_type::<MutationT>().innermost_name().to_owned();
+            subscription_type_name = registry.get_type::<SubscriptionT>().innermost_name().to_owned();
             types = registry.types;
         }
 
@@ -125,13 +232,33 @@ impl SchemaType {
         }
 
         SchemaType {
+            id: NEXT_SCHEMA_ID.fetch_add(1, Ordering::Relaxed),
             types: types,
             query_type_name: query_type_name,
             mutation_type_name: if &mutation_type_name != "__Unit" { Some(mutation_type_name) } else { None },
+            subscription_type_name:
+                if &subscription_type_name != "__Unit" { Some(subscription_type_name) } else { None },
             directives: directives,
         }
     }
 
+    /// An id that is unique to this schema within the process
+    ///
+    /// Prepared queries use it to check that they are executed with the
+    /// schema they were validated against.
+    #[doc(hidden)]
+    pub fn id(&self) -> usize {
+        self.id
+    }
+
+    /// Render the schema in the GraphQL schema definition language
+    ///
+    /// Types are sorted by name and fields are kept in declaration order, so
+    /// the output is stable and suitable for committing and diffing.
+    pub fn as_schema_language(&self) -> String {
+        print_schema(self)
+    }
+
     pub fn add_directive(&mut self, directive: DirectiveType) {
         self.directives.insert(directive.name.clone(), directive);
     }
@@ -171,6 +298,22 @@ impl SchemaType {
                 .expect("Mutation type does not exist in schema"))
     }
 
+    pub fn subscription_type(&self) -> Option<TypeType> {
+        if let Some(ref subscription_type_name) = self.subscription_type_name {
+            Some(self.type_by_name(subscription_type_name)
+                .expect("Subscription type does not exist in schema"))
+        }
+        else {
+            None
+        }
+    }
+
+    pub fn concrete_subscription_type(&self) -> Option<&MetaType> {
+        self.subscription_type_name.as_ref().map(|name|
+            self.concrete_type_by_name(name)
+                .expect("Subscription type does not exist in schema"))
+    }
+
     pub fn type_list(&self) -> Vec<TypeType> {
         self.types.values().map(|t| TypeType::Concrete(t)).collect()
     }
@@ -331,6 +474,7 @@ impl fmt::Display for DirectiveLocation {
         f.write_str(match *self {
             DirectiveLocation::Query => "query",
             DirectiveLocation::Mutation => "mutation",
+            DirectiveLocation::Subscription => "subscription",
             DirectiveLocation::Field => "field",
             DirectiveLocation::FragmentDefinition => "fragment definition",
             DirectiveLocation::FragmentSpread => "fragment spread",
@@ -0,0 +1,245 @@
+//! Print a schema in the GraphQL schema definition language
+
+use ast::{InputValue, Type, escape_string};
+use schema::meta::{MetaType, ScalarMeta, ObjectMeta, EnumMeta, InterfaceMeta, UnionMeta,
+                   InputObjectMeta, Field, Argument, EnumValue};
+use schema::model::{SchemaType, DirectiveType};
+
+const BUILTIN_SCALARS: &'static [&'static str] = &["String", "Int", "Float", "Boolean", "ID"];
+const BUILTIN_DIRECTIVES: &'static [&'static str] = &["skip", "include", "deprecated"];
+
+/// Render the schema as SDL text
+///
+/// Introspection types, built-in scalars, and built-in directives are
+/// omitted. Types and directives are sorted by name, while fields, arguments,
+/// and enum values are kept in the order they were declared, so the output
+/// is stable between runs.
+pub fn print_schema(schema: &SchemaType) -> String {
+    let mut blocks = vec![print_schema_definition(schema)];
+
+    let mut directives = schema.directive_list()
+        .into_iter()
+        .filter(|d| !BUILTIN_DIRECTIVES.contains(&d.name.as_str()))
+        .collect::<Vec<_>>();
+    directives.sort_by(|a, b| a.name.cmp(&b.name));
+
+    blocks.extend(directives.into_iter().map(|d| print_directive(schema, d)));
+
+    let mut types = schema.concrete_type_list()
+        .into_iter()
+        .filter(|t| t.name().map_or(false, |n| !n.starts_with("__") && !BUILTIN_SCALARS.contains(&n)))
+        .collect::<Vec<_>>();
+    types.sort_by(|a, b| a.name().cmp(&b.name()));
+
+    blocks.extend(types.into_iter().filter_map(|t| print_type(schema, t)));
+
+    let mut out = blocks.join("\n\n");
+    out.push('\n');
+    out
+}
+
+fn print_schema_definition(schema: &SchemaType) -> String {
+    let mut out = "schema {\n".to_owned();
+
+    out.push_str(&format!("  query: {}\n", schema.concrete_query_type().name().unwrap()));
+
+    if let Some(mutation_type) = schema.concrete_mutation_type() {
+        out.push_str(&format!("  mutation: {}\n", mutation_type.name().unwrap()));
+    }
+
+    if let Some(subscription_type) = schema.concrete_subscription_type() {
+        out.push_str(&format!("  subscription: {}\n", subscription_type.name().unwrap()));
+    }
+
+    out.push('}');
+    out
+}
+
+fn print_type(schema: &SchemaType, t: &MetaType) -> Option<String> {
+    Some(match *t {
+        MetaType::Scalar(ScalarMeta { ref name, ref description, .. }) =>
+            format!("{}scalar {}", print_description(description, ""), name),
+        MetaType::Object(ObjectMeta { ref name, ref description, ref fields, ref interface_names }) => {
+            let implements = if interface_names.is_empty() {
+                    String::new()
+                }
+                else {
+                    format!(" implements {}", interface_names.join(" & "))
+                };
+
+            format!("{}type {}{}{}",
+                print_description(description, ""),
+                name,
+                implements,
+                print_fields(schema, fields))
+        },
+        MetaType::Interface(InterfaceMeta { ref name, ref description, ref fields }) =>
+            format!("{}interface {}{}",
+                print_description(description, ""),
+                name,
+                print_fields(schema, fields)),
+        MetaType::Union(UnionMeta { ref name, ref description, ref of_type_names }) =>
+            format!("{}union {} = {}",
+                print_description(description, ""),
+                name,
+                of_type_names.join(" | ")),
+        MetaType::Enum(EnumMeta { ref name, ref description, ref values, .. }) =>
+            format!("{}enum {}{}",
+                print_description(description, ""),
+                name,
+                print_block(values.iter().map(print_enum_value).collect())),
+        MetaType::InputObject(InputObjectMeta { ref name, ref description, ref input_fields, .. }) =>
+            format!("{}input {}{}",
+                print_description(description, ""),
+                name,
+                print_block(input_fields.iter().map(|f| print_input_value(schema, f, "  ")).collect())),
+        MetaType::List(_) | MetaType::Nullable(_) | MetaType::Placeholder(_) => return None,
+    })
+}
+
+fn print_fields(schema: &SchemaType, fields: &[Field]) -> String {
+    print_block(fields
+        .iter()
+        .filter(|f| !f.name.starts_with("__"))
+        .map(|f| format!("{}  {}{}: {}{}",
+            print_description(&f.description, "  "),
+            f.name,
+            print_arguments(schema, &f.arguments, "  "),
+            f.field_type,
+            print_deprecated(&f.deprecation_reason)))
+        .collect())
+}
+
+fn print_enum_value(value: &EnumValue) -> String {
+    format!("{}  {}{}",
+        print_description(&value.description, "  "),
+        value.name,
+        print_deprecated(&value.deprecation_reason))
+}
+
+fn print_arguments(schema: &SchemaType, args: &Option<Vec<Argument>>, indent: &str) -> String {
+    let args = match *args {
+        Some(ref args) if !args.is_empty() => args,
+        _ => return String::new(),
+    };
+
+    if args.iter().all(|a| a.description.is_none()) {
+        format!("({})", args
+            .iter()
+            .map(|a| print_input_value(schema, a, ""))
+            .collect::<Vec<_>>()
+            .join(", "))
+    }
+    else {
+        let inner_indent = format!("{}  ", indent);
+
+        format!("(\n{}\n{})", args
+            .iter()
+            .map(|a| print_input_value(schema, a, &inner_indent))
+            .collect::<Vec<_>>()
+            .join("\n"),
+            indent)
+    }
+}
+
+fn print_input_value(schema: &SchemaType, arg: &Argument, indent: &str) -> String {
+    let default_value = match arg.default_value {
+        Some(ref v) => format!(" = {}", print_value(schema, v, &arg.arg_type)),
+        None => String::new(),
+    };
+
+    format!("{}{}{}: {}{}",
+        print_description(&arg.description, indent),
+        indent,
+        arg.name,
+        arg.arg_type,
+        default_value)
+}
+
+fn print_directive(schema: &SchemaType, directive: &DirectiveType) -> String {
+    let arguments = if directive.arguments.is_empty() {
+            None
+        }
+        else {
+            Some(directive.arguments.clone())
+        };
+
+    format!("{}directive @{}{} on {}",
+        print_description(&directive.description, ""),
+        directive.name,
+        print_arguments(schema, &arguments, ""),
+        directive.locations
+            .iter()
+            .map(|l| l.to_string().to_uppercase().replace(' ', "_"))
+            .collect::<Vec<_>>()
+            .join(" | "))
+}
+
+fn print_deprecated(reason: &Option<String>) -> String {
+    match *reason {
+        Some(ref reason) => format!(" @deprecated(reason: \"{}\")", escape_string(reason)),
+        None => String::new(),
+    }
+}
+
+fn print_description(description: &Option<String>, indent: &str) -> String {
+    match *description {
+        Some(ref d) if d.contains('\n') => {
+            let mut out = format!("{}\"\"\"\n", indent);
+            for line in d.replace("\"\"\"", "\\\"\"\"").lines() {
+                if !line.is_empty() {
+                    out.push_str(indent);
+                    out.push_str(line);
+                }
+                out.push('\n');
+            }
+            out.push_str(&format!("{}\"\"\"\n", indent));
+            out
+        },
+        Some(ref d) => format!("{}\"{}\"\n", indent, escape_string(d)),
+        None => String::new(),
+    }
+}
+
+fn print_block(lines: Vec<String>) -> String {
+    if lines.is_empty() {
+        String::new()
+    }
+    else {
+        format!(" {{\n{}\n}}", lines.join("\n"))
+    }
+}
+
+/// Print a value literal, using the declared type to turn the string values
+/// produced by `ToInputValue` for enums back into enum literals, and to order
+/// input object fields by declaration.
+fn print_value(schema: &SchemaType, value: &InputValue, value_type: &Type) -> String {
+    match (value, value_type) {
+        (&InputValue::List(ref items), &Type::List(ref inner)) |
+        (&InputValue::List(ref items), &Type::NonNullList(ref inner)) =>
+            format!("[{}]", items
+                .iter()
+                .map(|i| print_value(schema, &i.item, inner))
+                .collect::<Vec<_>>()
+                .join(", ")),
+        (_, &Type::List(ref inner)) | (_, &Type::NonNullList(ref inner)) =>
+            print_value(schema, value, inner),
+        (&InputValue::String(ref s), _) => match schema.concrete_type_by_name(value_type.innermost_name()) {
+            Some(&MetaType::Enum(_)) => s.clone(),
+            _ => value.to_string(),
+        },
+        (&InputValue::Object(ref fields), _) => match schema.concrete_type_by_name(value_type.innermost_name()) {
+            Some(&MetaType::InputObject(InputObjectMeta { ref input_fields, .. })) =>
+                format!("{{{}}}", input_fields
+                    .iter()
+                    .filter_map(|f| fields
+                        .iter()
+                        .find(|&&(ref k, _)| k.item == f.name)
+                        .map(|&(_, ref v)| format!("{}: {}", f.name, print_value(schema, &v.item, &f.arg_type))))
+                    .collect::<Vec<_>>()
+                    .join(", ")),
+            _ => value.to_string(),
+        },
+        _ => value.to_string(),
+    }
+}
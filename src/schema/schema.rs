@@ -7,9 +7,11 @@ use schema::meta::{MetaType, ObjectMeta, EnumMeta, InputObjectMeta, UnionMeta, I
                    Field, Argument, EnumValue};
 use schema::model::{RootNode, SchemaType, TypeType, DirectiveType, DirectiveLocation};
 
-impl<CtxT, QueryT, MutationT> GraphQLType<CtxT> for RootNode<CtxT, QueryT, MutationT>
+impl<CtxT, QueryT, MutationT, SubscriptionT> GraphQLType<CtxT>
+    for RootNode<CtxT, QueryT, MutationT, SubscriptionT>
     where QueryT: GraphQLType<CtxT>,
-          MutationT: GraphQLType<CtxT>
+          MutationT: GraphQLType<CtxT>,
+          SubscriptionT: GraphQLType<CtxT>
 {
     fn name() -> Option<&'static str> {
         QueryT::name()
@@ -23,7 +25,7 @@ impl<CtxT, QueryT, MutationT> GraphQLType<CtxT> for RootNode<CtxT, QueryT, Mutat
         match field {
             "__schema" => executor.replaced_context(&self.schema).resolve(&self.schema),
             "__type" => {
-                let type_name: String = args.get("name").unwrap();
+                let type_name: String = try!(args.try_get("name"));
                 executor.replaced_context(&self.schema).resolve(&self.schema.type_by_name(&type_name))
             },
             _=> self.query_type.resolve_field(field, args, executor),
@@ -44,6 +46,10 @@ graphql_object!(SchemaType: SchemaType as "__Schema" |&self| {
         self.mutation_type()
     }
 
+    field subscription_type() -> Option<TypeType> {
+        self.subscription_type()
+    }
+
     field directives() -> Vec<&DirectiveType> {
         self.directive_list()
     }
@@ -244,6 +250,7 @@ graphql_object!(DirectiveType: SchemaType as "__Directive" |&self| {
 graphql_enum!(DirectiveLocation as "__DirectiveLocation" {
     DirectiveLocation::Query => "QUERY",
     DirectiveLocation::Mutation => "MUTATION",
+    DirectiveLocation::Subscription => "SUBSCRIPTION",
     DirectiveLocation::Field => "FIELD",
     DirectiveLocation::FragmentDefinition => "FRAGMENT_DEFINITION",
     DirectiveLocation::FragmentSpread => "FRAGMENT_SPREAD",
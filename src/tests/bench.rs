@@ -22,6 +22,24 @@ fn query_type_name(b: &mut Bencher) {
     b.iter(|| ::execute(doc, None, &schema, &HashMap::new(), &database));
 }
 
+#[bench]
+fn query_type_name_prepared(b: &mut Bencher) {
+    let database = Database::new();
+    let schema = RootNode::new(&database, ());
+
+    let doc = r#"
+        query IntrospectionQueryTypeQuery {
+          __schema {
+            queryType {
+              name
+            }
+          }
+        }"#;
+    let query = ::PreparedQuery::new(doc, &schema).expect("Invalid query");
+
+    b.iter(|| query.execute(None, &schema, &HashMap::new(), &database));
+}
+
 #[bench]
 fn introspection_query(b: &mut Bencher) {
     let database = Database::new();
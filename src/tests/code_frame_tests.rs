@@ -0,0 +1,76 @@
+use std::collections::HashMap;
+
+use parser::{SourcePosition, Spanning};
+use schema::model::RootNode;
+use tests::model::Database;
+use ::GraphQLError;
+
+fn execute_error(query: &str) -> GraphQLError {
+    let database = Database::new();
+    let schema = RootNode::new(&database, ());
+
+    ::execute(query, None, &schema, &HashMap::new(), &database).expect_err("Query should fail")
+}
+
+#[test]
+fn renders_parse_errors() {
+    let query = "{\n  hero {\n    name\n}";
+
+    assert_eq!(execute_error(query).render(query), concat!(
+        "error: Unexpected end of input\n",
+        " --> 4:2\n",
+        "  |\n",
+        "4 | }\n",
+        "  |  ^\n"));
+}
+
+#[test]
+fn renders_validation_errors() {
+    let query = "{\n  hero {\n    nmae\n  }\n}";
+
+    assert_eq!(execute_error(query).render(query), concat!(
+        "error: Unknown field \"nmae\" on type \"Character\". Did you mean \"name\"?\n",
+        " --> 3:5\n",
+        "  |\n",
+        "3 |     nmae\n",
+        "  |     ^~~~\n"));
+}
+
+#[test]
+fn renders_every_location_of_validation_errors() {
+    let query = "{ hero { ...F } }\nfragment F on Character { name }\nfragment F on Character { id }";
+
+    assert_eq!(execute_error(query).render(query), concat!(
+        "error: There can only be one fragment named F\n",
+        " --> 2:10\n",
+        "  |\n",
+        "2 | fragment F on Character { name }\n",
+        "  |          ^\n",
+        " --> 3:10\n",
+        "  |\n",
+        "3 | fragment F on Character { id }\n",
+        "  |          ^\n"));
+}
+
+#[test]
+fn renders_operation_errors_without_code_frames() {
+    let query = "query A { hero { name } }\nquery B { hero { id } }";
+    let err = execute_error(query);
+
+    assert_eq!(err.spans(query), vec![]);
+    assert_eq!(
+        err.render(query),
+        "error: Must provide operation name if query contains multiple operations.\n");
+}
+
+#[test]
+fn spans_cover_the_tokens_errors_point_to() {
+    let query = "{ hero { nmae friends { nmae } } }";
+
+    assert_eq!(
+        execute_error(query).spans(query),
+        vec![
+            Spanning::start_end(&SourcePosition::new(9, 0, 9), &SourcePosition::new(13, 0, 13), ()),
+            Spanning::start_end(&SourcePosition::new(24, 0, 24), &SourcePosition::new(28, 0, 28), ()),
+        ]);
+}
@@ -0,0 +1,199 @@
+use std::collections::HashMap;
+
+use rustc_serialize::json::{Json, ToJson};
+
+use ast::InputValue;
+use value::Value;
+use schema::model::RootNode;
+use validation::QueryComplexity;
+use tests::model::Database;
+use ::GraphQLError;
+
+struct Root;
+struct Item;
+
+graphql_object!(Root: () |&self| {
+    field items(first: Option<i64>) -> Vec<Item> {
+        (0..first.unwrap_or(1)).map(|_| Item).collect()
+    }
+
+    field pages(first = 100: i64) -> Vec<Item> {
+        (0..first).map(|_| Item).collect()
+    }
+});
+
+graphql_object!(Item: () |&self| {
+    field name() -> &str {
+        "item"
+    }
+});
+
+const FRIENDS_OF_FRIENDS: &'static str = r#"
+    {
+        hero {
+            name
+            friends {
+                name
+                friends {
+                    name
+                }
+            }
+        }
+    }"#;
+
+fn cost(complexity: &QueryComplexity, query: &str, operation_name: Option<&str>, vars: &HashMap<String, InputValue>) -> usize {
+    let database = Database::new();
+    let schema = RootNode::new(&database, ());
+    let document = ::parser::parse_document_source(query).expect("Parse error");
+
+    complexity.cost(&document, operation_name, &schema.schema, vars)
+}
+
+fn item_cost(complexity: &QueryComplexity, query: &str, vars: &HashMap<String, InputValue>) -> usize {
+    let schema = RootNode::new(Root, ());
+    let document = ::parser::parse_document_source(query).expect("Parse error");
+
+    complexity.cost(&document, None, &schema.schema, vars)
+}
+
+#[test]
+fn every_field_costs_one_by_default() {
+    assert_eq!(cost(&QueryComplexity::new(), FRIENDS_OF_FRIENDS, None, &HashMap::new()), 6);
+}
+
+#[test]
+fn field_costs_apply_per_type_and_field() {
+    let complexity = QueryComplexity::new()
+        .field_cost("Character", "friends", 5);
+
+    assert_eq!(cost(&complexity, FRIENDS_OF_FRIENDS, None, &HashMap::new()), 14);
+}
+
+#[test]
+fn default_cost_can_be_changed() {
+    let complexity = QueryComplexity::new()
+        .default_cost(0)
+        .field_cost("Character", "friends", 1);
+
+    assert_eq!(cost(&complexity, FRIENDS_OF_FRIENDS, None, &HashMap::new()), 2);
+}
+
+#[test]
+fn fragments_are_expanded() {
+    let query = r#"
+        { hero { ...HeroName ... on Human { homePlanet } ... { id } } }
+        fragment HeroName on Character { name }"#;
+
+    assert_eq!(cost(&QueryComplexity::new(), query, None, &HashMap::new()), 4);
+}
+
+#[test]
+fn typename_is_free() {
+    assert_eq!(cost(&QueryComplexity::new(), "{ __typename hero { __typename } }", None, &HashMap::new()), 1);
+}
+
+#[test]
+fn skipped_selections_are_not_counted() {
+    let query = r#"
+        query Q($skip: Boolean!) {
+            hero {
+                id @skip(if: true)
+                name @include(if: $skip)
+                friends @skip(if: $skip) { name }
+            }
+        }"#;
+
+    let vars = vec![
+        ("skip".to_owned(), InputValue::boolean(true)),
+    ].into_iter().collect();
+
+    assert_eq!(cost(&QueryComplexity::new(), query, None, &vars), 2);
+}
+
+#[test]
+fn named_operation_is_selected() {
+    let query = r#"
+        query Small { hero { name } }
+        query Large { hero { name id friends { name } } }"#;
+
+    assert_eq!(cost(&QueryComplexity::new(), query, Some("Small"), &HashMap::new()), 2);
+    assert_eq!(cost(&QueryComplexity::new(), query, Some("Large"), &HashMap::new()), 5);
+    assert_eq!(cost(&QueryComplexity::new(), query, Some("Unknown"), &HashMap::new()), 0);
+}
+
+#[test]
+fn multiplier_arguments_multiply_the_field_cost() {
+    let complexity = QueryComplexity::new()
+        .multiplier_argument("first");
+
+    assert_eq!(item_cost(&complexity, "{ items(first: 10) { name } }", &HashMap::new()), 20);
+    assert_eq!(item_cost(&complexity, "{ items { name } }", &HashMap::new()), 2);
+    assert_eq!(item_cost(&complexity, "{ items(first: -1) { name } }", &HashMap::new()), 0);
+    assert_eq!(item_cost(&QueryComplexity::new(), "{ items(first: 10) { name } }", &HashMap::new()), 2);
+}
+
+#[test]
+fn multiplier_arguments_use_variables_and_defaults() {
+    let complexity = QueryComplexity::new()
+        .multiplier_argument("first");
+    let query = "query Q($n: Int = 5) { items(first: $n) { name } }";
+
+    let vars = vec![
+        ("n".to_owned(), InputValue::int(3)),
+    ].into_iter().collect();
+
+    assert_eq!(item_cost(&complexity, query, &HashMap::new()), 10);
+    assert_eq!(item_cost(&complexity, query, &vars), 6);
+}
+
+#[test]
+fn omitted_multiplier_arguments_use_the_schema_default() {
+    let complexity = QueryComplexity::new()
+        .multiplier_argument("first");
+
+    assert_eq!(item_cost(&complexity, "{ pages { name } }", &HashMap::new()), 200);
+    assert_eq!(item_cost(&complexity, "{ pages(first: 3) { name } }", &HashMap::new()), 6);
+    assert_eq!(item_cost(&complexity, "query Q($n: Int) { pages(first: $n) { name } }", &HashMap::new()), 200);
+}
+
+#[test]
+fn queries_over_the_maximum_are_rejected() {
+    let database = Database::new();
+    let schema = RootNode::new(&database, ())
+        .with_complexity(QueryComplexity::new().max_cost(5));
+
+    assert_eq!(
+        ::execute(FRIENDS_OF_FRIENDS, None, &schema, &HashMap::new(), &database),
+        Err(GraphQLError::QueryTooComplex { cost: 6, max_cost: 5 }));
+
+    assert_eq!(
+        ::execute("{ hero { name } }", None, &schema, &HashMap::new(), &database),
+        Ok((Value::object(vec![
+                ("hero", Value::object(vec![
+                    ("name", Value::string("R2-D2")),
+                ].into_iter().collect())),
+            ].into_iter().collect()),
+            vec![])));
+}
+
+#[test]
+fn cost_is_available_for_rejected_queries() {
+    let database = Database::new();
+    let schema = RootNode::new(&database, ())
+        .with_complexity(QueryComplexity::new().max_cost(5));
+
+    assert_eq!(::query_complexity(FRIENDS_OF_FRIENDS, None, &schema, &HashMap::new()), Ok(6));
+    assert_eq!(::query_complexity("{ hero { name } }", None, &schema, &HashMap::new()), Ok(2));
+    assert!(::query_complexity("{ unknown }", None, &schema, &HashMap::new()).is_err());
+}
+
+#[test]
+fn rejected_queries_are_reported_as_error_objects() {
+    let error = GraphQLError::QueryTooComplex { cost: 6, max_cost: 5 };
+
+    assert_eq!(
+        error.to_json(),
+        Json::from_str(r#"{"errors": [
+            {"message": "Query has a complexity of 6, which exceeds the maximum of 5"}
+        ]}"#).unwrap());
+}
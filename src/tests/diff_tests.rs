@@ -0,0 +1,189 @@
+use ast::Type;
+use schema::model::RootNode;
+use schema::diff::{compare_schemas, SchemaChange, ChangeCriticality};
+
+mod old {
+    pub enum Episode { NewHope, Empire, Jedi }
+    pub struct Human;
+    pub struct Droid;
+    pub struct Query;
+    pub struct Mutation;
+    pub enum Character { Human(Human) }
+
+    graphql_enum!(Episode {
+        Episode::NewHope => "NEW_HOPE",
+        Episode::Empire => "EMPIRE",
+        Episode::Jedi => "JEDI",
+    });
+
+    graphql_object!(Human: () |&self| {
+        field name() -> Option<String> { None }
+        field home_planet() -> Option<String> { None }
+        field friends(first = 10: i64, after: Option<String>) -> Vec<Human> { vec![] }
+        field mass() -> f64 { 0.0 }
+    });
+
+    graphql_object!(Droid: () |&self| {
+        field model() -> String { String::new() }
+    });
+
+    graphql_union!(Character: () |&self| {
+        instance_resolvers: |&_| {
+            &Human => match *self { Character::Human(ref h) => Some(h) },
+        }
+    });
+
+    graphql_object!(Query: () |&self| {
+        field hero(episode: Option<Episode>) -> Option<Character> { None }
+        field droid(id: String) -> Option<Droid> { None }
+    });
+
+    graphql_object!(Mutation: () |&self| {
+        field noop() -> bool { true }
+    });
+}
+
+mod new {
+    pub enum Episode { NewHope, Empire }
+    pub struct Human;
+    pub struct Starship;
+    pub struct Query;
+    pub struct Mutation;
+    pub enum Character { Human(Human), Starship(Starship) }
+
+    graphql_enum!(Episode {
+        Episode::NewHope => "NEW_HOPE",
+        Episode::Empire => "EMPIRE" deprecated "Watch the original",
+    });
+
+    graphql_object!(Human: () |&self| {
+        field name() -> String { String::new() }
+        field deprecated "Use planet" home_planet() -> Option<i64> { None }
+        field friends(first = 20: i64, order: String) -> Vec<Human> { vec![] }
+        field height() -> f64 { 0.0 }
+    });
+
+    graphql_object!(Starship: () |&self| {
+        field length() -> f64 { 0.0 }
+    });
+
+    graphql_union!(Character: () |&self| {
+        instance_resolvers: |&_| {
+            &Human => match *self { Character::Human(ref h) => Some(h), _ => None },
+            &Starship => match *self { Character::Starship(ref s) => Some(s), _ => None },
+        }
+    });
+
+    graphql_object!(Query: () |&self| {
+        field hero(episode: Option<Episode>) -> Option<Character> { None }
+        field starship(id: Option<String>) -> Option<Starship> { None }
+    });
+
+    graphql_object!(Mutation: () |&self| {
+        field noop() -> bool { true }
+    });
+}
+
+#[test]
+fn test_identical_schemas() {
+    let first = RootNode::new(old::Query, old::Mutation);
+    let second = RootNode::new(old::Query, old::Mutation);
+
+    assert_eq!(compare_schemas(&first.schema, &second.schema), vec![]);
+}
+
+#[test]
+fn test_changed_schemas() {
+    let before = RootNode::new(old::Query, old::Mutation);
+    let after = RootNode::new(new::Query, new::Mutation);
+
+    let changes = compare_schemas(&before.schema, &after.schema);
+
+    assert_eq!(
+        changes.iter().map(|c| (c.criticality(), c.to_string())).collect::<Vec<_>>(),
+        vec![
+            (ChangeCriticality::Dangerous, "Starship was added to union type Character.".to_owned()),
+            (ChangeCriticality::Breaking, "Droid was removed.".to_owned()),
+            (ChangeCriticality::Safe, "EMPIRE was deprecated in enum type Episode.".to_owned()),
+            (ChangeCriticality::Breaking, "JEDI was removed from enum type Episode.".to_owned()),
+            (ChangeCriticality::Safe, "Human.name changed type from String to String!.".to_owned()),
+            (ChangeCriticality::Breaking, "Human.homePlanet changed type from String to Int.".to_owned()),
+            (ChangeCriticality::Safe, "Human.homePlanet was deprecated.".to_owned()),
+            (ChangeCriticality::Dangerous, "Argument first on Human.friends has changed its default value.".to_owned()),
+            (ChangeCriticality::Breaking, "Argument after was removed from Human.friends.".to_owned()),
+            (ChangeCriticality::Breaking, "Required argument order was added to Human.friends.".to_owned()),
+            (ChangeCriticality::Breaking, "Human.mass was removed.".to_owned()),
+            (ChangeCriticality::Safe, "Human.height was added.".to_owned()),
+            (ChangeCriticality::Breaking, "Query.droid was removed.".to_owned()),
+            (ChangeCriticality::Safe, "Query.starship was added.".to_owned()),
+            (ChangeCriticality::Safe, "Starship was added.".to_owned()),
+        ]);
+}
+
+#[test]
+fn test_removed_mutation_type() {
+    let before = RootNode::new(old::Query, old::Mutation);
+    let after = RootNode::new(old::Query, ());
+
+    let changes = compare_schemas(&before.schema, &after.schema);
+
+    assert_eq!(changes[0], SchemaChange::MutationTypeChanged {
+        old_type_name: Some("Mutation".to_owned()),
+        new_type_name: None,
+    });
+    assert!(changes.iter().all(|c| c.is_breaking()));
+}
+
+#[test]
+fn test_added_mutation_type() {
+    let before = RootNode::new(old::Query, ());
+    let after = RootNode::new(old::Query, old::Mutation);
+
+    let changes = compare_schemas(&before.schema, &after.schema);
+
+    assert_eq!(changes[0], SchemaChange::MutationTypeChanged {
+        old_type_name: None,
+        new_type_name: Some("Mutation".to_owned()),
+    });
+    assert_eq!(changes[0].criticality(), ChangeCriticality::Safe);
+    assert_eq!(format!("{}", changes[0]), "Mutation root type Mutation was added.");
+}
+
+#[test]
+fn test_added_argument_criticality() {
+    let arg_added = |required: bool| SchemaChange::ArgumentAdded {
+        type_name: "T".to_owned(), field_name: "f".to_owned(), argument_name: "a".to_owned(),
+        required: required,
+    }.criticality();
+
+    assert_eq!(arg_added(true), ChangeCriticality::Breaking);
+    assert_eq!(arg_added(false), ChangeCriticality::Dangerous);
+}
+
+#[test]
+fn test_type_change_criticality() {
+    let field_change = |old: Type, new: Type| SchemaChange::FieldTypeChanged {
+        type_name: "T".to_owned(), field_name: "f".to_owned(), old_type: old, new_type: new,
+    }.criticality();
+
+    let arg_change = |old: Type, new: Type| SchemaChange::ArgumentTypeChanged {
+        type_name: "T".to_owned(), field_name: "f".to_owned(), argument_name: "a".to_owned(),
+        old_type: old, new_type: new,
+    }.criticality();
+
+    let named = || Type::Named("Int".to_owned());
+    let non_null = || Type::NonNullNamed("Int".to_owned());
+    let list = |t: Type| Type::List(Box::new(t));
+    let non_null_list = |t: Type| Type::NonNullList(Box::new(t));
+
+    assert_eq!(field_change(named(), non_null()), ChangeCriticality::Safe);
+    assert_eq!(field_change(non_null(), named()), ChangeCriticality::Breaking);
+    assert_eq!(field_change(list(named()), non_null_list(non_null())), ChangeCriticality::Safe);
+    assert_eq!(field_change(list(named()), named()), ChangeCriticality::Breaking);
+    assert_eq!(field_change(named(), Type::Named("Float".to_owned())), ChangeCriticality::Breaking);
+
+    assert_eq!(arg_change(non_null(), named()), ChangeCriticality::Safe);
+    assert_eq!(arg_change(named(), non_null()), ChangeCriticality::Breaking);
+    assert_eq!(arg_change(non_null_list(non_null()), list(named())), ChangeCriticality::Safe);
+    assert_eq!(arg_change(list(named()), list(non_null())), ChangeCriticality::Breaking);
+}
@@ -0,0 +1,128 @@
+use std::collections::HashMap;
+
+use parser::{parse_document_source, SourcePosition};
+use schema::model::RootNode;
+use validation::{QueryLimits, RuleError};
+use tests::model::Database;
+use ::GraphQLError;
+
+fn check(limits: &QueryLimits, query: &str) -> Vec<RuleError> {
+    let document = parse_document_source(query).expect("Parse error");
+
+    limits.check(&document)
+}
+
+#[test]
+fn no_limits_by_default() {
+    let query = r#"{ a: hero { b: friends { c: friends { d: name @include(if: true) } } } }"#;
+
+    assert_eq!(check(&QueryLimits::new(), query), vec![]);
+}
+
+#[test]
+fn depth_is_limited() {
+    let query = r#"{ hero { friends { friends { name } } } }"#;
+
+    assert_eq!(check(&QueryLimits::new().max_depth(4), query), vec![]);
+    assert_eq!(
+        check(&QueryLimits::new().max_depth(3), query),
+        vec![
+            RuleError::new("Field is nested deeper than the maximum depth of 3", &[
+                SourcePosition::new(29, 0, 29),
+            ]),
+        ]);
+}
+
+#[test]
+fn depth_follows_fragment_spreads() {
+    let query = r#"{ hero { ...F } } fragment F on Character { friends { name } }"#;
+
+    assert_eq!(check(&QueryLimits::new().max_depth(3), query), vec![]);
+    assert_eq!(
+        check(&QueryLimits::new().max_depth(2), query),
+        vec![
+            RuleError::new("Field is nested deeper than the maximum depth of 2", &[
+                SourcePosition::new(54, 0, 54),
+            ]),
+        ]);
+}
+
+#[test]
+fn aliases_are_limited() {
+    let query = r#"{ a: hero { name } b: hero { n: name } }"#;
+
+    assert_eq!(check(&QueryLimits::new().max_aliases(3), query), vec![]);
+    assert_eq!(
+        check(&QueryLimits::new().max_aliases(2), query),
+        vec![
+            RuleError::new("Document has more than the maximum of 2 aliases", &[
+                SourcePosition::new(29, 0, 29),
+            ]),
+        ]);
+}
+
+#[test]
+fn root_fields_are_limited_through_fragments() {
+    let query = r#"{ hero { name } ...F } fragment F on Query { human(id: "1000") { name } droid(id: "2000") { name } }"#;
+
+    assert_eq!(check(&QueryLimits::new().max_root_fields(3), query), vec![]);
+    assert_eq!(
+        check(&QueryLimits::new().max_root_fields(2), query),
+        vec![
+            RuleError::new("Operation has more than the maximum of 2 root fields", &[
+                SourcePosition::new(72, 0, 72),
+            ]),
+        ]);
+}
+
+#[test]
+fn directives_are_limited() {
+    let query = r#"query Q($t: Boolean!) { hero { name @include(if: $t) id @skip(if: $t) ...F @skip(if: $t) } } fragment F on Character { id @include(if: $t) }"#;
+
+    assert_eq!(check(&QueryLimits::new().max_directives(4), query), vec![]);
+    assert_eq!(
+        check(&QueryLimits::new().max_directives(2), query),
+        vec![
+            RuleError::new("Document has more than the maximum of 2 directives", &[
+                SourcePosition::new(75, 0, 75),
+            ]),
+        ]);
+}
+
+#[test]
+fn every_broken_limit_is_reported() {
+    let query = r#"{ a: hero { name } b: hero { n: name } }"#;
+    let limits = QueryLimits::new()
+        .max_depth(1)
+        .max_aliases(1)
+        .max_root_fields(1);
+
+    assert_eq!(
+        check(&limits, query),
+        vec![
+            RuleError::new("Field is nested deeper than the maximum depth of 1", &[
+                SourcePosition::new(12, 0, 12),
+            ]),
+            RuleError::new("Operation has more than the maximum of 1 root fields", &[
+                SourcePosition::new(19, 0, 19),
+            ]),
+            RuleError::new("Document has more than the maximum of 1 aliases", &[
+                SourcePosition::new(19, 0, 19),
+            ]),
+        ]);
+}
+
+#[test]
+fn execution_is_rejected_when_a_limit_is_broken() {
+    let database = Database::new();
+    let schema = RootNode::new(&database, ())
+        .with_limits(QueryLimits::new().max_depth(1));
+
+    assert_eq!(
+        ::execute("{ hero { name } }", None, &schema, &HashMap::new(), &database),
+        Err(GraphQLError::ValidationError(vec![
+            RuleError::new("Field is nested deeper than the maximum depth of 1", &[
+                SourcePosition::new(9, 0, 9),
+            ]),
+        ])));
+}
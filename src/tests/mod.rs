@@ -5,3 +5,10 @@ pub mod introspection_tests;
 
 #[cfg(feature="nightly")]
 pub mod bench;
+mod printer_tests;
+mod diff_tests;
+mod complexity_tests;
+mod limits_tests;
+mod code_frame_tests;
+mod prepared_tests;
+mod persisted_tests;
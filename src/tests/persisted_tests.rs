@@ -0,0 +1,181 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use value::Value;
+use schema::model::RootNode;
+use tests::model::Database;
+use ::{GraphQLError, PreparedQuery, PersistedQueries, PersistedQueryStore, MemoryQueryStore, LruQueryStore, query_hash};
+
+const HERO_NAME: &'static str = "{ hero { name } }";
+const HERO_NAME_HASH: &'static str = "aae585680c3470e4947255eafbd1eafe87d1c3f129259cf15e404d1bb7f1e8f4";
+
+fn hero_name() -> Value {
+    Value::object(vec![
+        ("hero", Value::object(vec![
+            ("name", Value::string("R2-D2")),
+        ].into_iter().collect())),
+    ].into_iter().collect())
+}
+
+fn prepared(source: &str) -> Arc<PreparedQuery> {
+    let database = Database::new();
+    let schema = RootNode::new(&database, ());
+
+    Arc::new(PreparedQuery::new(source, &schema).expect("Invalid query"))
+}
+
+#[test]
+fn hash_is_hex_encoded_sha256() {
+    assert_eq!(query_hash(""), "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855");
+    assert_eq!(query_hash(HERO_NAME), HERO_NAME_HASH);
+}
+
+#[test]
+fn lru_store_evicts_least_recently_used() {
+    let store = LruQueryStore::new(2);
+    let query = prepared(HERO_NAME);
+
+    store.insert("a".to_owned(), query.clone());
+    store.insert("b".to_owned(), query.clone());
+    assert!(store.get("a").is_some());
+
+    store.insert("c".to_owned(), query.clone());
+
+    assert_eq!(store.len(), 2);
+    assert!(store.get("a").is_some());
+    assert!(store.get("b").is_none());
+    assert!(store.get("c").is_some());
+}
+
+#[test]
+fn lru_store_replaces_existing_ids() {
+    let store = LruQueryStore::new(2);
+
+    store.insert("a".to_owned(), prepared(HERO_NAME));
+    store.insert("a".to_owned(), prepared("{ hero { id } }"));
+    store.insert("b".to_owned(), prepared(HERO_NAME));
+
+    assert_eq!(store.len(), 2);
+    assert_eq!(
+        store.get("a").map(|q| q.document().clone()),
+        Some(prepared("{ hero { id } }").document().clone()));
+}
+
+#[test]
+fn memory_store_keeps_every_query() {
+    let store = MemoryQueryStore::new();
+    let query = prepared(HERO_NAME);
+
+    for i in 0..100 {
+        store.insert(i.to_string(), query.clone());
+    }
+
+    assert_eq!(store.len(), 100);
+    assert!(store.get("0").is_some());
+    assert!(store.get("99").is_some());
+}
+
+#[test]
+fn executed_queries_are_stored_by_hash() {
+    let database = Database::new();
+    let schema = RootNode::new(&database, ())
+        .with_persisted_queries(PersistedQueries::new(LruQueryStore::new(10)));
+
+    assert_eq!(
+        ::execute_persisted(HERO_NAME_HASH, None, &schema, &HashMap::new(), &database),
+        Err(GraphQLError::PersistedQueryNotFound));
+
+    assert_eq!(
+        ::execute(HERO_NAME, None, &schema, &HashMap::new(), &database),
+        Ok((hero_name(), vec![])));
+
+    assert_eq!(
+        ::execute_persisted(HERO_NAME_HASH, None, &schema, &HashMap::new(), &database),
+        Ok((hero_name(), vec![])));
+}
+
+#[test]
+fn invalid_queries_are_not_stored() {
+    let database = Database::new();
+    let schema = RootNode::new(&database, ())
+        .with_persisted_queries(PersistedQueries::new(LruQueryStore::new(10)));
+
+    match ::execute("{ hero { unknown } }", None, &schema, &HashMap::new(), &database) {
+        Err(GraphQLError::ValidationError(_)) => {}
+        result => panic!("Expected a validation error, got {:?}", result),
+    }
+
+    assert_eq!(
+        ::execute_persisted(&query_hash("{ hero { unknown } }"), None, &schema, &HashMap::new(), &database),
+        Err(GraphQLError::PersistedQueryNotFound));
+}
+
+#[test]
+fn queries_can_be_persisted_under_an_id() {
+    let database = Database::new();
+    let schema = RootNode::new(&database, ())
+        .with_persisted_queries(PersistedQueries::new(LruQueryStore::new(10)));
+
+    schema.persist_query("heroName", HERO_NAME).expect("Invalid query");
+
+    assert_eq!(
+        ::execute_persisted("heroName", None, &schema, &HashMap::new(), &database),
+        Ok((hero_name(), vec![])));
+
+    match schema.persist_query("unknown", "{ hero { unknown } }") {
+        Err(GraphQLError::ValidationError(_)) => {}
+        result => panic!("Expected a validation error, got {:?}", result),
+    }
+}
+
+#[test]
+fn allow_list_rejects_unknown_queries() {
+    let database = Database::new();
+    let schema = RootNode::new(&database, ())
+        .with_persisted_queries(PersistedQueries::allow_list(MemoryQueryStore::new()));
+
+    schema.persist_query(&query_hash(HERO_NAME), HERO_NAME).expect("Invalid query");
+
+    assert_eq!(
+        ::execute(HERO_NAME, None, &schema, &HashMap::new(), &database),
+        Ok((hero_name(), vec![])));
+    assert_eq!(
+        ::execute("{ hero { id } }", None, &schema, &HashMap::new(), &database),
+        Err(GraphQLError::QueryNotAllowed));
+    assert_eq!(
+        ::execute_persisted(&query_hash("{ hero { id } }"), None, &schema, &HashMap::new(), &database),
+        Err(GraphQLError::PersistedQueryNotFound));
+}
+
+#[test]
+fn persisted_queries_need_a_store() {
+    let database = Database::new();
+    let schema = RootNode::new(&database, ());
+
+    assert_eq!(
+        ::execute_persisted(HERO_NAME_HASH, None, &schema, &HashMap::new(), &database),
+        Err(GraphQLError::PersistedQueryNotSupported));
+    assert_eq!(
+        schema.persist_query("heroName", HERO_NAME),
+        Err(GraphQLError::PersistedQueryNotSupported));
+}
+
+#[test]
+fn stored_queries_are_bound_to_their_schema() {
+    let database = Database::new();
+    let schema = RootNode::new(&database, ())
+        .with_persisted_queries(PersistedQueries::new(MemoryQueryStore::new()));
+    let other_schema = RootNode::new(&database, ());
+
+    schema.persist_query("heroName", HERO_NAME).expect("Invalid query");
+    let query = schema.persisted_queries.as_ref()
+        .and_then(|persisted| persisted.get("heroName"))
+        .expect("Query was not stored");
+
+    assert_eq!(
+        query.execute(None, &schema, &HashMap::new(), &database),
+        Ok((hero_name(), vec![])));
+    assert_eq!(
+        query.execute(None, &other_schema, &HashMap::new(), &database),
+        Err(GraphQLError::SchemaMismatch));
+}
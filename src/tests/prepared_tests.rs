@@ -0,0 +1,141 @@
+use std::collections::HashMap;
+
+use ast::InputValue;
+use parser::SourcePosition;
+use value::Value;
+use schema::model::RootNode;
+use validation::{QueryComplexity, QueryLimits, RuleError};
+use tests::model::Database;
+use ::{GraphQLError, PreparedQuery};
+
+const HUMAN_NAME: &'static str = r#"
+    query Human($id: String!) { human(id: $id) { name } }
+"#;
+
+const NAMES: &'static str = r#"
+    query Hero { hero { name } }
+    query Luke { human(id: "1000") { name } }
+"#;
+
+fn name_of(field: &str, name: &str) -> Value {
+    Value::object(vec![
+        (field, Value::object(vec![
+            ("name", Value::string(name)),
+        ].into_iter().collect())),
+    ].into_iter().collect())
+}
+
+#[test]
+fn prepared_query_can_be_executed_many_times() {
+    let database = Database::new();
+    let schema = RootNode::new(&database, ());
+    let query = PreparedQuery::new(HUMAN_NAME, &schema).expect("Invalid query");
+
+    for &(id, name) in &[("1000", "Luke Skywalker"), ("1003", "Leia Organa")] {
+        let vars = vec![
+            ("id".to_owned(), InputValue::string(id)),
+        ].into_iter().collect();
+
+        assert_eq!(
+            query.execute(None, &schema, &vars, &database),
+            Ok((name_of("human", name), vec![])));
+    }
+}
+
+#[test]
+fn operation_is_selected_per_execution() {
+    let database = Database::new();
+    let schema = RootNode::new(&database, ());
+    let query = PreparedQuery::new(NAMES, &schema).expect("Invalid query");
+
+    assert_eq!(
+        query.execute(Some("Hero"), &schema, &HashMap::new(), &database),
+        Ok((name_of("hero", "R2-D2"), vec![])));
+    assert_eq!(
+        query.execute(Some("Luke"), &schema, &HashMap::new(), &database),
+        Ok((name_of("human", "Luke Skywalker"), vec![])));
+    assert_eq!(
+        query.execute(None, &schema, &HashMap::new(), &database),
+        Err(GraphQLError::MultipleOperationsProvided));
+    assert_eq!(
+        query.execute(Some("Droid"), &schema, &HashMap::new(), &database),
+        Err(GraphQLError::UnknownOperationName));
+}
+
+#[test]
+fn invalid_queries_are_rejected_when_prepared() {
+    let database = Database::new();
+    let schema = RootNode::new(&database, ());
+
+    assert_eq!(
+        PreparedQuery::new("{ hero { unknown } }", &schema),
+        Err(GraphQLError::ValidationError(vec![
+            RuleError::new(r#"Unknown field "unknown" on type "Character""#, &[
+                SourcePosition::new(9, 0, 9),
+            ]),
+        ])));
+    assert!(PreparedQuery::new("{ hero { name }", &schema).is_err());
+}
+
+#[test]
+fn limits_are_checked_when_prepared() {
+    let database = Database::new();
+    let schema = RootNode::new(&database, ())
+        .with_limits(QueryLimits::new().max_depth(1));
+
+    assert_eq!(
+        PreparedQuery::new("{ hero { name } }", &schema),
+        Err(GraphQLError::ValidationError(vec![
+            RuleError::new("Field is nested deeper than the maximum depth of 1", &[
+                SourcePosition::new(9, 0, 9),
+            ]),
+        ])));
+}
+
+#[test]
+fn variables_are_checked_when_executed() {
+    let database = Database::new();
+    let schema = RootNode::new(&database, ());
+    let query = PreparedQuery::new(HUMAN_NAME, &schema).expect("Invalid query");
+
+    match query.execute(None, &schema, &HashMap::new(), &database) {
+        Err(GraphQLError::ValidationError(errors)) => assert_eq!(errors.len(), 1),
+        result => panic!("Expected a variable error, got {:?}", result),
+    }
+}
+
+#[test]
+fn complexity_is_checked_when_executed() {
+    let database = Database::new();
+    let schema = RootNode::new(&database, ())
+        .with_complexity(QueryComplexity::new().max_cost(2));
+    let query = PreparedQuery::new(r#"
+        query Hero { hero { name } }
+        query Friends { hero { name friends { name } } }
+    "#, &schema).expect("Invalid query");
+
+    assert_eq!(
+        query.execute(Some("Hero"), &schema, &HashMap::new(), &database),
+        Ok((name_of("hero", "R2-D2"), vec![])));
+    assert_eq!(
+        query.execute(Some("Friends"), &schema, &HashMap::new(), &database),
+        Err(GraphQLError::QueryTooComplex { cost: 4, max_cost: 2 }));
+}
+
+#[test]
+fn prepared_queries_can_not_be_executed_with_another_schema() {
+    let database = Database::new();
+    let schema = RootNode::new(&database, ());
+    let other_schema = RootNode::new(&database, ());
+    let query = PreparedQuery::new(NAMES, &schema).expect("Invalid query");
+
+    assert_eq!(
+        query.execute(Some("Hero"), &other_schema, &HashMap::new(), &database),
+        Err(GraphQLError::SchemaMismatch));
+
+    let subscribed = match query.subscribe(Some("Hero"), &other_schema, &HashMap::new(), &database) {
+        Err(GraphQLError::SchemaMismatch) => false,
+        _ => true,
+    };
+    assert!(!subscribed);
+}
@@ -0,0 +1,177 @@
+use value::Value;
+use schema::model::RootNode;
+use tests::model::Database;
+
+#[test]
+fn test_star_wars_schema() {
+    let database = Database::new();
+    let schema = RootNode::new(&database, ());
+
+    assert_eq!(schema.as_schema_language(), r#"schema {
+  query: Query
+}
+
+"A character in the Star Wars Trilogy"
+interface Character {
+  "The id of the character"
+  id: String!
+  "The name of the character"
+  name: String
+  "The friends of the character"
+  friends: [Character!]!
+  "Which movies they appear in"
+  appearsIn: [Episode!]!
+}
+
+"A mechanical creature in the Star Wars universe."
+type Droid implements Character {
+  "The id of the droid"
+  id: String!
+  "The name of the droid"
+  name: String
+  "The friends of the droid"
+  friends: [Character!]!
+  "Which movies they appear in"
+  appearsIn: [Episode!]!
+  "The primary function of the droid"
+  primaryFunction: String
+}
+
+enum Episode {
+  NEW_HOPE
+  EMPIRE
+  JEDI
+}
+
+"A humanoid creature in the Star Wars universe."
+type Human implements Character {
+  "The id of the human"
+  id: String!
+  "The name of the human"
+  name: String
+  "The friends of the human"
+  friends: [Character!]!
+  "Which movies they appear in"
+  appearsIn: [Episode!]!
+  "The home planet of the human"
+  homePlanet: String
+}
+
+"The root query object of the schema"
+type Query {
+  human(
+    "id of the human"
+    id: String!
+  ): Human
+  droid(
+    "id of the droid"
+    id: String!
+  ): Droid
+  hero(
+    "If omitted, returns the hero of the whole saga. If provided, returns the hero of that particular episode"
+    episode: Episode
+  ): Character
+}
+"#);
+}
+
+enum Color { Red, Green }
+
+struct Timestamp(i64);
+
+struct Root;
+struct Mutation;
+
+enum Shape { Circle(Root) }
+
+graphql_enum!(Color {
+    Color::Red => "RED" as "The color red",
+    Color::Green => "GREEN" deprecated "Nobody likes \"green\"",
+});
+
+graphql_scalar!(Timestamp {
+    description: "Seconds since the epoch"
+
+    resolve(&self) -> Value {
+        Value::int(self.0)
+    }
+
+    from_input_value(v: &InputValue) -> Option<Timestamp> {
+        v.as_int_value().map(Timestamp)
+    }
+});
+
+graphql_input_object!(
+    description: "Where to paint"
+
+    struct Canvas {
+        width: i64,
+        height: Option<i64>,
+        color: Color,
+    }
+);
+
+graphql_union!(Shape: () |&self| {
+    instance_resolvers: |&_| {
+        &Root => match *self { Shape::Circle(ref r) => Some(r) },
+    }
+});
+
+graphql_object!(Root: () as "Root" |&self| {
+    description: "Multiple lines\nof description"
+
+    field paint(canvas: Canvas, color = (Color::Red): Color, times = 1: i64) -> Option<Shape> {
+        None
+    }
+
+    field deprecated "Use paint" draw() -> i64 { 0 }
+
+    field now() -> Option<Timestamp> { None }
+});
+
+graphql_object!(Mutation: () |&self| {
+    field clear() -> bool { true }
+});
+
+#[test]
+fn test_all_type_kinds() {
+    let schema = RootNode::new(Root, Mutation);
+
+    assert_eq!(schema.as_schema_language(), r#"schema {
+  query: Root
+  mutation: Mutation
+}
+
+"Where to paint"
+input Canvas {
+  width: Int!
+  height: Int
+  color: Color!
+}
+
+enum Color {
+  "The color red"
+  RED
+  GREEN @deprecated(reason: "Nobody likes \"green\"")
+}
+
+type Mutation {
+  clear: Boolean!
+}
+
+"""
+Multiple lines
+of description
+"""
+type Root {
+  paint(canvas: Canvas!, color: Color = RED, times: Int = 1): Shape
+  draw: Int! @deprecated(reason: "Use paint")
+  now: Timestamp
+}
+
+union Shape = Root
+
+"Seconds since the epoch"
+scalar Timestamp
+"#);
+}
@@ -331,8 +331,8 @@ fn test_query_inline_fragments_droid() {
         ::execute(doc, None, &schema, &HashMap::new(), &database),
         Ok((Value::object(vec![
                 ("hero", Value::object(vec![
-                    ("__typename", Value::string("Droid")),
                     ("name", Value::string("R2-D2")),
+                    ("__typename", Value::string("Droid")),
                     ("primaryFunction", Value::string("Astromech")),
                 ].into_iter().collect())),
             ].into_iter().collect()),
@@ -356,8 +356,8 @@ fn test_query_inline_fragments_human() {
         ::execute(doc, None, &schema, &HashMap::new(), &database),
         Ok((Value::object(vec![
                 ("hero", Value::object(vec![
-                    ("__typename", Value::string("Human")),
                     ("name", Value::string("Luke Skywalker")),
+                    ("__typename", Value::string("Human")),
                 ].into_iter().collect())),
             ].into_iter().collect()),
             vec![])));
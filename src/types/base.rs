@@ -1,11 +1,10 @@
 use std::collections::HashMap;
-use std::collections::hash_map::Entry;
 
-use ast::{InputValue, Selection, Directive, FromInputValue};
-use value::Value;
+use ast::{InputValue, Selection, Directive, FromInputValue, Type};
+use value::{Value, Object};
 
 use schema::meta::{Argument, MetaType};
-use executor::{Executor, Registry, ExecutionResult};
+use executor::{Executor, Registry, ExecutionResult, FieldResult};
 use parser::Spanning;
 
 /// GraphQL type kind
@@ -66,6 +65,7 @@ pub enum TypeKind {
 /// Field argument container
 pub struct Arguments {
     args: Option<HashMap<String, InputValue>>,
+    arg_types: HashMap<String, Type>,
 }
 
 impl Arguments {
@@ -87,8 +87,15 @@ impl Arguments {
             }
         }
 
+        let arg_types = meta_args.as_ref()
+            .map(|meta_args| meta_args.iter()
+                .map(|arg| (arg.name.clone(), arg.arg_type.clone()))
+                .collect())
+            .unwrap_or_else(HashMap::new);
+
         Arguments {
-            args: args
+            args: args,
+            arg_types: arg_types,
         }
     }
 
@@ -98,16 +105,35 @@ impl Arguments {
     /// the `InputValue` will be converted into the type `T`.
     ///
     /// Returns `Some` if the argument is present _and_ type conversion
-    /// succeeeds.
+    /// succeeeds. A value that fails to convert is indistinguishable from a
+    /// missing argument; use `try_get` to report the conversion error.
+    #[deprecated(since = "0.6.0", note = "use `Arguments::try_get`, which reports conversion errors")]
     pub fn get<T>(&self, key: &str) -> Option<T> where T: FromInputValue {
         match self.args {
             Some(ref args) => match args.get(key) {
-                Some(v) => Some(v.convert().unwrap()),
+                Some(v) => v.convert(),
                 None => None,
             },
             None => None,
         }
     }
+
+    /// Get and convert an argument, describing why the conversion failed
+    ///
+    /// A missing argument is converted from `null`, so optional arguments
+    /// come back as `None`. If the conversion fails, the error names the
+    /// argument, its expected type, and the problem with the value.
+    pub fn try_get<T>(&self, key: &str) -> FieldResult<T> where T: FromInputValue {
+        let null = InputValue::null();
+        let value = self.args.as_ref().and_then(|args| args.get(key)).unwrap_or(&null);
+
+        value.try_convert().map_err(|e| match self.arg_types.get(key) {
+            Some(arg_type) => format!(
+                r#"Invalid value for argument "{}", expected type "{}": {}"#, key, arg_type, e),
+            None => format!(
+                r#"Invalid value for argument "{}": {}"#, key, e),
+        })
+    }
 }
 
 /**
@@ -258,13 +284,18 @@ pub trait GraphQLType<CtxT>: Sized {
     /// For objects, all fields in the selection set should be resolved.
     ///
     /// The default implementation uses `resolve_field` to resolve all fields,
-    /// including those through fragment expansion, for object types. For
-    /// non-object types, this method panics.
+    /// including those through fragment expansion, for object types. If a
+    /// non-null field fails to resolve, the whole object resolves to `null`.
+    /// For non-object types, this method panics.
     fn resolve(&self, selection_set: Option<Vec<Selection>>, executor: &mut Executor<CtxT>) -> Value {
         if let Some(selection_set) = selection_set {
-            let mut result = HashMap::new();
-            resolve_selection_set_into(self, selection_set, executor, &mut result);
-            Value::object(result)
+            let mut result = Object::new();
+            if resolve_selection_set_into(self, selection_set, executor, &mut result) {
+                Value::object(result)
+            }
+            else {
+                Value::null()
+            }
         }
         else {
             panic!("resolve() must be implemented by non-object output types");
@@ -272,13 +303,68 @@ pub trait GraphQLType<CtxT>: Sized {
     }
 }
 
+/**
+Trait implemented by the subscription root type of a schema
+
+The subscription root is exposed as a regular object type, usually through
+`graphql_object!`. In addition, it creates the _source event stream_ for each
+of its fields: every value yielded by `subscribe` is resolved against the
+selection set of the subscription operation, producing one response per event.
+
+## Example
+
+```rust
+# #[macro_use] extern crate juniper;
+use juniper::{Arguments, FieldResult, GraphQLSubscriptionType};
+
+struct Database { messages: Vec<String> }
+struct Subscription { message: String }
+
+graphql_object!(Subscription: Database |&self| {
+    field message_added() -> &String {
+        &self.message
+    }
+});
+
+impl GraphQLSubscriptionType<Database> for Subscription {
+    fn subscribe<'a>(&'a self, field_name: &str, _: &Arguments, context: &'a Database)
+        -> FieldResult<Box<Iterator<Item = Subscription> + 'a>>
+    {
+        match field_name {
+            "messageAdded" => Ok(Box::new(
+                context.messages.iter().map(|m| Subscription { message: m.clone() }))),
+            _ => Err(format!("Can not subscribe to {}", field_name)),
+        }
+    }
+}
+
+# fn main() { }
+```
+
+*/
+pub trait GraphQLSubscriptionType<CtxT>: GraphQLType<CtxT> {
+    /// Create the event stream for a single subscription root field.
+    ///
+    /// The arguments object contain all specified arguments, with default
+    /// values substituted for the ones not provided by the query.
+    fn subscribe<'a>(&'a self, field_name: &str, arguments: &Arguments, context: &'a CtxT)
+        -> FieldResult<Box<Iterator<Item = Self> + 'a>>;
+}
+
+/// Resolve all fields in the selection set into `result`
+///
+/// Returns `false` if a non-null field resolved to `null`, in which case the
+/// enclosing object must be replaced by `null` as well.
 fn resolve_selection_set_into<T, CtxT>(
     instance: &T,
     selection_set: Vec<Selection>,
     executor: &mut Executor<CtxT>,
-    result: &mut HashMap<String, Value>)
+    result: &mut Object)
+    -> bool
     where T: GraphQLType<CtxT>
 {
+    let mut is_valid = true;
+
     let meta_type = executor.schema()
         .concrete_type_by_name(T::name().expect("Resolving named type's selection set"))
         .expect("Type not found in schema");
@@ -312,9 +398,12 @@ fn resolve_selection_set_into<T, CtxT>(
 
                 let mut sub_exec = executor.sub_executor(
                     Some(response_name.clone()),
+                    Some(&meta_field.field_type),
                     start_pos.clone(),
                     f.selection_set);
 
+                let deferred_start = sub_exec.deferred_count();
+
                 let field_result = instance.resolve_field(
                     &f.name.item,
                     &Arguments::new(
@@ -324,12 +413,19 @@ fn resolve_selection_set_into<T, CtxT>(
                         &meta_field.arguments),
                     &mut sub_exec);
 
-                match field_result {
-                    Ok(v) => merge_key_into(result, response_name.clone(), v),
+                let value = match field_result {
+                    Ok(v) => v,
                     Err(e) => {
                         sub_exec.push_error(e, start_pos);
-                        result.insert(response_name.clone(), Value::null());
+                        Value::null()
                     }
+                };
+
+                let placeholders = sub_exec.placeholders_since(deferred_start);
+
+                match propagate_nulls(&meta_field.field_type, value, &placeholders) {
+                    Some(v) => merge_key_into(result, response_name.clone(), v),
+                    None => is_valid = false,
                 }
             },
             Selection::FragmentSpread(Spanning { item: spread, .. }) => {
@@ -345,8 +441,10 @@ fn resolve_selection_set_into<T, CtxT>(
                 let fragment = &executor.fragment_by_name(&spread.name.item)
                     .expect("Fragment could not be found");
 
-                resolve_selection_set_into(
-                    instance, fragment.selection_set.clone(), executor, result);
+                if !resolve_selection_set_into(
+                        instance, fragment.selection_set.clone(), executor, result) {
+                    is_valid = false;
+                }
             },
             Selection::InlineFragment(Spanning { item: fragment, start: start_pos, .. }) => {
                 if is_excluded(
@@ -359,38 +457,113 @@ fn resolve_selection_set_into<T, CtxT>(
                 }
 
                 let mut sub_exec = executor.sub_executor(
+                    None,
                     None,
                     start_pos.clone(),
                     Some(fragment.selection_set.clone()));
 
-                if let Some(type_condition) = fragment.type_condition {
+                let applies_to_self = match fragment.type_condition {
+                    Some(ref type_condition) => T::name() == Some(&type_condition.item as &str),
+                    None => true,
+                };
+
+                if applies_to_self {
+                    if !resolve_selection_set_into(
+                            instance,
+                            fragment.selection_set.clone(),
+                            &mut sub_exec,
+                            result) {
+                        is_valid = false;
+                    }
+                }
+                else if let Some(type_condition) = fragment.type_condition {
                     let sub_result = instance.resolve_into_type(
                         &type_condition.item,
                         Some(fragment.selection_set.clone()),
                         &mut sub_exec);
 
-                    if let Ok(Value::Object(mut hash_map)) = sub_result {
-                        for (k, v) in hash_map.drain() {
-                            result.insert(k, v);
+                    match sub_result {
+                        Ok(Value::Object(object)) => {
+                            for (k, v) in object {
+                                merge_key_into(result, k, v);
+                            }
                         }
-                    }
-                    else if let Err(e) = sub_result {
-                         sub_exec.push_error(e, start_pos);
+                        // Interfaces and unions resolve to null for type
+                        // conditions that do not apply to this instance
+                        Ok(Value::Null) =>
+                            if instance.concrete_type_name(sub_exec.context()) == type_condition.item {
+                                is_valid = false;
+                            },
+                        Ok(_) => (),
+                        Err(e) => sub_exec.push_error(e, start_pos),
                     }
                 }
-                else {
-                    resolve_selection_set_into(
-                        instance,
-                        fragment.selection_set.clone(),
-                        &mut sub_exec,
-                        result);
-                }
             },
         }
     }
+
+    is_valid
 }
 
-fn is_excluded(directives: &Option<Vec<Directive>>, vars: &HashMap<String, InputValue>) -> bool {
+/// Apply the null propagation rules for a value of the given type
+///
+/// Returns `None` if the value is `null` but the type is non-null, meaning
+/// the `null` has to propagate to the parent field. `null` items in lists of
+/// non-null types turn the whole list into `null`.
+///
+/// `placeholders` holds the list indices of values that are still deferred;
+/// their `null`s are kept until the deferred values are resolved.
+#[doc(hidden)]
+pub fn propagate_nulls(field_type: &Type, value: Value, placeholders: &[Vec<usize>]) -> Option<Value> {
+    propagate_nulls_at(field_type, value, placeholders, &mut Vec::new())
+}
+
+fn propagate_nulls_at(
+    field_type: &Type,
+    value: Value,
+    placeholders: &[Vec<usize>],
+    position: &mut Vec<usize>,
+)
+    -> Option<Value>
+{
+    match (field_type, value) {
+        (&Type::NonNullNamed(_), Value::Null) |
+        (&Type::NonNullList(_), Value::Null) =>
+            if placeholders.contains(position) { Some(Value::Null) } else { None },
+        (&Type::List(ref inner), Value::List(items)) =>
+            Some(propagate_list_nulls(inner, items, placeholders, position).unwrap_or(Value::Null)),
+        (&Type::NonNullList(ref inner), Value::List(items)) =>
+            propagate_list_nulls(inner, items, placeholders, position),
+        (_, value) => Some(value),
+    }
+}
+
+fn propagate_list_nulls(
+    item_type: &Type,
+    items: Vec<Value>,
+    placeholders: &[Vec<usize>],
+    position: &mut Vec<usize>,
+)
+    -> Option<Value>
+{
+    let mut values = Vec::with_capacity(items.len());
+
+    for (i, item) in items.into_iter().enumerate() {
+        position.push(i);
+        let value = propagate_nulls_at(item_type, item, placeholders, position);
+        position.pop();
+
+        match value {
+            Some(v) => values.push(v),
+            None => return None,
+        }
+    }
+
+    Some(Value::list(values))
+}
+
+#[doc(hidden)]
+pub fn is_excluded(directives: &Option<Vec<Directive>>, vars: &HashMap<String, InputValue>) -> bool {
     if let Some(ref directives) = *directives {
         for directive in directives {
             let condition: bool = directive.arguments.iter()
@@ -413,32 +586,43 @@ fn is_excluded(directives: &Option<Vec<Directive>>, vars: &HashMap<String, Input
     }
 }
 
-fn merge_key_into(
-    result: &mut HashMap<String, Value>,
+#[doc(hidden)]
+pub fn merge_key_into(
+    result: &mut Object,
     response_name: String,
     value: Value,
 ) {
-    match result.entry(response_name) {
-        Entry::Occupied(mut e) => {
-            println!("Merging object at '{}'", e.key());
-            match (e.get_mut().as_mut_object_value(), value) {
-                (Some(dest_obj), Value::Object(src_obj)) => {
-                    merge_maps(dest_obj, src_obj);
-                },
-                _ => {
-                    println!("Not merging object/object - this is bad :(");
-                }
+    if let Some(existing) = result.get_mut(&response_name) {
+        merge_values(existing, value);
+        return;
+    }
+
+    result.insert(response_name, value);
+}
+
+/// Merge the value of a field that is selected more than once into the
+/// value of an earlier selection
+///
+/// Validation guarantees that such fields resolve to the same value, so only
+/// the objects in them, which can have different sub-selections, need to be
+/// merged. A `null` is kept, whether it is the value of the field or the
+/// placeholder of a deferred value.
+#[doc(hidden)]
+pub fn merge_values(dest: &mut Value, src: Value) {
+    match (dest, src) {
+        (&mut Value::Object(ref mut dest_obj), Value::Object(src_obj)) => merge_maps(dest_obj, src_obj),
+        (&mut Value::List(ref mut dest_items), Value::List(src_items)) => {
+            for (dest_item, src_item) in dest_items.iter_mut().zip(src_items) {
+                merge_values(dest_item, src_item);
             }
-        },
-        Entry::Vacant(e) => {
-            e.insert(value);
-        },
+        }
+        _ => (),
     }
 }
 
 fn merge_maps(
-    dest: &mut HashMap<String, Value>,
-    src: HashMap<String, Value>,
+    dest: &mut Object,
+    src: Object,
 ) {
     for (key, value) in src {
         if dest.contains_key(&key) {
@@ -2,7 +2,7 @@ use ast::{InputValue, ToInputValue, FromInputValue, Selection};
 use value::Value;
 use schema::meta::MetaType;
 
-use executor::{Executor, Registry, IntoFieldResult, FieldResult};
+use executor::{Executor, Registry, IntoFieldResult, FieldError};
 use types::base::{GraphQLType};
 
 impl<T, CtxT> GraphQLType<CtxT> for Option<T> where T: GraphQLType<CtxT> {
@@ -32,6 +32,13 @@ impl<T> FromInputValue for Option<T> where T: FromInputValue {
             }
         }
     }
+
+    fn try_from_input(v: &InputValue) -> Result<Option<T>, String> {
+        match v {
+            &InputValue::Null => Ok(None),
+            v => v.try_convert().map(Some),
+        }
+    }
 }
 
 impl<T> ToInputValue for Option<T> where T: ToInputValue {
@@ -44,7 +51,7 @@ impl<T> ToInputValue for Option<T> where T: ToInputValue {
 }
 
 impl<T> IntoFieldResult<Option<T>> for Option<T> {
-    fn into(self) -> FieldResult<Option<T>> {
+    fn into(self) -> Result<Option<T>, FieldError> {
         Ok(self)
     }
 }
@@ -61,7 +68,9 @@ impl<T, CtxT> GraphQLType<CtxT> for Vec<T> where T: GraphQLType<CtxT> {
 
     fn resolve(&self, _: Option<Vec<Selection>>, executor: &mut Executor<CtxT>) -> Value {
         Value::list(
-            self.iter().map(|e| executor.resolve_into_value(e)).collect()
+            self.iter().enumerate()
+                .map(|(i, e)| executor.list_item_sub_executor(i).resolve_into_value(e))
+                .collect()
         )
     }
 }
@@ -87,6 +96,24 @@ impl<T> FromInputValue for Vec<T> where T: FromInputValue {
                 }
         }
     }
+
+    fn try_from_input(v: &InputValue) -> Result<Vec<T>, String> {
+        match *v {
+            InputValue::List(ref ls) => {
+                let mut v = Vec::with_capacity(ls.len());
+
+                for (i, item) in ls.iter().enumerate() {
+                    match item.item.try_convert() {
+                        Ok(x) => v.push(x),
+                        Err(e) => return Err(format!("In element #{}: {}", i, e)),
+                    }
+                }
+
+                Ok(v)
+            },
+            ref other => other.try_convert().map(|e| vec![ e ]),
+        }
+    }
 }
 
 impl<T> ToInputValue for Vec<T> where T: ToInputValue {
@@ -96,7 +123,7 @@ impl<T> ToInputValue for Vec<T> where T: ToInputValue {
 }
 
 impl<T> IntoFieldResult<Vec<T>> for Vec<T> {
-    fn into(self) -> FieldResult<Vec<T>> {
+    fn into(self) -> Result<Vec<T>, FieldError> {
         Ok(self)
     }
 }
@@ -113,7 +140,9 @@ impl<'a, T, CtxT> GraphQLType<CtxT> for &'a [T] where T: GraphQLType<CtxT> {
 
     fn resolve(&self, _: Option<Vec<Selection>>, executor: &mut Executor<CtxT>) -> Value {
         Value::list(
-            self.iter().map(|e| executor.resolve_into_value(e)).collect()
+            self.iter().enumerate()
+                .map(|(i, e)| executor.list_item_sub_executor(i).resolve_into_value(e))
+                .collect()
         )
     }
 }
@@ -125,7 +154,7 @@ impl<'a, T> ToInputValue for &'a [T] where T: ToInputValue {
 }
 
 impl<'a, T> IntoFieldResult<&'a [T]> for &'a [T] {
-    fn into(self) -> FieldResult<&'a [T]> {
+    fn into(self) -> Result<&'a [T], FieldError> {
         Ok(self)
     }
 }
@@ -0,0 +1,104 @@
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use futures::Future;
+
+use ast::Selection;
+use value::Value;
+use schema::meta::MetaType;
+
+use executor::{Executor, Registry, DeferredResolve, FieldError, IntoFieldResult};
+use types::base::GraphQLType;
+
+/// A field value that is computed asynchronously
+///
+/// Return this from a field to resolve it with a future. When the query is
+/// run with `execute_async`, the futures of all fields on the same level are
+/// waited for concurrently. The synchronous `execute` blocks on each future
+/// in turn.
+///
+/// A `FieldFuture<T>` has the same type in the schema as `T`. If the future
+/// fails, the error is reported and the field resolves to `null`; use
+/// `FieldFuture<Option<T>>` for fields that may fail without nulling their
+/// parent.
+pub struct FieldFuture<T> {
+    future: RefCell<Option<Box<Future<Item = T, Error = FieldError>>>>,
+}
+
+struct DeferredFuture<T> {
+    future: Option<Box<Future<Item = T, Error = FieldError>>>,
+    result: Rc<RefCell<Option<Result<T, FieldError>>>>,
+}
+
+impl<T> FieldFuture<T> {
+    /// Resolve the field with the result of a future
+    pub fn new<F>(future: F) -> FieldFuture<T>
+        where F: Future<Item = T, Error = FieldError> + 'static
+    {
+        FieldFuture {
+            future: RefCell::new(Some(Box::new(future))),
+        }
+    }
+}
+
+impl<T, CtxT> GraphQLType<CtxT> for FieldFuture<T>
+    where T: GraphQLType<CtxT> + 'static,
+{
+    fn name() -> Option<&'static str> {
+        T::name()
+    }
+
+    fn meta(registry: &mut Registry<CtxT>) -> MetaType {
+        T::meta(registry)
+    }
+
+    fn resolve(&self, _: Option<Vec<Selection>>, executor: &mut Executor<CtxT>) -> Value {
+        match self.future.borrow_mut().take() {
+            Some(future) => executor.defer(Box::new(DeferredFuture {
+                future: Some(future),
+                result: Rc::new(RefCell::new(None)),
+            })),
+            None => Value::null(),
+        }
+    }
+}
+
+impl<T> IntoFieldResult<FieldFuture<T>> for FieldFuture<T> {
+    fn into(self) -> Result<FieldFuture<T>, FieldError> {
+        Ok(self)
+    }
+}
+
+impl<T, CtxT> DeferredResolve<CtxT> for DeferredFuture<T>
+    where T: GraphQLType<CtxT> + 'static,
+{
+    fn take_future(&mut self) -> Option<Box<Future<Item = (), Error = ()>>> {
+        let result = self.result.clone();
+
+        self.future.take().map(|future| -> Box<Future<Item = (), Error = ()>> {
+            Box::new(future.then(move |r| {
+                *result.borrow_mut() = Some(r);
+                Ok(())
+            }))
+        })
+    }
+
+    fn resolve(self: Box<Self>, executor: &mut Executor<CtxT>) -> Value {
+        let result = match self.future {
+            Some(future) => future.wait(),
+            None => match self.result.borrow_mut().take() {
+                Some(result) => result,
+                None => return Value::null(),
+            },
+        };
+
+        match result {
+            Ok(value) => executor.resolve_into_value(&value),
+            Err(e) => {
+                let location = executor.location().clone();
+                executor.push_error(e, location);
+                Value::null()
+            }
+        }
+    }
+}
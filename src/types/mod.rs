@@ -3,3 +3,4 @@ pub mod scalars;
 pub mod pointers;
 pub mod containers;
 pub mod utilities;
+pub mod future;
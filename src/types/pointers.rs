@@ -2,7 +2,7 @@ use ast::{Selection, InputValue, ToInputValue, FromInputValue};
 use value::Value;
 
 use schema::meta::MetaType;
-use executor::{Executor, Registry, ExecutionResult, IntoFieldResult, FieldResult};
+use executor::{Executor, Registry, ExecutionResult, IntoFieldResult, FieldError};
 use types::base::{Arguments, GraphQLType};
 
 impl<T, CtxT> GraphQLType<CtxT> for Box<T> where T: GraphQLType<CtxT> {
@@ -35,6 +35,10 @@ impl<T> FromInputValue for Box<T> where T: FromInputValue {
             None => None,
         }
     }
+
+    fn try_from_input(v: &InputValue) -> Result<Box<T>, String> {
+        <T as FromInputValue>::try_from_input(v).map(Box::new)
+    }
 }
 
 impl<T> ToInputValue for Box<T> where T: ToInputValue {
@@ -44,7 +48,7 @@ impl<T> ToInputValue for Box<T> where T: ToInputValue {
 }
 
 impl<T> IntoFieldResult<Box<T>> for Box<T> {
-    fn into(self) -> FieldResult<Box<T>> {
+    fn into(self) -> Result<Box<T>, FieldError> {
         Ok(self)
     }
 }
@@ -79,7 +83,7 @@ impl<'a, T> ToInputValue for &'a T where T: ToInputValue {
 }
 
 impl<'a, T> IntoFieldResult<&'a T> for &'a T {
-    fn into(self) -> FieldResult<&'a T> {
+    fn into(self) -> Result<&'a T, FieldError> {
         Ok(self)
     }
 }
@@ -3,8 +3,8 @@ use value::Value;
 
 use schema::meta::MetaType;
 
-use executor::{Executor, Registry, FieldResult, IntoFieldResult};
-use types::base::GraphQLType;
+use executor::{Executor, Registry, FieldError, FieldResult, IntoFieldResult};
+use types::base::{Arguments, GraphQLType, GraphQLSubscriptionType};
 
 /// An ID as defined by the GraphQL specification
 ///
@@ -61,7 +61,7 @@ impl<'a> ToInputValue for &'a str {
 }
 
 impl<'a> IntoFieldResult<&'a str> for &'a str {
-    fn into(self) -> FieldResult<&'a str> {
+    fn into(self) -> Result<&'a str, FieldError> {
         Ok(self)
     }
 }
@@ -121,6 +121,14 @@ impl<CtxT> GraphQLType<CtxT> for () {
     }
 }
 
+impl<CtxT> GraphQLSubscriptionType<CtxT> for () {
+    fn subscribe<'a>(&'a self, _: &str, _: &Arguments, _: &'a CtxT)
+        -> FieldResult<Box<Iterator<Item = ()> + 'a>>
+    {
+        Err("Schema is not configured for subscriptions".to_owned())
+    }
+}
+
 impl FromInputValue for () {
     fn from(_: &InputValue) -> Option<()> {
         None
@@ -128,7 +136,7 @@ impl FromInputValue for () {
 }
 
 impl IntoFieldResult<()> for () {
-    fn into(self) -> FieldResult<()> {
+    fn into(self) -> Result<(), FieldError> {
         Ok(self)
     }
 }
@@ -1,4 +1,3 @@
-use std::collections::HashSet;
 use ast::InputValue;
 use schema::model::{SchemaType, TypeType};
 use schema::meta::{MetaType, InputObjectMeta};
@@ -37,12 +36,7 @@ pub fn is_valid_literal_value(schema: &SchemaType, arg_type: &TypeType, arg_valu
                 InputValue::Variable(_) => true,
                 InputValue::Object(ref obj) => {
                     if let &MetaType::InputObject(InputObjectMeta { ref input_fields, .. }) = t {
-                        let mut remaining_required_fields = input_fields.iter()
-                            .filter_map(|f| if f.arg_type.is_non_null() { Some(&f.name) } else { None })
-                            .collect::<HashSet<_>>();
-
-                        let all_types_ok = obj.iter().all(|&(ref key, ref value)| {
-                            remaining_required_fields.remove(&key.item);
+                        obj.iter().all(|&(ref key, ref value)| {
                             if let Some(ref arg_type) = input_fields.iter()
                                 .filter(|f| f.name == key.item)
                                 .map(|f| schema.make_type(&f.arg_type))
@@ -53,9 +47,7 @@ pub fn is_valid_literal_value(schema: &SchemaType, arg_type: &TypeType, arg_valu
                             else {
                                 false
                             }
-                        });
-
-                        all_types_ok && remaining_required_fields.is_empty()
+                        })
                     }
                     else {
                         false
@@ -0,0 +1,320 @@
+use std::collections::HashMap;
+
+use ast::{Document, Definition, Operation, OperationType, Fragment, Field, Selection, Directive, InputValue};
+use parser::Spanning;
+use schema::meta::Field as MetaField;
+use schema::model::SchemaType;
+
+/// Static cost analysis of queries
+///
+/// The cost of a query is computed from the document alone, before any
+/// resolver runs. Every field costs the default cost, unless a cost is set
+/// for it with `field_cost`. The cost of a field's selection set is added to
+/// the field's own cost, and the sum is multiplied by the values of any
+/// multiplier arguments the field is given, or their defaults if omitted:
+///
+/// ```rust
+/// use juniper::validation::QueryComplexity;
+///
+/// let complexity = QueryComplexity::new()
+///     .field_cost("Character", "friends", 5)
+///     .multiplier_argument("first")
+///     .max_cost(1000);
+/// ```
+///
+/// Attach it to a schema with `RootNode::with_complexity` to reject queries
+/// over the maximum cost when they are executed.
+pub struct QueryComplexity {
+    default_cost: usize,
+    field_costs: HashMap<(String, String), usize>,
+    multiplier_arguments: Vec<String>,
+    max_cost: Option<usize>,
+}
+
+struct CostContext<'a> {
+    complexity: &'a QueryComplexity,
+    schema: &'a SchemaType,
+    fragments: HashMap<&'a str, &'a Fragment>,
+    variables: HashMap<&'a str, InputValue>,
+}
+
+impl QueryComplexity {
+    /// Create an analysis where every field costs 1 and there is no maximum
+    pub fn new() -> QueryComplexity {
+        QueryComplexity {
+            default_cost: 1,
+            field_costs: HashMap::new(),
+            multiplier_arguments: Vec::new(),
+            max_cost: None,
+        }
+    }
+
+    /// Set the cost of fields without a cost of their own
+    pub fn default_cost(mut self, cost: usize) -> QueryComplexity {
+        self.default_cost = cost;
+        self
+    }
+
+    /// Set the cost of a single field of a type
+    pub fn field_cost(mut self, type_name: &str, field_name: &str, cost: usize) -> QueryComplexity {
+        self.field_costs.insert((type_name.to_owned(), field_name.to_owned()), cost);
+        self
+    }
+
+    /// Multiply the cost of fields by the value of this integer argument
+    ///
+    /// Negative values count as zero, and fields that are not given the
+    /// argument are not multiplied.
+    pub fn multiplier_argument(mut self, name: &str) -> QueryComplexity {
+        self.multiplier_arguments.push(name.to_owned());
+        self
+    }
+
+    /// Reject queries that cost more than this
+    pub fn max_cost(mut self, max_cost: usize) -> QueryComplexity {
+        self.max_cost = Some(max_cost);
+        self
+    }
+
+    /// The maximum cost of a query, if any
+    pub fn max(&self) -> Option<usize> {
+        self.max_cost
+    }
+
+    /// Compute the cost of an operation in a validated document
+    ///
+    /// The operation is selected like during execution. If there is no such
+    /// operation, the cost is zero. Variables missing from `variables` take
+    /// their default values.
+    pub fn cost(
+        &self,
+        document: &Document,
+        operation_name: Option<&str>,
+        schema: &SchemaType,
+        variables: &HashMap<String, InputValue>,
+    )
+        -> usize
+    {
+        let mut operation = None;
+        let mut fragments = HashMap::new();
+
+        for def in document {
+            match *def {
+                Definition::Operation(ref op) => {
+                    let matches = match operation_name {
+                        Some(name) => op.item.name.as_ref().map(|n| n.item == name) == Some(true),
+                        None => operation.is_none(),
+                    };
+
+                    if matches {
+                        operation = Some(&op.item);
+                    }
+                }
+                Definition::Fragment(ref f) => {
+                    fragments.insert(&f.item.name.item as &str, &f.item);
+                }
+                _ => {}
+            }
+        }
+
+        let operation = match operation {
+            Some(op) => op,
+            None => return 0,
+        };
+
+        let root_type = match root_type_name(operation, schema) {
+            Some(name) => name,
+            None => return 0,
+        };
+
+        let ctx = CostContext {
+            complexity: self,
+            schema: schema,
+            fragments: fragments,
+            variables: operation_variables(operation, variables),
+        };
+
+        ctx.selection_set_cost(&operation.selection_set, &root_type, &mut Vec::new())
+    }
+
+    fn field_cost_of(&self, type_name: &str, field_name: &str) -> usize {
+        self.field_costs.get(&(type_name.to_owned(), field_name.to_owned()))
+            .cloned()
+            .unwrap_or(self.default_cost)
+    }
+}
+
+impl<'a> CostContext<'a> {
+    fn selection_set_cost(
+        &self,
+        selection_set: &'a [Selection],
+        type_name: &str,
+        visited_fragments: &mut Vec<&'a str>,
+    )
+        -> usize
+    {
+        let mut cost: usize = 0;
+
+        for selection in selection_set {
+            let selection_cost = match *selection {
+                Selection::Field(ref field) => {
+                    if self.is_skipped(&field.item.directives) {
+                        continue;
+                    }
+
+                    self.field_cost(&field.item, type_name, visited_fragments)
+                }
+                Selection::FragmentSpread(ref spread) => {
+                    let name = &spread.item.name.item as &str;
+
+                    if self.is_skipped(&spread.item.directives) || visited_fragments.contains(&name) {
+                        continue;
+                    }
+
+                    match self.fragments.get(name) {
+                        Some(fragment) => {
+                            visited_fragments.push(name);
+                            let cost = self.selection_set_cost(
+                                &fragment.selection_set, &fragment.type_condition.item, visited_fragments);
+                            visited_fragments.pop();
+                            cost
+                        }
+                        None => 0,
+                    }
+                }
+                Selection::InlineFragment(ref fragment) => {
+                    if self.is_skipped(&fragment.item.directives) {
+                        continue;
+                    }
+
+                    let type_condition = fragment.item.type_condition.as_ref()
+                        .map_or(type_name, |t| &t.item as &str);
+
+                    self.selection_set_cost(&fragment.item.selection_set, type_condition, visited_fragments)
+                }
+            };
+
+            cost = cost.saturating_add(selection_cost);
+        }
+
+        cost
+    }
+
+    fn field_cost(
+        &self,
+        field: &'a Field,
+        type_name: &str,
+        visited_fragments: &mut Vec<&'a str>,
+    )
+        -> usize
+    {
+        let name = &field.name.item as &str;
+
+        if name == "__typename" {
+            return 0;
+        }
+
+        let meta_field = match self.schema.concrete_type_by_name(type_name).and_then(|t| t.field_by_name(name)) {
+            Some(f) => f,
+            None => return 0,
+        };
+
+        let children_cost = match field.selection_set {
+            Some(ref selection_set) => self.selection_set_cost(
+                selection_set, meta_field.field_type.innermost_name(), visited_fragments),
+            None => 0,
+        };
+
+        let cost = self.complexity.field_cost_of(type_name, name).saturating_add(children_cost);
+
+        cost.saturating_mul(self.multiplier(field, meta_field))
+    }
+
+    /// The product of the multiplier arguments of a field
+    ///
+    /// Arguments that are not given, or given a variable that is not
+    /// provided, fall back to their default value in the schema.
+    fn multiplier(&self, field: &Field, meta_field: &MetaField) -> usize {
+        let mut multiplier: usize = 1;
+
+        for argument in meta_field.arguments.iter().flat_map(|args| args.iter()) {
+            if !self.complexity.multiplier_arguments.contains(&argument.name) {
+                continue;
+            }
+
+            let value = match field.arguments.as_ref().and_then(|args| args.item.get(&argument.name)) {
+                Some(&Spanning { item: InputValue::Variable(ref name), .. })
+                    if !self.variables.contains_key(name as &str) => argument.default_value.clone(),
+                Some(value) => Some(self.resolve(&value.item)),
+                None => argument.default_value.clone(),
+            };
+
+            let value = value.unwrap_or(InputValue::Null);
+
+            if let Some(i) = value.as_int_value() {
+                multiplier = multiplier.saturating_mul(if i < 0 { 0 } else { i as usize });
+            }
+        }
+
+        multiplier
+    }
+
+    fn resolve(&self, value: &InputValue) -> InputValue {
+        match *value {
+            InputValue::Variable(ref name) => self.variables.get(name as &str)
+                .cloned()
+                .unwrap_or(InputValue::Null),
+            ref v => v.clone(),
+        }
+    }
+
+    /// Whether `@skip` or `@include` certainly exclude a selection
+    fn is_skipped(&self, directives: &Option<Vec<Spanning<Directive>>>) -> bool {
+        let directives = match *directives {
+            Some(ref directives) => directives,
+            None => return false,
+        };
+
+        directives.iter().any(|directive| {
+            let condition = directive.item.arguments.as_ref()
+                .and_then(|args| args.item.get("if"))
+                .and_then(|v| self.resolve(&v.item).convert::<bool>());
+
+            match (&directive.item.name.item as &str, condition) {
+                ("skip", Some(true)) | ("include", Some(false)) => true,
+                _ => false,
+            }
+        })
+    }
+}
+
+fn root_type_name(operation: &Operation, schema: &SchemaType) -> Option<String> {
+    let meta = match operation.operation_type {
+        OperationType::Query => Some(schema.concrete_query_type()),
+        OperationType::Mutation => schema.concrete_mutation_type(),
+        OperationType::Subscription => schema.concrete_subscription_type(),
+    };
+
+    meta.and_then(|m| m.name()).map(|n| n.to_owned())
+}
+
+fn operation_variables<'a>(
+    operation: &'a Operation,
+    variables: &'a HashMap<String, InputValue>,
+)
+    -> HashMap<&'a str, InputValue>
+{
+    let mut values: HashMap<&str, InputValue> = variables.iter()
+        .map(|(k, v)| (k as &str, v.clone()))
+        .collect();
+
+    if let Some(ref defs) = operation.variable_definitions {
+        for &(ref name, ref def) in defs.item.iter() {
+            if let Some(ref default) = def.default_value {
+                values.entry(&name.item).or_insert_with(|| default.item.clone());
+            }
+        }
+    }
+
+    values
+}
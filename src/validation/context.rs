@@ -5,7 +5,7 @@ use ast::{Document, Definition, Type};
 use schema::meta::MetaType;
 use schema::model::SchemaType;
 
-use parser::SourcePosition;
+use parser::{SourcePosition, Spanning, token_span, render_code_frame};
 
 /// Query validation error
 #[derive(Debug, PartialEq, Eq, PartialOrd, Ord)]
@@ -14,8 +14,12 @@ pub struct RuleError {
     message: String,
 }
 
-#[doc(hidden)]
+/// The state of a document being validated
+///
+/// Passed to every `Visitor` method. It keeps track of the types of the
+/// selections being visited and collects the errors that rules report.
 pub struct ValidatorContext<'a> {
+    /// The schema the document is validated against
     pub schema: &'a SchemaType,
     errors: Vec<RuleError>,
     type_stack: Vec<Option<&'a MetaType>>,
@@ -47,6 +51,25 @@ impl RuleError {
     pub fn locations(&self) -> &[SourcePosition] {
         &self.locations
     }
+
+    /// The spans of the validation error in the document source
+    ///
+    /// Each location is extended to the token starting there, e.g. the name
+    /// of an unknown field.
+    pub fn spans(&self, source: &str) -> Vec<Spanning<()>> {
+        self.locations.iter().map(|pos| token_span(source, pos)).collect()
+    }
+
+    /// Render the message followed by a code frame for each location
+    pub fn render(&self, source: &str) -> String {
+        let mut rendered = format!("error: {}\n", self.message);
+
+        for span in self.spans(source) {
+            rendered.push_str(&render_code_frame(source, &span));
+        }
+
+        rendered
+    }
 }
 
 impl<'a> ValidatorContext<'a> {
@@ -74,7 +97,7 @@ impl<'a> ValidatorContext<'a> {
         self.errors.append(&mut errors);
     }
 
-    #[doc(hidden)]
+    /// Report a validation error at one or more locations in the document
     pub fn report_error(&mut self, message: &str, locations: &[SourcePosition]) {
         self.errors.push(RuleError::new(message, locations))
     }
@@ -141,12 +164,13 @@ impl<'a> ValidatorContext<'a> {
         res
     }
 
-    #[doc(hidden)]
+    /// The type of the field or fragment being visited, if it is known
     pub fn current_type(&self) -> Option<&'a MetaType> {
         *self.type_stack.last().unwrap_or(&None)
     }
 
-    #[doc(hidden)]
+    /// The type of the field being visited as written in the schema,
+    /// including lists and non-null wrappers
     pub fn current_type_literal(&self) -> Option<&Type> {
         match self.type_literal_stack.last() {
             Some(&Some(ref t)) => Some(t),
@@ -154,12 +178,12 @@ impl<'a> ValidatorContext<'a> {
         }
     }
 
-    #[doc(hidden)]
+    /// The type whose fields are being selected, if it is known
     pub fn parent_type(&self) -> Option<&'a MetaType> {
         *self.parent_type_stack.last().unwrap_or(&None)
     }
 
-    #[doc(hidden)]
+    /// The expected type of the argument or input value being visited
     pub fn current_input_type_literal(&self) -> Option<&Type> {
         match self.input_type_literal_stack.last() {
             Some(&Some(ref t)) => Some(t),
@@ -167,7 +191,7 @@ impl<'a> ValidatorContext<'a> {
         }
     }
 
-    #[doc(hidden)]
+    /// Whether the document defines a fragment with this name
     pub fn is_known_fragment(&self, name: &str) -> bool {
         self.fragment_names.contains(name)
     }
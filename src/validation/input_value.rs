@@ -232,7 +232,7 @@ fn unify_input_object<'a>(
                 var_name,
                 var_pos,
                 &Path::ObjectField(&key, path),
-                &format!("Unknown field"),
+                &format!(r#"Unknown field "{}""#, key),
             );
         }
     }
@@ -247,6 +247,243 @@ fn unify_input_object<'a>(
     }
 }
 
+/// Coerce the provided variable values to the types declared by an operation
+///
+/// Missing variables take their default value, if one is declared; an explicit
+/// `null` is kept as provided.
+/// Provided values are converted to their declared type: strings are turned
+/// into enum values, integers into floats, and single values into lists.
+/// Values that can not be coerced are reported as errors.
+pub fn coerce_variable_values(
+    values: &HashMap<String, InputValue>,
+    var_defs: Option<&VariableDefinitions>,
+    schema: &SchemaType,
+)
+    -> Result<HashMap<String, InputValue>, Vec<RuleError>>
+{
+    let mut coerced = HashMap::new();
+    let mut errors = vec![];
+
+    if let Some(var_defs) = var_defs {
+        for &(ref name, ref def) in var_defs.iter() {
+            let value = match values.get(&name.item) {
+                Some(v) => v,
+                None => match def.default_value {
+                    Some(ref default) => &default.item,
+                    None if def.var_type.item.is_non_null() => {
+                        errors.push(RuleError::new(
+                            &format!(
+                                r#"Variable "${}" of required type "{}" was not provided."#,
+                                name.item, def.var_type.item,
+                            ),
+                            &[ name.start.clone() ],
+                        ));
+                        continue;
+                    }
+                    None => continue,
+                },
+            };
+
+            let meta_type = schema.make_type(&def.var_type.item);
+            let value = coerce_value(
+                &name.item, &name.start, value, &meta_type, schema, &mut errors, Path::Root);
+
+            coerced.insert(name.item.clone(), value);
+        }
+    }
+
+    if errors.is_empty() {
+        Ok(coerced)
+    }
+    else {
+        errors.sort();
+        Err(errors)
+    }
+}
+
+fn coerce_value<'a>(
+    var_name: &str,
+    var_pos: &SourcePosition,
+    value: &InputValue,
+    meta_type: &TypeType<'a>,
+    schema: &SchemaType,
+    errors: &mut Vec<RuleError>,
+    path: Path<'a>,
+)
+    -> InputValue
+{
+    match *meta_type {
+        TypeType::NonNull(ref inner) => {
+            if value.is_null() {
+                push_unification_error(
+                    errors, var_name, var_pos, &path,
+                    &format!(r#"Expected "{}", found null"#, meta_type)
+                );
+                InputValue::null()
+            }
+            else {
+                coerce_value(var_name, var_pos, value, &inner, schema, errors, path)
+            }
+        }
+
+        TypeType::List(ref inner) => {
+            if value.is_null() {
+                return InputValue::null();
+            }
+
+            match value.to_list_value() {
+                Some(l) => InputValue::list(l.iter().enumerate()
+                    .map(|(i, v)| coerce_value(
+                        var_name, var_pos, v, &inner, schema, errors, Path::ArrayElement(i, &path)))
+                    .collect()),
+                _ => InputValue::list(vec![
+                    coerce_value(var_name, var_pos, value, &inner, schema, errors, path)
+                ]),
+            }
+        }
+
+        TypeType::Concrete(mt) => {
+            if value.is_null() {
+                return InputValue::null();
+            }
+
+            match mt {
+                &MetaType::Scalar(ref sm) =>
+                    coerce_scalar(var_name, var_pos, value, sm, errors, &path),
+                &MetaType::Enum(ref em) =>
+                    coerce_enum(var_name, var_pos, value, em, errors, &path),
+                &MetaType::InputObject(ref iom) =>
+                    coerce_input_object(var_name, var_pos, value, iom, schema, errors, &path),
+                _ => {
+                    push_unification_error(
+                        errors, var_name, var_pos, &path,
+                        &format!(r#"Type "{}" is not an input type"#, meta_type)
+                    );
+                    InputValue::null()
+                }
+            }
+        }
+    }
+}
+
+fn coerce_scalar<'a>(
+    var_name: &str,
+    var_pos: &SourcePosition,
+    value: &InputValue,
+    meta: &ScalarMeta,
+    errors: &mut Vec<RuleError>,
+    path: &Path<'a>,
+)
+    -> InputValue
+{
+    let value = match (meta.name.as_str(), value) {
+        ("Float", &InputValue::Int(i)) => InputValue::float(i as f64),
+        _ => value.clone(),
+    };
+
+    if !(meta.try_parse_fn)(&value) {
+        push_unification_error(
+            errors, var_name, var_pos, path,
+            &format!(r#"Expected "{}", found {}"#, meta.name, value)
+        );
+    }
+
+    value
+}
+
+fn coerce_enum<'a>(
+    var_name: &str,
+    var_pos: &SourcePosition,
+    value: &InputValue,
+    meta: &EnumMeta,
+    errors: &mut Vec<RuleError>,
+    path: &Path<'a>,
+)
+    -> InputValue
+{
+    match value {
+        &InputValue::String(ref name) | &InputValue::Enum(ref name)
+            if meta.values.iter().any(|ev| &ev.name == name) =>
+            InputValue::enum_value(name),
+        _ => {
+            push_unification_error(
+                errors, var_name, var_pos, path,
+                &format!(r#"Expected "{}", found {}"#, meta.name, value)
+            );
+            value.clone()
+        }
+    }
+}
+
+fn coerce_input_object<'a>(
+    var_name: &str,
+    var_pos: &SourcePosition,
+    value: &InputValue,
+    meta: &InputObjectMeta,
+    schema: &SchemaType,
+    errors: &mut Vec<RuleError>,
+    path: &Path<'a>,
+)
+    -> InputValue
+{
+    let obj = match value.to_object_value() {
+        Some(obj) => obj,
+        None => {
+            push_unification_error(
+                errors, var_name, var_pos, path,
+                &format!(r#"Expected "{}", found not an object"#, meta.name)
+            );
+            return value.clone();
+        }
+    };
+
+    let mut keys = obj.keys().collect::<HashSet<&&str>>();
+    let mut fields = HashMap::new();
+
+    for input_field in &meta.input_fields {
+        keys.remove(&input_field.name.as_str());
+
+        let field_value = match obj.get(input_field.name.as_str()) {
+            Some(v) => *v,
+            None => match input_field.default_value {
+                Some(ref default) => default,
+                None if input_field.arg_type.is_non_null() => {
+                    push_unification_error(
+                        errors, var_name, var_pos, &Path::ObjectField(&input_field.name, path),
+                        &format!(r#"Expected "{}", found null"#, input_field.arg_type)
+                    );
+                    continue;
+                }
+                None => continue,
+            },
+        };
+
+        let field_value = coerce_value(
+            var_name,
+            var_pos,
+            field_value,
+            &schema.make_type(&input_field.arg_type),
+            schema,
+            errors,
+            Path::ObjectField(&input_field.name, path),
+        );
+
+        fields.insert(input_field.name.as_str(), field_value);
+    }
+
+    for key in keys {
+        push_unification_error(
+            errors,
+            var_name,
+            var_pos,
+            &Path::ObjectField(&key, path),
+            &format!(r#"Unknown field "{}""#, key),
+        );
+    }
+
+    InputValue::object(fields)
+}
+
 fn is_absent_or_null(v: Option<&InputValue>) -> bool {
     v.map_or(true, InputValue::is_null)
 }
@@ -0,0 +1,273 @@
+use std::collections::HashMap;
+
+use ast::{Document, Definition, Fragment, Selection, Directive};
+use parser::{SourcePosition, Spanning};
+use validation::RuleError;
+
+/// Structural limits on query documents
+///
+/// These are checked after validation and before execution. Every limit is
+/// unset by default:
+///
+/// ```rust
+/// use juniper::validation::QueryLimits;
+///
+/// let limits = QueryLimits::new()
+///     .max_depth(10)
+///     .max_aliases(30)
+///     .max_root_fields(20)
+///     .max_directives(50);
+/// ```
+///
+/// Attach them to a schema with `RootNode::with_limits`. Documents that break
+/// a limit are rejected with a validation error pointing at the first
+/// selection, alias, or directive over the limit.
+#[derive(Clone, Debug, Default)]
+pub struct QueryLimits {
+    max_depth: Option<usize>,
+    max_aliases: Option<usize>,
+    max_root_fields: Option<usize>,
+    max_directives: Option<usize>,
+}
+
+impl QueryLimits {
+    /// Create a set of limits that allows everything
+    pub fn new() -> QueryLimits {
+        QueryLimits::default()
+    }
+
+    /// Limit how deeply fields can be nested, following fragment spreads
+    ///
+    /// The root fields of an operation are at depth 1.
+    pub fn max_depth(mut self, max_depth: usize) -> QueryLimits {
+        self.max_depth = Some(max_depth);
+        self
+    }
+
+    /// Limit the number of aliased fields in a document
+    pub fn max_aliases(mut self, max_aliases: usize) -> QueryLimits {
+        self.max_aliases = Some(max_aliases);
+        self
+    }
+
+    /// Limit the number of root fields of each operation, following fragment
+    /// spreads
+    pub fn max_root_fields(mut self, max_root_fields: usize) -> QueryLimits {
+        self.max_root_fields = Some(max_root_fields);
+        self
+    }
+
+    /// Limit the number of directives in a document
+    pub fn max_directives(mut self, max_directives: usize) -> QueryLimits {
+        self.max_directives = Some(max_directives);
+        self
+    }
+
+    /// Check a validated document against the limits
+    pub fn check(&self, document: &Document) -> Vec<RuleError> {
+        let mut errors = Vec::new();
+
+        let fragments = document.iter()
+            .filter_map(|def| match *def {
+                Definition::Fragment(ref f) => Some((&f.item.name.item as &str, &f.item)),
+                _ => None,
+            })
+            .collect::<HashMap<_, _>>();
+
+        for def in document {
+            if let Definition::Operation(ref op) = *def {
+                if let Some(max_depth) = self.max_depth {
+                    if let Some(pos) = find_too_deep(&op.item.selection_set, 1, max_depth, &fragments, &mut Vec::new()) {
+                        errors.push(RuleError::new(
+                            &format!("Field is nested deeper than the maximum depth of {}", max_depth),
+                            &[pos.clone()]));
+                    }
+                }
+
+                if let Some(max_root_fields) = self.max_root_fields {
+                    let mut root_fields = Vec::new();
+                    collect_fields(&op.item.selection_set, &fragments, &mut Vec::new(), &mut root_fields);
+
+                    if let Some(&pos) = root_fields.get(max_root_fields) {
+                        errors.push(RuleError::new(
+                            &format!("Operation has more than the maximum of {} root fields", max_root_fields),
+                            &[pos.clone()]));
+                    }
+                }
+            }
+        }
+
+        if let Some(max_aliases) = self.max_aliases {
+            let mut aliases = Vec::new();
+
+            for selection_set in document_selection_sets(document) {
+                collect_aliases(selection_set, &mut aliases);
+            }
+
+            if let Some(&pos) = aliases.get(max_aliases) {
+                errors.push(RuleError::new(
+                    &format!("Document has more than the maximum of {} aliases", max_aliases),
+                    &[pos.clone()]));
+            }
+        }
+
+        if let Some(max_directives) = self.max_directives {
+            let mut directives = Vec::new();
+
+            for def in document {
+                match *def {
+                    Definition::Operation(ref op) => {
+                        collect_directive_positions(&op.item.directives, &mut directives);
+                        collect_directives(&op.item.selection_set, &mut directives);
+                    }
+                    Definition::Fragment(ref f) => {
+                        collect_directive_positions(&f.item.directives, &mut directives);
+                        collect_directives(&f.item.selection_set, &mut directives);
+                    }
+                    _ => {}
+                }
+            }
+
+            if let Some(&pos) = directives.get(max_directives) {
+                errors.push(RuleError::new(
+                    &format!("Document has more than the maximum of {} directives", max_directives),
+                    &[pos.clone()]));
+            }
+        }
+
+        errors
+    }
+}
+
+fn document_selection_sets(document: &Document) -> Vec<&[Selection]> {
+    document.iter()
+        .filter_map(|def| match *def {
+            Definition::Operation(ref op) => Some(&op.item.selection_set as &[Selection]),
+            Definition::Fragment(ref f) => Some(&f.item.selection_set as &[Selection]),
+            _ => None,
+        })
+        .collect()
+}
+
+/// Find the first field nested deeper than `max_depth`
+fn find_too_deep<'a>(
+    selection_set: &'a [Selection],
+    depth: usize,
+    max_depth: usize,
+    fragments: &HashMap<&str, &'a Fragment>,
+    visited_fragments: &mut Vec<&'a str>,
+)
+    -> Option<&'a SourcePosition>
+{
+    for selection in selection_set {
+        let found = match *selection {
+            Selection::Field(ref field) => {
+                if depth > max_depth {
+                    return Some(&field.start);
+                }
+
+                match field.item.selection_set {
+                    Some(ref selection_set) => find_too_deep(
+                        selection_set, depth + 1, max_depth, fragments, visited_fragments),
+                    None => None,
+                }
+            }
+            Selection::FragmentSpread(ref spread) => {
+                let name = &spread.item.name.item as &str;
+
+                match fragments.get(name) {
+                    Some(fragment) if !visited_fragments.contains(&name) => {
+                        visited_fragments.push(name);
+                        let found = find_too_deep(
+                            &fragment.selection_set, depth, max_depth, fragments, visited_fragments);
+                        visited_fragments.pop();
+                        found
+                    }
+                    _ => None,
+                }
+            }
+            Selection::InlineFragment(ref fragment) => find_too_deep(
+                &fragment.item.selection_set, depth, max_depth, fragments, visited_fragments),
+        };
+
+        if found.is_some() {
+            return found;
+        }
+    }
+
+    None
+}
+
+/// Collect the positions of the fields of a selection set, expanding fragments
+fn collect_fields<'a>(
+    selection_set: &'a [Selection],
+    fragments: &HashMap<&str, &'a Fragment>,
+    visited_fragments: &mut Vec<&'a str>,
+    fields: &mut Vec<&'a SourcePosition>,
+) {
+    for selection in selection_set {
+        match *selection {
+            Selection::Field(ref field) => fields.push(&field.start),
+            Selection::FragmentSpread(ref spread) => {
+                let name = &spread.item.name.item as &str;
+
+                if let Some(fragment) = fragments.get(name) {
+                    if !visited_fragments.contains(&name) {
+                        visited_fragments.push(name);
+                        collect_fields(&fragment.selection_set, fragments, visited_fragments, fields);
+                        visited_fragments.pop();
+                    }
+                }
+            }
+            Selection::InlineFragment(ref fragment) =>
+                collect_fields(&fragment.item.selection_set, fragments, visited_fragments, fields),
+        }
+    }
+}
+
+fn collect_aliases<'a>(selection_set: &'a [Selection], aliases: &mut Vec<&'a SourcePosition>) {
+    for selection in selection_set {
+        match *selection {
+            Selection::Field(ref field) => {
+                if let Some(ref alias) = field.item.alias {
+                    aliases.push(&alias.start);
+                }
+
+                if let Some(ref selection_set) = field.item.selection_set {
+                    collect_aliases(selection_set, aliases);
+                }
+            }
+            Selection::InlineFragment(ref fragment) => collect_aliases(&fragment.item.selection_set, aliases),
+            Selection::FragmentSpread(_) => {}
+        }
+    }
+}
+
+fn collect_directives<'a>(selection_set: &'a [Selection], directives: &mut Vec<&'a SourcePosition>) {
+    for selection in selection_set {
+        match *selection {
+            Selection::Field(ref field) => {
+                collect_directive_positions(&field.item.directives, directives);
+
+                if let Some(ref selection_set) = field.item.selection_set {
+                    collect_directives(selection_set, directives);
+                }
+            }
+            Selection::FragmentSpread(ref spread) =>
+                collect_directive_positions(&spread.item.directives, directives),
+            Selection::InlineFragment(ref fragment) => {
+                collect_directive_positions(&fragment.item.directives, directives);
+                collect_directives(&fragment.item.selection_set, directives);
+            }
+        }
+    }
+}
+
+fn collect_directive_positions<'a>(
+    directives: &'a Option<Vec<Spanning<Directive>>>,
+    positions: &mut Vec<&'a SourcePosition>,
+) {
+    if let Some(ref directives) = *directives {
+        positions.extend(directives.iter().map(|d| &d.start));
+    }
+}
@@ -6,6 +6,10 @@ mod context;
 mod multi_visitor;
 mod rules;
 mod input_value;
+mod complexity;
+mod limits;
+mod validator;
+mod suggestions;
 
 #[cfg(test)]
 mod test_harness;
@@ -13,9 +17,12 @@ mod test_harness;
 pub use self::traits::Visitor;
 pub use self::visitor::visit;
 pub use self::context::{RuleError, ValidatorContext};
-pub use self::rules::visit_all_rules;
+pub use self::rules::{visit_all_rules, SPEC_RULE_NAMES, EXECUTION_RULE_NAMES};
+pub use self::validator::{Validator, ValidationRule};
 pub use self::multi_visitor::MultiVisitor;
-pub use self::input_value::validate_input_values;
+pub use self::input_value::{validate_input_values, coerce_variable_values};
+pub use self::complexity::QueryComplexity;
+pub use self::limits::QueryLimits;
 
 #[cfg(test)]
 pub use self::test_harness::{
@@ -14,19 +14,12 @@ pub fn factory() -> DefaultValuesOfCorrectType {
 impl<'a> Visitor<'a> for DefaultValuesOfCorrectType {
     fn enter_variable_definition(&mut self, ctx: &mut ValidatorContext<'a>, &(ref var_name, ref var_def): &'a (Spanning<String>, VariableDefinition)) {
         if let Some(Spanning { item: ref var_value, ref start, .. }) = var_def.default_value {
-            if var_def.var_type.item.is_non_null() {
-                ctx.report_error(
-                    &non_null_error_message(&var_name.item, &format!("{}", var_def.var_type.item)),
-                    &[start.clone()])
-            }
-            else {
-                let meta_type = ctx.schema.make_type(&var_def.var_type.item);
+            let meta_type = ctx.schema.make_type(&var_def.var_type.item);
 
-                if !is_valid_literal_value(&ctx.schema, &meta_type, var_value) {
-                    ctx.report_error(
-                        &type_error_message(&var_name.item, &format!("{}", var_def.var_type.item)),
-                        &[start.clone()]);
-                }
+            if !is_valid_literal_value(&ctx.schema, &meta_type, var_value) {
+                ctx.report_error(
+                    &type_error_message(&var_name.item, &format!("{}", var_def.var_type.item)),
+                    &[start.clone()]);
             }
         }
     }
@@ -38,15 +31,9 @@ fn type_error_message(arg_name: &str, type_name: &str) -> String {
         arg_name, type_name)
 }
 
-fn non_null_error_message(arg_name: &str, type_name: &str) -> String {
-    format!(
-        "Argument \"{}\" has type \"{}\" and is not nullable, so it't can't have a default value",
-        arg_name, type_name)
-}
-
 #[cfg(test)]
 mod tests {
-    use super::{type_error_message, non_null_error_message, factory};
+    use super::{type_error_message, factory};
 
     use parser::SourcePosition;
     use validation::{RuleError, expect_passes_rule, expect_fails_rule};
@@ -83,20 +70,12 @@ mod tests {
     }
 
     #[test]
-    fn no_required_variables_with_default_values() {
-        expect_fails_rule(factory, r#"
-          query UnreachableDefaultValues($a: Int! = 3, $b: String! = "default") {
+    fn required_variables_with_valid_default_values() {
+        expect_passes_rule(factory, r#"
+          query RequiredDefaultValues($a: Int! = 3, $b: String! = "default") {
             dog { name }
           }
-        "#,
-            &[
-                RuleError::new(&non_null_error_message("a", "Int!"), &[
-                    SourcePosition::new(53, 1, 52),
-                ]),
-                RuleError::new(&non_null_error_message("b", "String!"), &[
-                    SourcePosition::new(70, 1, 69),
-                ]),
-            ]);
+        "#);
     }
 
     #[test]
@@ -125,16 +104,12 @@ mod tests {
 
     #[test]
     fn complex_variables_missing_required_field() {
-        expect_fails_rule(factory, r#"
+        // Missing fields are reported by ProvidedNonNullInputFields
+        expect_passes_rule(factory, r#"
           query MissingRequiredField($a: ComplexInput = {intField: 3}) {
             dog { name }
           }
-        "#,
-            &[
-                RuleError::new(&type_error_message("a", "ComplexInput"), &[
-                    SourcePosition::new(57, 1, 56),
-                ]),
-            ]);
+        "#);
     }
 
     #[test]
@@ -0,0 +1,138 @@
+use ast::{Definition, Document};
+use validation::{ValidatorContext, Visitor};
+
+pub struct ExecutableDefinitions {
+}
+
+pub fn factory() -> ExecutableDefinitions {
+    ExecutableDefinitions {}
+}
+
+impl<'a> Visitor<'a> for ExecutableDefinitions {
+    fn enter_document(&mut self, ctx: &mut ValidatorContext<'a>, doc: &'a Document) {
+        for def in doc {
+            let (name, start) = match *def {
+                Definition::Operation(_) | Definition::Fragment(_) => continue,
+                Definition::Schema(ref s) | Definition::SchemaExtension(ref s) => (None, &s.start),
+                Definition::Type(ref t) | Definition::TypeExtension(ref t) =>
+                    (Some(&t.item.name().item), &t.start),
+                Definition::Directive(ref d) => (Some(&d.item.name.item), &d.start),
+            };
+
+            ctx.report_error(&error_message(name.map(|n| n as &str)), &[start.clone()]);
+        }
+    }
+}
+
+fn error_message(def_name: Option<&str>) -> String {
+    match def_name {
+        Some(name) => format!(r#"The "{}" definition is not executable"#, name),
+        None => "The schema definition is not executable".to_owned(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{error_message, factory};
+
+    use parser::SourcePosition;
+    use validation::{RuleError, expect_passes_rule, expect_fails_rule};
+
+    #[test]
+    fn with_only_operation() {
+        expect_passes_rule(factory, r#"
+          query Foo {
+            dog {
+              name
+            }
+          }
+        "#);
+    }
+
+    #[test]
+    fn with_operation_and_fragment() {
+        expect_passes_rule(factory, r#"
+          query Foo {
+            dog {
+              name
+              ...Frag
+            }
+          }
+
+          fragment Frag on Dog {
+            name
+          }
+        "#);
+    }
+
+    #[test]
+    fn with_type_definition() {
+        expect_fails_rule(factory, r#"
+          query Foo {
+            dog {
+              name
+            }
+          }
+
+          type Cow {
+            name: String
+          }
+
+          extend type Dog {
+            color: String
+          }
+        "#,
+            &[
+                RuleError::new(&error_message(Some("Cow")), &[
+                    SourcePosition::new(97, 7, 10),
+                ]),
+                RuleError::new(&error_message(Some("Dog")), &[
+                    SourcePosition::new(156, 11, 10),
+                ]),
+            ]);
+    }
+
+    #[test]
+    fn with_schema_definition() {
+        expect_fails_rule(factory, r#"
+          schema {
+            query: Query
+          }
+
+          type Query {
+            test: String
+          }
+
+          extend schema @directive
+        "#,
+            &[
+                RuleError::new(&error_message(None), &[
+                    SourcePosition::new(11, 1, 10),
+                ]),
+                RuleError::new(&error_message(Some("Query")), &[
+                    SourcePosition::new(68, 5, 10),
+                ]),
+                RuleError::new(&error_message(None), &[
+                    SourcePosition::new(129, 9, 10),
+                ]),
+            ]);
+    }
+
+    #[test]
+    fn with_directive_definition() {
+        expect_fails_rule(factory, r#"
+          directive @cached(ttl: Int) on FIELD
+
+          query Foo {
+            dog @cached(ttl: 60) {
+              name
+            }
+          }
+        "#,
+            &[
+                RuleError::new(&error_message(Some("cached")), &[
+                    SourcePosition::new(11, 1, 10),
+                ]),
+            ]);
+    }
+}
@@ -1,5 +1,10 @@
+use std::collections::HashMap;
+
 use ast::Field;
+use schema::meta::{MetaType, ObjectMeta, InterfaceMeta};
+use schema::model::SchemaType;
 use validation::{Visitor, ValidatorContext};
+use validation::suggestions::{suggestion_list, did_you_mean};
 use parser::Spanning;
 
 pub struct FieldsOnCorrectType {}
@@ -16,8 +21,16 @@ impl<'a> Visitor<'a> for FieldsOnCorrectType {
                 let type_name = parent_type.name().clone().unwrap_or("<unknown>");
 
                 if parent_type.field_by_name(&field_name.item).is_none() {
+                    let suggested_types = suggested_type_names(context.schema, parent_type, &field_name.item);
+                    let suggested_fields = if suggested_types.is_empty() {
+                        suggested_field_names(parent_type, &field_name.item)
+                    }
+                    else {
+                        Vec::new()
+                    };
+
                     context.report_error(
-                        &error_message(&field_name.item, &type_name),
+                        &error_message(&field_name.item, &type_name, &suggested_types, &suggested_fields),
                         &[field_name.start.clone()]);
                 }
             }
@@ -25,8 +38,65 @@ impl<'a> Visitor<'a> for FieldsOnCorrectType {
     }
 }
 
-fn error_message(field: &str, type_name: &str) -> String {
-    format!(r#"Unknown field "{}" on type "{}""#, field, type_name)
+/// Find the types that implement or belong to an abstract type and have the
+/// field
+///
+/// Interfaces are listed first, the ones implemented by the most matching
+/// object types before the others.
+fn suggested_type_names<'a>(schema: &'a SchemaType, parent_type: &'a MetaType, field_name: &str) -> Vec<&'a str> {
+    if !parent_type.is_abstract() {
+        return Vec::new();
+    }
+
+    let mut object_names = Vec::new();
+    let mut interface_counts = HashMap::new();
+
+    for possible_type in schema.possible_types(parent_type) {
+        if possible_type.field_by_name(field_name).is_none() {
+            continue;
+        }
+
+        if let MetaType::Object(ObjectMeta { ref name, ref interface_names, .. }) = *possible_type {
+            object_names.push(name as &str);
+
+            for interface_name in interface_names {
+                let has_field = schema.concrete_type_by_name(interface_name)
+                    .and_then(|t| t.field_by_name(field_name))
+                    .is_some();
+
+                if has_field {
+                    *interface_counts.entry(interface_name as &str).or_insert(0) += 1;
+                }
+            }
+        }
+    }
+
+    let mut interfaces = interface_counts.into_iter().collect::<Vec<_>>();
+    interfaces.sort_by(|&(a_name, a_count), &(b_name, b_count)| b_count.cmp(&a_count).then(a_name.cmp(b_name)));
+
+    object_names.sort();
+
+    interfaces.into_iter().map(|(name, _)| name).chain(object_names).collect()
+}
+
+fn suggested_field_names<'a>(parent_type: &'a MetaType, field_name: &str) -> Vec<&'a str> {
+    match *parent_type {
+        MetaType::Object(ObjectMeta { ref fields, .. }) |
+        MetaType::Interface(InterfaceMeta { ref fields, .. }) =>
+            suggestion_list(field_name, fields.iter().map(|f| &f.name as &str)),
+        _ => Vec::new(),
+    }
+}
+
+fn error_message(field: &str, type_name: &str, suggested_types: &[&str], suggested_fields: &[&str]) -> String {
+    let message = format!(r#"Unknown field "{}" on type "{}""#, field, type_name);
+
+    if !suggested_types.is_empty() {
+        did_you_mean(message, Some("to use an inline fragment on"), suggested_types)
+    }
+    else {
+        did_you_mean(message, None, suggested_fields)
+    }
 }
 
 #[cfg(test)]
@@ -105,10 +175,10 @@ mod tests {
           }
         "#,
             &[
-                RuleError::new(&error_message("unknown_pet_field", "Pet"), &[
+                RuleError::new(&error_message("unknown_pet_field", "Pet", &[], &[]), &[
                     SourcePosition::new(56, 2, 12)
                 ]),
-                RuleError::new(&error_message("unknown_cat_field", "Cat"), &[
+                RuleError::new(&error_message("unknown_cat_field", "Cat", &[], &[]), &[
                     SourcePosition::new(119, 4, 16)
                 ]),
             ]);
@@ -122,7 +192,7 @@ mod tests {
           }
         "#,
             &[
-                RuleError::new(&error_message("meowVolume", "Dog"), &[
+                RuleError::new(&error_message("meowVolume", "Dog", &[], &["barkVolume"]), &[
                     SourcePosition::new(57, 2, 12)
                 ]),
             ]);
@@ -138,7 +208,7 @@ mod tests {
           }
         "#,
             &[
-                RuleError::new(&error_message("unknown_field", "Dog"), &[
+                RuleError::new(&error_message("unknown_field", "Dog", &[], &[]), &[
                     SourcePosition::new(61, 2, 12)
                 ]),
             ]);
@@ -154,7 +224,7 @@ mod tests {
           }
         "#,
             &[
-                RuleError::new(&error_message("unknown_field", "Pet"), &[
+                RuleError::new(&error_message("unknown_field", "Pet", &[], &[]), &[
                     SourcePosition::new(83, 3, 14)
                 ]),
             ]);
@@ -170,7 +240,7 @@ mod tests {
           }
         "#,
             &[
-                RuleError::new(&error_message("meowVolume", "Dog"), &[
+                RuleError::new(&error_message("meowVolume", "Dog", &[], &["barkVolume"]), &[
                     SourcePosition::new(84, 3, 14)
                 ]),
             ]);
@@ -184,7 +254,7 @@ mod tests {
           }
         "#,
             &[
-                RuleError::new(&error_message("mooVolume", "Dog"), &[
+                RuleError::new(&error_message("mooVolume", "Dog", &[], &["barkVolume"]), &[
                     SourcePosition::new(79, 2, 21)
                 ]),
             ]);
@@ -198,7 +268,7 @@ mod tests {
           }
         "#,
             &[
-                RuleError::new(&error_message("kawVolume", "Dog"), &[
+                RuleError::new(&error_message("kawVolume", "Dog", &[], &["barkVolume"]), &[
                     SourcePosition::new(88, 2, 25)
                 ]),
             ]);
@@ -212,7 +282,7 @@ mod tests {
           }
         "#,
             &[
-                RuleError::new(&error_message("tailLength", "Pet"), &[
+                RuleError::new(&error_message("tailLength", "Pet", &[], &[]), &[
                     SourcePosition::new(63, 2, 12)
                 ]),
             ]);
@@ -226,12 +296,40 @@ mod tests {
           }
         "#,
             &[
-                RuleError::new(&error_message("nickname", "Pet"), &[
+                RuleError::new(&error_message("nickname", "Pet", &["Cat", "Dog"], &[]), &[
                     SourcePosition::new(78, 2, 12)
                 ]),
             ]);
     }
 
+    #[test]
+    fn suggests_similar_field_names() {
+        expect_fails_rule(factory, r#"
+          fragment misspelledField on Dog {
+            nmae
+          }
+        "#,
+            &[
+                RuleError::new(r#"Unknown field "nmae" on type "Dog". Did you mean "name"?"#, &[
+                    SourcePosition::new(57, 2, 12)
+                ]),
+            ]);
+    }
+
+    #[test]
+    fn suggests_types_with_the_field_on_abstract_types() {
+        expect_fails_rule(factory, r#"
+          fragment onlyOnDogs on Pet {
+            barkVolume
+          }
+        "#,
+            &[
+                RuleError::new(r#"Unknown field "barkVolume" on type "Pet". Did you mean to use an inline fragment on "Dog"?"#, &[
+                    SourcePosition::new(52, 2, 12)
+                ]),
+            ]);
+    }
+
     #[test]
     fn meta_field_on_union() {
         expect_passes_rule(factory, r#"
@@ -249,7 +347,7 @@ mod tests {
           }
         "#,
             &[
-                RuleError::new(&error_message("name", "CatOrDog"), &[
+                RuleError::new(&error_message("name", "CatOrDog", &["Being", "Pet", "Canine", "Cat", "Dog"], &[]), &[
                     SourcePosition::new(82, 2, 12)
                 ]),
             ]);
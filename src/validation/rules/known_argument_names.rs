@@ -2,6 +2,7 @@ use ast::{Field, InputValue, Directive};
 use schema::meta::Argument;
 use parser::Spanning;
 use validation::{ValidatorContext, Visitor};
+use validation::suggestions::{suggestion_list, did_you_mean};
 
 #[derive(Debug)]
 enum ArgumentPosition<'a> {
@@ -49,11 +50,13 @@ impl<'a> Visitor<'a> for KnownArgumentNames<'a> {
     fn enter_argument(&mut self, ctx: &mut ValidatorContext<'a>, &(ref arg_name, _): &'a (Spanning<String>, Spanning<InputValue>)) {
         if let Some((ref pos, args)) = self.current_args {
             if args.iter().filter(|a| a.name == arg_name.item).next().is_none() {
+                let suggestions = suggestion_list(&arg_name.item, args.iter().map(|a| &a.name as &str));
+
                 let message = match *pos {
                     ArgumentPosition::Field(ref field_name, ref type_name) =>
-                        field_error_message(&arg_name.item, field_name, type_name),
+                        field_error_message(&arg_name.item, field_name, type_name, &suggestions),
                     ArgumentPosition::Directive(ref directive_name) =>
-                        directive_error_message(&arg_name.item, directive_name),
+                        directive_error_message(&arg_name.item, directive_name, &suggestions),
                 };
 
                 ctx.report_error(
@@ -64,16 +67,22 @@ impl<'a> Visitor<'a> for KnownArgumentNames<'a> {
     }
 }
 
-fn field_error_message(arg_name: &str, field_name: &str, type_name: &str) -> String {
-    format!(
-        r#"Unknown argument "{}" on field "{}" of type "{}""#,
-        arg_name, field_name, type_name)
+fn field_error_message(arg_name: &str, field_name: &str, type_name: &str, suggestions: &[&str]) -> String {
+    did_you_mean(
+        format!(
+            r#"Unknown argument "{}" on field "{}" of type "{}""#,
+            arg_name, field_name, type_name),
+        None,
+        suggestions)
 }
 
-fn directive_error_message(arg_name: &str, directive_name: &str) -> String {
-    format!(
-        r#"Unknown argument "{}" on directive "{}""#,
-        arg_name, directive_name)
+fn directive_error_message(arg_name: &str, directive_name: &str, suggestions: &[&str]) -> String {
+    did_you_mean(
+        format!(
+            r#"Unknown argument "{}" on directive "{}""#,
+            arg_name, directive_name),
+        None,
+        suggestions)
 }
 
 #[cfg(test)]
@@ -163,12 +172,49 @@ mod tests {
           }
         "#,
             &[
-                RuleError::new(&directive_error_message("unless", "skip"), &[
+                RuleError::new(&directive_error_message("unless", "skip", &[]), &[
+                    SourcePosition::new(35, 2, 22),
+                ]),
+            ]);
+    }
+
+    #[test]
+    fn directive_without_args_is_valid() {
+        expect_passes_rule(factory, r#"
+          {
+            dog @onField
+          }
+        "#);
+    }
+
+    #[test]
+    fn misspelled_directive_args_are_reported() {
+        expect_fails_rule(factory, r#"
+          {
+            dog @skip(iff: true)
+          }
+        "#,
+            &[
+                RuleError::new(&directive_error_message("iff", "skip", &["if"]), &[
                     SourcePosition::new(35, 2, 22),
                 ]),
             ]);
     }
 
+    #[test]
+    fn args_are_case_sensitive() {
+        expect_fails_rule(factory, r#"
+          fragment caseSensitiveArgName on Dog {
+            doesKnowCommand(dogcommand: true)
+          }
+        "#,
+            &[
+                RuleError::new(&field_error_message("dogcommand", "doesKnowCommand", "Dog", &["dogCommand"]), &[
+                    SourcePosition::new(78, 2, 28),
+                ]),
+            ]);
+    }
+
     #[test]
     fn invalid_arg_name() {
         expect_fails_rule(factory, r#"
@@ -177,7 +223,7 @@ mod tests {
           }
         "#,
             &[
-                RuleError::new(&field_error_message("unknown", "doesKnowCommand", "Dog"), &[
+                RuleError::new(&field_error_message("unknown", "doesKnowCommand", "Dog", &[]), &[
                     SourcePosition::new(72, 2, 28),
                 ]),
             ]);
@@ -191,10 +237,10 @@ mod tests {
           }
         "#,
             &[
-                RuleError::new(&field_error_message("whoknows", "doesKnowCommand", "Dog"), &[
+                RuleError::new(&field_error_message("whoknows", "doesKnowCommand", "Dog", &[]), &[
                     SourcePosition::new(81, 2, 28),
                 ]),
-                RuleError::new(&field_error_message("unknown", "doesKnowCommand", "Dog"), &[
+                RuleError::new(&field_error_message("unknown", "doesKnowCommand", "Dog", &[]), &[
                     SourcePosition::new(111, 2, 58),
                 ]),
             ]);
@@ -217,10 +263,10 @@ mod tests {
           }
         "#,
             &[
-                RuleError::new(&field_error_message("unknown", "doesKnowCommand", "Dog"), &[
+                RuleError::new(&field_error_message("unknown", "doesKnowCommand", "Dog", &[]), &[
                     SourcePosition::new(61, 3, 30),
                 ]),
-                RuleError::new(&field_error_message("unknown", "doesKnowCommand", "Dog"), &[
+                RuleError::new(&field_error_message("unknown", "doesKnowCommand", "Dog", &[]), &[
                     SourcePosition::new(193, 8, 34),
                 ]),
             ]);
@@ -18,12 +18,14 @@ impl<'a> Visitor<'a> for KnownDirectives {
         self.location_stack.push(match op.item.operation_type {
             OperationType::Query => DirectiveLocation::Query,
             OperationType::Mutation => DirectiveLocation::Mutation,
+            OperationType::Subscription => DirectiveLocation::Subscription,
         });
     }
 
     fn exit_operation_definition(&mut self, _: &mut ValidatorContext<'a>, _: &'a Spanning<Operation>) {
         let top = self.location_stack.pop();
-        assert!(top == Some(DirectiveLocation::Query) || top == Some(DirectiveLocation::Mutation));
+        assert!(top == Some(DirectiveLocation::Query) || top == Some(DirectiveLocation::Mutation)
+                || top == Some(DirectiveLocation::Subscription));
     }
 
     fn enter_field(&mut self, _: &mut ValidatorContext<'a>, _: &'a Spanning<Field>) {
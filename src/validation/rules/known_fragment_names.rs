@@ -1,26 +1,43 @@
-use ast::FragmentSpread;
+use ast::{Definition, Document, FragmentSpread};
 use validation::{ValidatorContext, Visitor};
+use validation::suggestions::{suggestion_list, did_you_mean};
 use parser::Spanning;
 
-pub struct KnownFragmentNames {}
+pub struct KnownFragmentNames<'a> {
+    fragment_names: Vec<&'a str>,
+}
 
-pub fn factory() -> KnownFragmentNames {
-    KnownFragmentNames {}
+pub fn factory<'a>() -> KnownFragmentNames<'a> {
+    KnownFragmentNames {
+        fragment_names: Vec::new(),
+    }
 }
 
-impl<'a> Visitor<'a> for KnownFragmentNames {
+impl<'a> Visitor<'a> for KnownFragmentNames<'a> {
+    fn enter_document(&mut self, _: &mut ValidatorContext<'a>, doc: &'a Document) {
+        self.fragment_names = doc
+            .iter()
+            .filter_map(|d| match *d {
+                Definition::Fragment(ref f) => Some(&f.item.name.item as &str),
+                _ => None,
+            })
+            .collect();
+    }
+
     fn enter_fragment_spread(&mut self, context: &mut ValidatorContext<'a>, spread: &'a Spanning<FragmentSpread>) {
         let spread_name = &spread.item.name;
         if !context.is_known_fragment(&spread_name.item) {
+            let suggestions = suggestion_list(&spread_name.item, self.fragment_names.iter().cloned());
+
             context.report_error(
-                &error_message(&spread_name.item),
+                &error_message(&spread_name.item, &suggestions),
                 &[spread_name.start.clone()]);
         }
     }
 }
 
-fn error_message(frag_name: &str) -> String {
-    format!(r#"Unknown fragment: "{}""#, frag_name)
+fn error_message(frag_name: &str, suggestions: &[&str]) -> String {
+    did_you_mean(format!(r#"Unknown fragment: "{}""#, frag_name), None, suggestions)
 }
 
 #[cfg(test)]
@@ -74,15 +91,34 @@ mod tests {
           }
         "#,
             &[
-                RuleError::new(&error_message("UnknownFragment1"), &[
+                RuleError::new(&error_message("UnknownFragment1", &[]), &[
                     SourcePosition::new(57, 3, 17),
                 ]),
-                RuleError::new(&error_message("UnknownFragment2"), &[
+                RuleError::new(&error_message("UnknownFragment2", &[]), &[
                     SourcePosition::new(122, 5, 19),
                 ]),
-                RuleError::new(&error_message("UnknownFragment3"), &[
+                RuleError::new(&error_message("UnknownFragment3", &[]), &[
                     SourcePosition::new(255, 11, 15),
                 ]),
             ]);
     }
+
+    #[test]
+    fn suggests_similar_fragment_names() {
+        expect_fails_rule(factory, r#"
+          {
+            human(id: 4) {
+              ...HumanFeilds
+            }
+          }
+          fragment HumanFields on Human {
+            name
+          }
+        "#,
+            &[
+                RuleError::new(&error_message("HumanFeilds", &["HumanFields"]), &[
+                    SourcePosition::new(57, 3, 17),
+                ]),
+            ]);
+    }
 }
@@ -1,5 +1,6 @@
 use ast::{Fragment, InlineFragment, VariableDefinition};
 use validation::{ValidatorContext, Visitor};
+use validation::suggestions::{suggestion_list, did_you_mean};
 use parser::{SourcePosition, Spanning};
 
 pub struct KnownTypeNames {}
@@ -28,14 +29,18 @@ impl<'a> Visitor<'a> for KnownTypeNames {
 
 fn validate_type<'a>(ctx: &mut ValidatorContext<'a>, type_name: &str, location: &SourcePosition) {
     if ctx.schema.type_by_name(type_name).is_none() {
+        let suggestions = suggestion_list(
+            type_name,
+            ctx.schema.concrete_type_list().into_iter().filter_map(|t| t.name()));
+
         ctx.report_error(
-            &error_message(type_name),
+            &error_message(type_name, &suggestions),
             &[location.clone()]);
     }
 }
 
-fn error_message(type_name: &str) -> String {
-    format!(r#"Unknown type "{}""#, type_name)
+fn error_message(type_name: &str, suggestions: &[&str]) -> String {
+    did_you_mean(format!(r#"Unknown type "{}""#, type_name), None, suggestions)
 }
 
 #[cfg(test)]
@@ -73,13 +78,13 @@ mod tests {
           }
         "#,
             &[
-                RuleError::new(&error_message("JumbledUpLetters"), &[
+                RuleError::new(&error_message("JumbledUpLetters", &[]), &[
                     SourcePosition::new(27, 1, 26),
                 ]),
-                RuleError::new(&error_message("Badger"), &[
+                RuleError::new(&error_message("Badger", &[]), &[
                     SourcePosition::new(120, 4, 28),
                 ]),
-                RuleError::new(&error_message("Peettt"), &[
+                RuleError::new(&error_message("Peettt", &["Pet"]), &[
                     SourcePosition::new(210, 7, 32),
                 ]),
             ]);
@@ -18,7 +18,7 @@ impl<'a> Visitor<'a> for LoneAnonymousOperation {
             .iter()
             .filter(|d| match **d {
                 Definition::Operation(_) => true,
-                Definition::Fragment(_) => false,
+                _ => false,
             })
             .count());
     }
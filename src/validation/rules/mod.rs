@@ -1,5 +1,6 @@
 mod arguments_of_correct_type;
 mod default_values_of_correct_type;
+mod executable_definitions;
 mod fields_on_correct_type;
 mod fragments_on_composite_types;
 mod known_argument_names;
@@ -14,46 +15,120 @@ mod no_unused_variables;
 mod overlapping_fields_can_be_merged;
 mod possible_fragment_spreads;
 mod provided_non_null_arguments;
+mod provided_non_null_input_fields;
 mod scalar_leafs;
+mod single_field_subscriptions;
 mod unique_argument_names;
+mod unique_directives_per_location;
 mod unique_fragment_names;
 mod unique_input_field_names;
 mod unique_operation_names;
 mod unique_variable_names;
 mod variables_are_input_types;
+mod variables_default_value_allowed;
 mod variables_in_allowed_position;
 
 use ast::Document;
-use validation::{ValidatorContext, MultiVisitor, visit};
+use validation::{ValidatorContext, MultiVisitor, Visitor, visit};
+
+/// The names of the validation rules from the specification, in the order
+/// they are run
+pub const SPEC_RULE_NAMES: &'static [&'static str] = &[
+    "ArgumentsOfCorrectType",
+    "DefaultValuesOfCorrectType",
+    "ExecutableDefinitions",
+    "FieldsOnCorrectType",
+    "FragmentsOnCompositeTypes",
+    "KnownArgumentNames",
+    "KnownDirectives",
+    "KnownFragmentNames",
+    "KnownTypeNames",
+    "LoneAnonymousOperation",
+    "NoFragmentCycles",
+    "NoUndefinedVariables",
+    "NoUnusedFragments",
+    "NoUnusedVariables",
+    "OverlappingFieldsCanBeMerged",
+    "PossibleFragmentSpreads",
+    "ProvidedNonNullArguments",
+    "ProvidedNonNullInputFields",
+    "ScalarLeafs",
+    "SingleFieldSubscriptions",
+    "UniqueArgumentNames",
+    "UniqueDirectivesPerLocation",
+    "UniqueFragmentNames",
+    "UniqueInputFieldNames",
+    "UniqueOperationNames",
+    "UniqueVariableNames",
+    "VariablesAreInputTypes",
+    "VariablesDefaultValueAllowed",
+    "VariablesInAllowedPosition",
+];
+
+/// The names of the rules from the specification that execution relies on
+///
+/// The executor assumes that every field, fragment and type condition in a
+/// document exists, that fragments do not form cycles, that object fields
+/// have a selection set, and that `@skip` and `@include` get a boolean `if`
+/// argument. Every `Validator` runs these rules, and they can not be removed
+/// or replaced.
+pub const EXECUTION_RULE_NAMES: &'static [&'static str] = &[
+    "ArgumentsOfCorrectType",
+    "FieldsOnCorrectType",
+    "FragmentsOnCompositeTypes",
+    "KnownFragmentNames",
+    "KnownTypeNames",
+    "NoFragmentCycles",
+    "NoUndefinedVariables",
+    "PossibleFragmentSpreads",
+    "ProvidedNonNullArguments",
+    "ScalarLeafs",
+];
+
+/// Create the visitor of a rule from the specification
+#[doc(hidden)]
+pub fn spec_rule<'a>(name: &str) -> Option<Box<Visitor<'a> + 'a>> {
+    let rule: Box<Visitor<'a> + 'a> = match name {
+        "ArgumentsOfCorrectType" => Box::new(self::arguments_of_correct_type::factory()),
+        "DefaultValuesOfCorrectType" => Box::new(self::default_values_of_correct_type::factory()),
+        "ExecutableDefinitions" => Box::new(self::executable_definitions::factory()),
+        "FieldsOnCorrectType" => Box::new(self::fields_on_correct_type::factory()),
+        "FragmentsOnCompositeTypes" => Box::new(self::fragments_on_composite_types::factory()),
+        "KnownArgumentNames" => Box::new(self::known_argument_names::factory()),
+        "KnownDirectives" => Box::new(self::known_directives::factory()),
+        "KnownFragmentNames" => Box::new(self::known_fragment_names::factory()),
+        "KnownTypeNames" => Box::new(self::known_type_names::factory()),
+        "LoneAnonymousOperation" => Box::new(self::lone_anonymous_operation::factory()),
+        "NoFragmentCycles" => Box::new(self::no_fragment_cycles::factory()),
+        "NoUndefinedVariables" => Box::new(self::no_undefined_variables::factory()),
+        "NoUnusedFragments" => Box::new(self::no_unused_fragments::factory()),
+        "NoUnusedVariables" => Box::new(self::no_unused_variables::factory()),
+        "OverlappingFieldsCanBeMerged" => Box::new(self::overlapping_fields_can_be_merged::factory()),
+        "PossibleFragmentSpreads" => Box::new(self::possible_fragment_spreads::factory()),
+        "ProvidedNonNullArguments" => Box::new(self::provided_non_null_arguments::factory()),
+        "ProvidedNonNullInputFields" => Box::new(self::provided_non_null_input_fields::factory()),
+        "ScalarLeafs" => Box::new(self::scalar_leafs::factory()),
+        "SingleFieldSubscriptions" => Box::new(self::single_field_subscriptions::factory()),
+        "UniqueArgumentNames" => Box::new(self::unique_argument_names::factory()),
+        "UniqueDirectivesPerLocation" => Box::new(self::unique_directives_per_location::factory()),
+        "UniqueFragmentNames" => Box::new(self::unique_fragment_names::factory()),
+        "UniqueInputFieldNames" => Box::new(self::unique_input_field_names::factory()),
+        "UniqueOperationNames" => Box::new(self::unique_operation_names::factory()),
+        "UniqueVariableNames" => Box::new(self::unique_variable_names::factory()),
+        "VariablesAreInputTypes" => Box::new(self::variables_are_input_types::factory()),
+        "VariablesDefaultValueAllowed" => Box::new(self::variables_default_value_allowed::factory()),
+        "VariablesInAllowedPosition" => Box::new(self::variables_in_allowed_position::factory()),
+        _ => return None,
+    };
+
+    Some(rule)
+}
 
 #[doc(hidden)]
 pub fn visit_all_rules<'a>(ctx: &mut ValidatorContext<'a>, doc: &'a Document) {
-    let mut mv = MultiVisitor::new(vec![
-        Box::new(self::arguments_of_correct_type::factory()),
-        Box::new(self::default_values_of_correct_type::factory()),
-        Box::new(self::fields_on_correct_type::factory()),
-        Box::new(self::fragments_on_composite_types::factory()),
-        Box::new(self::known_argument_names::factory()),
-        Box::new(self::known_directives::factory()),
-        Box::new(self::known_fragment_names::factory()),
-        Box::new(self::known_type_names::factory()),
-        Box::new(self::lone_anonymous_operation::factory()),
-        Box::new(self::no_fragment_cycles::factory()),
-        Box::new(self::no_undefined_variables::factory()),
-        Box::new(self::no_unused_fragments::factory()),
-        Box::new(self::no_unused_variables::factory()),
-        Box::new(self::overlapping_fields_can_be_merged::factory()),
-        Box::new(self::possible_fragment_spreads::factory()),
-        Box::new(self::provided_non_null_arguments::factory()),
-        Box::new(self::scalar_leafs::factory()),
-        Box::new(self::unique_argument_names::factory()),
-        Box::new(self::unique_fragment_names::factory()),
-        Box::new(self::unique_input_field_names::factory()),
-        Box::new(self::unique_operation_names::factory()),
-        Box::new(self::unique_variable_names::factory()),
-        Box::new(self::variables_are_input_types::factory()),
-        Box::new(self::variables_in_allowed_position::factory()),
-    ]);
+    let mut mv = MultiVisitor::new(SPEC_RULE_NAMES.iter()
+        .filter_map(|name| spec_rule(name))
+        .collect());
 
     visit(&mut mv, ctx, doc);
 }
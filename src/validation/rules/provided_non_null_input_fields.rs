@@ -0,0 +1,154 @@
+use ast::InputValue;
+use validation::{ValidatorContext, Visitor};
+use parser::Spanning;
+use schema::meta::{MetaType, InputObjectMeta};
+
+pub struct ProvidedNonNullInputFields {
+}
+
+pub fn factory() -> ProvidedNonNullInputFields {
+    ProvidedNonNullInputFields {}
+}
+
+impl<'a> Visitor<'a> for ProvidedNonNullInputFields {
+    fn enter_object_value(&mut self, ctx: &mut ValidatorContext<'a>, object: Spanning<&'a Vec<(Spanning<String>, Spanning<InputValue>)>>) {
+        let meta_type = ctx.current_input_type_literal()
+            .and_then(|t| t.name())
+            .and_then(|name| ctx.schema.concrete_type_by_name(name));
+
+        if let Some(&MetaType::InputObject(InputObjectMeta { ref name, ref input_fields, .. })) = meta_type {
+            for meta_field in input_fields {
+                if meta_field.arg_type.is_non_null()
+                    && meta_field.default_value.is_none()
+                    && !object.item.iter().any(|&(ref field_name, _)| field_name.item == meta_field.name)
+                {
+                    ctx.report_error(
+                        &error_message(name, &meta_field.name, &format!("{}", meta_field.arg_type)),
+                        &[object.start.clone()]);
+                }
+            }
+        }
+    }
+}
+
+fn error_message(type_name: &str, field_name: &str, field_type_name: &str) -> String {
+    format!(
+        r#"Field "{}.{}" of required type "{}" was not provided"#,
+        type_name, field_name, field_type_name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{error_message, factory};
+
+    use parser::SourcePosition;
+    use validation::{RuleError, expect_passes_rule, expect_fails_rule};
+
+    #[test]
+    fn partial_object_only_required() {
+        expect_passes_rule(factory, r#"
+          {
+            complicatedArgs {
+              complexArgField(complexArg: { requiredField: true })
+            }
+          }
+        "#);
+    }
+
+    #[test]
+    fn partial_object_required_field_can_be_falsey() {
+        expect_passes_rule(factory, r#"
+          {
+            complicatedArgs {
+              complexArgField(complexArg: { requiredField: false })
+            }
+          }
+        "#);
+    }
+
+    #[test]
+    fn partial_object_including_required() {
+        expect_passes_rule(factory, r#"
+          {
+            complicatedArgs {
+              complexArgField(complexArg: { requiredField: true, intField: 4 })
+            }
+          }
+        "#);
+    }
+
+    #[test]
+    fn full_object() {
+        expect_passes_rule(factory, r#"
+          {
+            complicatedArgs {
+              complexArgField(complexArg: {
+                requiredField: true,
+                intField: 4,
+                stringField: "foo",
+                booleanField: false,
+                stringListField: ["one", "two"]
+              })
+            }
+          }
+        "#);
+    }
+
+    #[test]
+    fn ignores_objects_of_other_types() {
+        expect_passes_rule(factory, r#"
+          {
+            dog {
+              isHousetrained(atOtherHomes: {})
+            }
+            unknownField(unknownArg: { requiredField: true })
+          }
+        "#);
+    }
+
+    #[test]
+    fn partial_object_missing_required() {
+        expect_fails_rule(factory, r#"
+          {
+            complicatedArgs {
+              complexArgField(complexArg: { intField: 4 })
+            }
+          }
+        "#,
+            &[
+                RuleError::new(&error_message("ComplexInput", "requiredField", "Boolean!"), &[
+                    SourcePosition::new(85, 3, 42),
+                ]),
+            ]);
+    }
+
+    #[test]
+    fn empty_object_missing_required() {
+        expect_fails_rule(factory, r#"
+          {
+            complicatedArgs {
+              complexArgField(complexArg: {})
+            }
+          }
+        "#,
+            &[
+                RuleError::new(&error_message("ComplexInput", "requiredField", "Boolean!"), &[
+                    SourcePosition::new(85, 3, 42),
+                ]),
+            ]);
+    }
+
+    #[test]
+    fn default_value_missing_required() {
+        expect_fails_rule(factory, r#"
+          query MissingRequiredField($a: ComplexInput = { intField: 3 }) {
+            dog { name }
+          }
+        "#,
+            &[
+                RuleError::new(&error_message("ComplexInput", "requiredField", "Boolean!"), &[
+                    SourcePosition::new(57, 1, 56),
+                ]),
+            ]);
+    }
+}
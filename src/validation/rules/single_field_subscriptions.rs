@@ -0,0 +1,197 @@
+use std::collections::HashMap;
+
+use ast::{Definition, Document, Fragment, Operation, OperationType, Selection};
+use validation::{ValidatorContext, Visitor};
+use parser::{SourcePosition, Spanning};
+
+pub struct SingleFieldSubscriptions<'a> {
+    fragments: HashMap<&'a str, &'a Fragment>,
+}
+
+pub fn factory<'a>() -> SingleFieldSubscriptions<'a> {
+    SingleFieldSubscriptions {
+        fragments: HashMap::new(),
+    }
+}
+
+impl<'a> Visitor<'a> for SingleFieldSubscriptions<'a> {
+    fn enter_document(&mut self, _: &mut ValidatorContext<'a>, doc: &'a Document) {
+        self.fragments = doc
+            .iter()
+            .filter_map(|d| match *d {
+                Definition::Fragment(ref f) => Some((&f.item.name.item as &str, &f.item)),
+                _ => None,
+            })
+            .collect();
+    }
+
+    fn enter_operation_definition(&mut self, ctx: &mut ValidatorContext<'a>, op: &'a Spanning<Operation>) {
+        if op.item.operation_type != OperationType::Subscription {
+            return;
+        }
+
+        let mut fields = Vec::new();
+        self.collect_fields(&op.item.selection_set, &mut Vec::new(), &mut fields);
+
+        if fields.len() > 1 {
+            let positions = fields[1..].iter()
+                .map(|&(_, pos)| pos.clone())
+                .collect::<Vec<_>>();
+
+            ctx.report_error(
+                &error_message(op.item.name.as_ref().map(|s| &s.item[..])),
+                &positions);
+        }
+    }
+}
+
+impl<'a> SingleFieldSubscriptions<'a> {
+    /// Collect the first field of every response name, expanding fragments
+    fn collect_fields(
+        &self,
+        selection_set: &'a [Selection],
+        visited_fragments: &mut Vec<&'a str>,
+        fields: &mut Vec<(&'a str, &'a SourcePosition)>,
+    ) {
+        for selection in selection_set {
+            match *selection {
+                Selection::Field(ref field) => {
+                    let response_name = &field.item.alias.as_ref().unwrap_or(&field.item.name).item as &str;
+
+                    if !fields.iter().any(|&(name, _)| name == response_name) {
+                        fields.push((response_name, &field.start));
+                    }
+                }
+                Selection::FragmentSpread(ref spread) => {
+                    let name = &spread.item.name.item as &str;
+
+                    if let Some(fragment) = self.fragments.get(name) {
+                        if !visited_fragments.contains(&name) {
+                            visited_fragments.push(name);
+                            self.collect_fields(&fragment.selection_set, visited_fragments, fields);
+                            visited_fragments.pop();
+                        }
+                    }
+                }
+                Selection::InlineFragment(ref fragment) =>
+                    self.collect_fields(&fragment.item.selection_set, visited_fragments, fields),
+            }
+        }
+    }
+}
+
+fn error_message(op_name: Option<&str>) -> String {
+    match op_name {
+        Some(name) => format!(r#"Subscription "{}" must select only one top level field"#, name),
+        None => "Anonymous Subscription must select only one top level field".to_owned(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{error_message, factory};
+
+    use parser::SourcePosition;
+    use validation::{RuleError, expect_passes_rule, expect_fails_rule};
+
+    #[test]
+    fn valid_subscription() {
+        expect_passes_rule(factory, r#"
+          subscription ImportantEmails {
+            importantEmails
+          }
+        "#);
+    }
+
+    #[test]
+    fn fails_with_more_than_one_root_field() {
+        expect_fails_rule(factory, r#"
+          subscription ImportantEmails {
+            importantEmails
+            notImportantEmails
+          }
+        "#,
+            &[
+                RuleError::new(&error_message(Some("ImportantEmails")), &[
+                    SourcePosition::new(82, 3, 12),
+                ]),
+            ]);
+    }
+
+    #[test]
+    fn fails_with_more_than_one_root_field_including_introspection() {
+        expect_fails_rule(factory, r#"
+          subscription ImportantEmails {
+            importantEmails
+            __typename
+          }
+        "#,
+            &[
+                RuleError::new(&error_message(Some("ImportantEmails")), &[
+                    SourcePosition::new(82, 3, 12),
+                ]),
+            ]);
+    }
+
+    #[test]
+    fn fails_with_many_more_than_one_root_field() {
+        expect_fails_rule(factory, r#"
+          subscription ImportantEmails {
+            importantEmails
+            notImportantEmails
+            spamEmails
+          }
+        "#,
+            &[
+                RuleError::new(&error_message(Some("ImportantEmails")), &[
+                    SourcePosition::new(82, 3, 12),
+                    SourcePosition::new(113, 4, 12),
+                ]),
+            ]);
+    }
+
+    #[test]
+    fn fails_with_more_than_one_root_field_in_anonymous_subscriptions() {
+        expect_fails_rule(factory, r#"
+          subscription {
+            importantEmails
+            notImportantEmails
+          }
+        "#,
+            &[
+                RuleError::new(&error_message(None), &[
+                    SourcePosition::new(66, 3, 12),
+                ]),
+            ]);
+    }
+
+    #[test]
+    fn same_field_selected_twice() {
+        expect_passes_rule(factory, r#"
+          subscription ImportantEmails {
+            importantEmails
+            importantEmails
+          }
+        "#);
+    }
+
+    #[test]
+    fn fails_with_more_than_one_root_field_in_fragments() {
+        expect_fails_rule(factory, r#"
+          subscription ImportantEmails {
+            ...Emails
+          }
+          fragment Emails on SubscriptionRoot {
+            importantEmails
+            ... on SubscriptionRoot {
+              notImportantEmails
+            }
+          }
+        "#,
+            &[
+                RuleError::new(&error_message(Some("ImportantEmails")), &[
+                    SourcePosition::new(204, 7, 14),
+                ]),
+            ]);
+    }
+}
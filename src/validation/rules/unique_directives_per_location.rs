@@ -0,0 +1,183 @@
+use std::collections::hash_map::{HashMap, Entry};
+
+use ast::{Directive, Field, Fragment, FragmentSpread, InlineFragment, Operation};
+use validation::{ValidatorContext, Visitor};
+use parser::{SourcePosition, Spanning};
+
+pub struct UniqueDirectivesPerLocation {
+}
+
+pub fn factory() -> UniqueDirectivesPerLocation {
+    UniqueDirectivesPerLocation {}
+}
+
+impl<'a> Visitor<'a> for UniqueDirectivesPerLocation {
+    fn enter_operation_definition(&mut self, ctx: &mut ValidatorContext<'a>, op: &'a Spanning<Operation>) {
+        check_directives(ctx, &op.item.directives);
+    }
+
+    fn enter_fragment_definition(&mut self, ctx: &mut ValidatorContext<'a>, f: &'a Spanning<Fragment>) {
+        check_directives(ctx, &f.item.directives);
+    }
+
+    fn enter_field(&mut self, ctx: &mut ValidatorContext<'a>, field: &'a Spanning<Field>) {
+        check_directives(ctx, &field.item.directives);
+    }
+
+    fn enter_fragment_spread(&mut self, ctx: &mut ValidatorContext<'a>, spread: &'a Spanning<FragmentSpread>) {
+        check_directives(ctx, &spread.item.directives);
+    }
+
+    fn enter_inline_fragment(&mut self, ctx: &mut ValidatorContext<'a>, f: &'a Spanning<InlineFragment>) {
+        check_directives(ctx, &f.item.directives);
+    }
+}
+
+fn check_directives(ctx: &mut ValidatorContext, directives: &Option<Vec<Spanning<Directive>>>) {
+    if let Some(ref directives) = *directives {
+        let mut known_directives: HashMap<&str, SourcePosition> = HashMap::new();
+
+        for directive in directives {
+            match known_directives.entry(&directive.item.name.item) {
+                Entry::Occupied(e) => {
+                    ctx.report_error(
+                        &error_message(&directive.item.name.item),
+                        &[e.get().clone(), directive.start.clone()]);
+                }
+                Entry::Vacant(e) => {
+                    e.insert(directive.start.clone());
+                }
+            }
+        }
+    }
+}
+
+fn error_message(directive_name: &str) -> String {
+    format!(r#"The directive "{}" can only be used once at this location"#, directive_name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{error_message, factory};
+
+    use parser::SourcePosition;
+    use validation::{RuleError, expect_passes_rule, expect_fails_rule};
+
+    #[test]
+    fn no_directives() {
+        expect_passes_rule(factory, r#"
+          fragment Test on Type {
+            field
+          }
+        "#);
+    }
+
+    #[test]
+    fn unique_directives_in_different_locations() {
+        expect_passes_rule(factory, r#"
+          fragment Test on Type @directiveA {
+            field @directiveB
+          }
+        "#);
+    }
+
+    #[test]
+    fn unique_directives_in_same_locations() {
+        expect_passes_rule(factory, r#"
+          fragment Test on Type @directiveA @directiveB {
+            field @directiveA @directiveB
+          }
+        "#);
+    }
+
+    #[test]
+    fn same_directives_in_different_locations() {
+        expect_passes_rule(factory, r#"
+          fragment Test on Type @directiveA {
+            field @directiveA
+          }
+        "#);
+    }
+
+    #[test]
+    fn same_directives_in_similar_locations() {
+        expect_passes_rule(factory, r#"
+          fragment Test on Type {
+            field @directive
+            field @directive
+          }
+        "#);
+    }
+
+    #[test]
+    fn duplicate_directives_in_one_location() {
+        expect_fails_rule(factory, r#"
+          fragment Test on Type {
+            field @directive @directive
+          }
+        "#,
+            &[
+                RuleError::new(&error_message("directive"), &[
+                    SourcePosition::new(53, 2, 18),
+                    SourcePosition::new(64, 2, 29),
+                ]),
+            ]);
+    }
+
+    #[test]
+    fn many_duplicate_directives_in_one_location() {
+        expect_fails_rule(factory, r#"
+          fragment Test on Type {
+            field @directive @directive @directive
+          }
+        "#,
+            &[
+                RuleError::new(&error_message("directive"), &[
+                    SourcePosition::new(53, 2, 18),
+                    SourcePosition::new(64, 2, 29),
+                ]),
+                RuleError::new(&error_message("directive"), &[
+                    SourcePosition::new(53, 2, 18),
+                    SourcePosition::new(75, 2, 40),
+                ]),
+            ]);
+    }
+
+    #[test]
+    fn different_duplicate_directives_in_one_location() {
+        expect_fails_rule(factory, r#"
+          fragment Test on Type {
+            field @directiveA @directiveB @directiveA @directiveB
+          }
+        "#,
+            &[
+                RuleError::new(&error_message("directiveA"), &[
+                    SourcePosition::new(53, 2, 18),
+                    SourcePosition::new(77, 2, 42),
+                ]),
+                RuleError::new(&error_message("directiveB"), &[
+                    SourcePosition::new(65, 2, 30),
+                    SourcePosition::new(89, 2, 54),
+                ]),
+            ]);
+    }
+
+    #[test]
+    fn duplicate_directives_in_many_locations() {
+        expect_fails_rule(factory, r#"
+          fragment Test on Type @directive @directive {
+            field @directive @directive
+          }
+        "#,
+            &[
+                RuleError::new(&error_message("directive"), &[
+                    SourcePosition::new(33, 1, 32),
+                    SourcePosition::new(44, 1, 43),
+                ]),
+                RuleError::new(&error_message("directive"), &[
+                    SourcePosition::new(75, 2, 18),
+                    SourcePosition::new(86, 2, 29),
+                ]),
+            ]);
+    }
+}
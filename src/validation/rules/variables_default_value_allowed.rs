@@ -0,0 +1,105 @@
+use ast::{Type, VariableDefinition};
+use validation::{ValidatorContext, Visitor};
+use parser::Spanning;
+
+pub struct VariablesDefaultValueAllowed {
+}
+
+pub fn factory() -> VariablesDefaultValueAllowed {
+    VariablesDefaultValueAllowed {}
+}
+
+impl<'a> Visitor<'a> for VariablesDefaultValueAllowed {
+    fn enter_variable_definition(&mut self, ctx: &mut ValidatorContext<'a>, &(ref var_name, ref var_def): &'a (Spanning<String>, VariableDefinition)) {
+        if let Some(Spanning { ref start, .. }) = var_def.default_value {
+            let nullable_type = match var_def.var_type.item {
+                Type::NonNullNamed(ref name) => Type::Named(name.clone()),
+                Type::NonNullList(ref inner) => Type::List(inner.clone()),
+                _ => return,
+            };
+
+            ctx.report_error(
+                &error_message(
+                    &var_name.item,
+                    &format!("{}", var_def.var_type.item),
+                    &format!("{}", nullable_type)),
+                &[start.clone()]);
+        }
+    }
+}
+
+fn error_message(var_name: &str, type_name: &str, guessed_type_name: &str) -> String {
+    format!(
+        r#"Variable "${}" of type "{}" is required and will not use the default value. Perhaps you meant to use type "{}""#,
+        var_name, type_name, guessed_type_name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{error_message, factory};
+
+    use parser::SourcePosition;
+    use validation::{RuleError, expect_passes_rule, expect_fails_rule};
+
+    #[test]
+    fn variables_with_no_default_values() {
+        expect_passes_rule(factory, r#"
+          query NullableValues($a: Int, $b: String, $c: ComplexInput) {
+            dog { name }
+          }
+        "#);
+    }
+
+    #[test]
+    fn required_variables_without_default_values() {
+        expect_passes_rule(factory, r#"
+          query RequiredValues($a: Int!, $b: String!) {
+            dog { name }
+          }
+        "#);
+    }
+
+    #[test]
+    fn variables_with_valid_default_values() {
+        expect_passes_rule(factory, r#"
+          query WithDefaultValues(
+            $a: Int = 1,
+            $b: String = "ok",
+            $c: ComplexInput = { requiredField: true, intField: 3 }
+          ) {
+            dog { name }
+          }
+        "#);
+    }
+
+    #[test]
+    fn no_required_variables_with_default_values() {
+        expect_fails_rule(factory, r#"
+          query UnreachableDefaultValues($a: Int! = 3, $b: String! = "default") {
+            dog { name }
+          }
+        "#,
+            &[
+                RuleError::new(&error_message("a", "Int!", "Int"), &[
+                    SourcePosition::new(53, 1, 52),
+                ]),
+                RuleError::new(&error_message("b", "String!", "String"), &[
+                    SourcePosition::new(70, 1, 69),
+                ]),
+            ]);
+    }
+
+    #[test]
+    fn no_required_list_variables_with_default_values() {
+        expect_fails_rule(factory, r#"
+          query UnreachableDefaultValues($a: [String]! = ["default"]) {
+            dog { name }
+          }
+        "#,
+            &[
+                RuleError::new(&error_message("a", "[String]!", "[String]"), &[
+                    SourcePosition::new(58, 1, 57),
+                ]),
+            ]);
+    }
+}
@@ -0,0 +1,134 @@
+use std::cmp::min;
+
+/// The maximum number of suggestions included in a message
+const MAX_SUGGESTIONS: usize = 5;
+
+/// Find the options that are similar enough to `input` to be suggested
+///
+/// Options are sorted by their distance to the input, then by name.
+pub fn suggestion_list<'a, I>(input: &str, options: I) -> Vec<&'a str>
+    where I: IntoIterator<Item = &'a str>
+{
+    let threshold = input.chars().count() * 2 / 5 + 1;
+
+    let mut suggestions = options.into_iter()
+        .filter_map(|option| {
+            let distance = lexical_distance(input, option);
+
+            if distance <= threshold {
+                Some((distance, option))
+            }
+            else {
+                None
+            }
+        })
+        .collect::<Vec<_>>();
+
+    suggestions.sort();
+    suggestions.dedup();
+
+    suggestions.into_iter().map(|(_, option)| option).collect()
+}
+
+/// Append a "Did you mean" hint listing the suggestions to a message
+///
+/// The message is returned unchanged if there are no suggestions.
+pub fn did_you_mean(message: String, sub_message: Option<&str>, suggestions: &[&str]) -> String {
+    if suggestions.is_empty() {
+        return message;
+    }
+
+    let quoted = suggestions.iter()
+        .take(MAX_SUGGESTIONS)
+        .map(|s| format!("\"{}\"", s))
+        .collect::<Vec<_>>();
+
+    let list = match quoted.len() {
+        1 => quoted[0].clone(),
+        2 => format!("{} or {}", quoted[0], quoted[1]),
+        n => format!("{}, or {}", quoted[..n - 1].join(", "), quoted[n - 1]),
+    };
+
+    match sub_message {
+        Some(sub_message) => format!("{}. Did you mean {} {}?", message, sub_message, list),
+        None => format!("{}. Did you mean {}?", message, list),
+    }
+}
+
+/// The optimal string alignment distance between two strings
+///
+/// Strings that only differ in case are at distance one.
+fn lexical_distance(a: &str, b: &str) -> usize {
+    if a == b {
+        return 0;
+    }
+
+    let a = a.to_lowercase().chars().collect::<Vec<_>>();
+    let b = b.to_lowercase().chars().collect::<Vec<_>>();
+
+    if a == b {
+        return 1;
+    }
+
+    let mut d = vec![vec![0; b.len() + 1]; a.len() + 1];
+
+    for i in 0..a.len() + 1 {
+        d[i][0] = i;
+    }
+
+    for j in 0..b.len() + 1 {
+        d[0][j] = j;
+    }
+
+    for i in 1..a.len() + 1 {
+        for j in 1..b.len() + 1 {
+            let cost = if a[i - 1] == b[j - 1] { 0 } else { 1 };
+
+            d[i][j] = min(min(d[i - 1][j] + 1, d[i][j - 1] + 1), d[i - 1][j - 1] + cost);
+
+            if i > 1 && j > 1 && a[i - 1] == b[j - 2] && a[i - 2] == b[j - 1] {
+                d[i][j] = min(d[i][j], d[i - 2][j - 2] + cost);
+            }
+        }
+    }
+
+    d[a.len()][b.len()]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{suggestion_list, did_you_mean, lexical_distance};
+
+    #[test]
+    fn distance_counts_edits() {
+        assert_eq!(lexical_distance("friends", "friends"), 0);
+        assert_eq!(lexical_distance("friends", "Friends"), 1);
+        assert_eq!(lexical_distance("friends", "freinds"), 1);
+        assert_eq!(lexical_distance("friends", "friendIds"), 2);
+        assert_eq!(lexical_distance("meowVolume", "barkVolume"), 4);
+    }
+
+    #[test]
+    fn suggestions_are_sorted_by_distance() {
+        assert_eq!(
+            suggestion_list("friend", vec!["name", "friendIds", "friends", "id"]),
+            vec!["friends", "friendIds"]);
+        assert_eq!(suggestion_list("a", vec!["b", "c", "def"]), vec!["b", "c"]);
+        assert_eq!(suggestion_list("name", vec!["other"]), Vec::<&str>::new());
+    }
+
+    #[test]
+    fn message_lists_suggestions() {
+        assert_eq!(did_you_mean("Unknown".to_owned(), None, &[]), "Unknown");
+        assert_eq!(did_you_mean("Unknown".to_owned(), None, &["a"]), r#"Unknown. Did you mean "a"?"#);
+        assert_eq!(
+            did_you_mean("Unknown".to_owned(), None, &["a", "b"]),
+            r#"Unknown. Did you mean "a" or "b"?"#);
+        assert_eq!(
+            did_you_mean("Unknown".to_owned(), Some("to use an inline fragment on"), &["a", "b", "c"]),
+            r#"Unknown. Did you mean to use an inline fragment on "a", "b", or "c"?"#);
+        assert_eq!(
+            did_you_mean("Unknown".to_owned(), None, &["a", "b", "c", "d", "e", "f"]),
+            r#"Unknown. Did you mean "a", "b", "c", "d", or "e"?"#);
+    }
+}
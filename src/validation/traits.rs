@@ -3,8 +3,12 @@ use ast::{Document, Operation, Fragment, VariableDefinition, Selection,
 use parser::Spanning;
 use validation::ValidatorContext;
 
-
-#[doc(hidden)]
+/// A pass over a query document
+///
+/// Validation rules implement the `enter_` and `exit_` methods for the parts
+/// of the document they check, and report problems through the context. All
+/// methods do nothing by default.
+#[allow(missing_docs)]
 pub trait Visitor<'a> {
     fn enter_document(&mut self, _: &mut ValidatorContext<'a>, _: &'a Document) {}
     fn exit_document(&mut self, _: &mut ValidatorContext<'a>, _: &'a Document) {}
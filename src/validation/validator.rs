@@ -0,0 +1,324 @@
+use ast::Document;
+use schema::model::SchemaType;
+use validation::{RuleError, ValidatorContext, MultiVisitor, Visitor, visit};
+use validation::rules::{SPEC_RULE_NAMES, EXECUTION_RULE_NAMES, spec_rule};
+
+/// A validation rule that can be added to a `Validator`
+///
+/// The rule creates a fresh visitor for every document that is validated.
+/// Any function or closure returning a `Visitor` implements this trait, so a
+/// rule is usually added by passing the constructor of its visitor.
+pub trait ValidationRule: Send + Sync {
+    /// Create the visitor that checks a single document
+    fn visitor<'a>(&self) -> Box<Visitor<'a> + 'a>;
+}
+
+impl<F, V> ValidationRule for F
+    where F: Fn() -> V + Send + Sync,
+          V: for<'a> Visitor<'a> + 'static,
+{
+    fn visitor<'a>(&self) -> Box<Visitor<'a> + 'a> {
+        Box::new(self())
+    }
+}
+
+struct SpecRule(&'static str);
+
+impl ValidationRule for SpecRule {
+    fn visitor<'a>(&self) -> Box<Visitor<'a> + 'a> {
+        spec_rule(self.0).expect("Unknown specification rule")
+    }
+}
+
+/// A configurable set of validation rules
+///
+/// `Validator::new` starts out with the rules from the specification, listed
+/// in `SPEC_RULE_NAMES`. Rules are identified by name, so that built-in rules
+/// can be removed or replaced:
+///
+/// ```rust
+/// use juniper::Operation;
+/// use juniper::parser::Spanning;
+/// use juniper::validation::{Validator, ValidatorContext, Visitor};
+///
+/// struct NamedOperations;
+///
+/// impl<'a> Visitor<'a> for NamedOperations {
+///     fn enter_operation_definition(&mut self, ctx: &mut ValidatorContext<'a>, op: &'a Spanning<Operation>) {
+///         if op.item.name.is_none() {
+///             ctx.report_error("Operations must be named", &[op.start.clone()]);
+///         }
+///     }
+/// }
+///
+/// let validator = Validator::new()
+///     .remove_rule("NoUnusedFragments")
+///     .add_rule("NamedOperations", || NamedOperations);
+/// ```
+///
+/// The rules listed in `EXECUTION_RULE_NAMES` are part of every validator,
+/// since the executor relies on them to reject documents it can not run.
+///
+/// Attach it to a schema with `RootNode::with_validator` to use it when
+/// executing queries.
+pub struct Validator {
+    rules: Vec<(String, Box<ValidationRule>)>,
+}
+
+fn spec_rules(names: &[&'static str]) -> Vec<(String, Box<ValidationRule>)> {
+    names.iter()
+        .map(|name| ((*name).to_owned(), Box::new(SpecRule(name)) as Box<ValidationRule>))
+        .collect()
+}
+
+fn assert_not_execution_rule(name: &str) {
+    if EXECUTION_RULE_NAMES.contains(&name) {
+        panic!("The {} rule is needed for execution and can not be removed or replaced", name);
+    }
+}
+
+impl Validator {
+    /// Create a validator with all rules from the specification
+    pub fn new() -> Validator {
+        Validator {
+            rules: spec_rules(SPEC_RULE_NAMES),
+        }
+    }
+
+    /// Create a validator with only the rules execution relies on
+    pub fn empty() -> Validator {
+        Validator {
+            rules: spec_rules(EXECUTION_RULE_NAMES),
+        }
+    }
+
+    /// Add a rule to run after all existing rules
+    ///
+    /// A rule with the same name is replaced.
+    ///
+    /// # Panics
+    /// Panics if the name is one of `EXECUTION_RULE_NAMES`.
+    pub fn add_rule<R>(self, name: &str, rule: R) -> Validator
+        where R: ValidationRule + 'static
+    {
+        let mut validator = self.remove_rule(name);
+        validator.rules.push((name.to_owned(), Box::new(rule)));
+        validator
+    }
+
+    /// Replace the rule with a name, keeping its position
+    ///
+    /// If there is no such rule, the new rule is added after all others.
+    ///
+    /// # Panics
+    /// Panics if the name is one of `EXECUTION_RULE_NAMES`.
+    pub fn replace_rule<R>(mut self, name: &str, rule: R) -> Validator
+        where R: ValidationRule + 'static
+    {
+        assert_not_execution_rule(name);
+
+        match self.rules.iter().position(|&(ref n, _)| n == name) {
+            Some(index) => {
+                self.rules[index].1 = Box::new(rule);
+                self
+            }
+            None => self.add_rule(name, rule),
+        }
+    }
+
+    /// Remove the rule with a name, if there is one
+    ///
+    /// # Panics
+    /// Panics if the name is one of `EXECUTION_RULE_NAMES`.
+    pub fn remove_rule(mut self, name: &str) -> Validator {
+        assert_not_execution_rule(name);
+
+        self.rules.retain(|&(ref n, _)| n != name);
+        self
+    }
+
+    /// The names of the rules in the order they are run
+    pub fn rule_names(&self) -> Vec<&str> {
+        self.rules.iter().map(|&(ref n, _)| n as &str).collect()
+    }
+
+    /// Validate a document against a schema, returning all errors found
+    pub fn validate<'a>(&self, schema: &'a SchemaType, document: &'a Document) -> Vec<RuleError> {
+        let mut ctx = ValidatorContext::new(schema, document);
+        let mut mv = MultiVisitor::new(self.rules.iter()
+            .map(|&(_, ref rule)| rule.visitor())
+            .collect());
+
+        visit(&mut mv, &mut ctx, document);
+
+        ctx.into_errors()
+    }
+}
+
+impl Default for Validator {
+    fn default() -> Validator {
+        Validator::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use ast::{Operation, OperationType, Field};
+    use parser::{parse_document_source, Spanning, SourcePosition};
+    use schema::model::RootNode;
+    use tests::model::Database;
+    use validation::{RuleError, ValidatorContext, Visitor, SPEC_RULE_NAMES, EXECUTION_RULE_NAMES};
+    use ::GraphQLError;
+    use super::Validator;
+
+    struct NoIntrospection;
+
+    impl<'a> Visitor<'a> for NoIntrospection {
+        fn enter_field(&mut self, ctx: &mut ValidatorContext<'a>, field: &'a Spanning<Field>) {
+            let name = &field.item.name.item;
+
+            if name == "__schema" || name == "__type" {
+                ctx.report_error("Introspection is disabled", &[field.start.clone()]);
+            }
+        }
+    }
+
+    struct MutationNames;
+
+    impl<'a> Visitor<'a> for MutationNames {
+        fn enter_operation_definition(&mut self, ctx: &mut ValidatorContext<'a>, op: &'a Spanning<Operation>) {
+            if op.item.operation_type != OperationType::Mutation {
+                return;
+            }
+
+            match op.item.name {
+                Some(ref name) if name.item.ends_with("Mutation") => {}
+                _ => ctx.report_error("Mutation names must end in Mutation", &[op.start.clone()]),
+            }
+        }
+    }
+
+    fn validate(validator: &Validator, query: &str) -> Vec<RuleError> {
+        let database = Database::new();
+        let schema = RootNode::new(&database, ());
+        let document = parse_document_source(query).expect("Parse error");
+
+        validator.validate(&schema.schema, &document)
+    }
+
+    #[test]
+    fn runs_spec_rules_by_default() {
+        assert_eq!(Validator::new().rule_names(), SPEC_RULE_NAMES.to_vec());
+
+        assert_eq!(
+            validate(&Validator::new(), "{ hero { name } } fragment Unused on Character { name }"),
+            vec![
+                RuleError::new(r#"Fragment "Unused" is never used"#, &[
+                    SourcePosition::new(18, 0, 18),
+                ]),
+            ]);
+    }
+
+    #[test]
+    fn spec_rules_can_be_removed() {
+        let validator = Validator::new().remove_rule("NoUnusedFragments");
+
+        assert!(!validator.rule_names().contains(&"NoUnusedFragments"));
+        assert_eq!(
+            validate(&validator, "{ hero { name } } fragment Unused on Character { name }"),
+            vec![]);
+    }
+
+    #[test]
+    fn custom_rules_are_run_after_spec_rules() {
+        let validator = Validator::new()
+            .add_rule("NoIntrospection", || NoIntrospection);
+
+        assert_eq!(validator.rule_names().last(), Some(&"NoIntrospection"));
+        assert_eq!(
+            validate(&validator, "{ hero { name } __schema { queryType { name } } }"),
+            vec![
+                RuleError::new("Introspection is disabled", &[
+                    SourcePosition::new(16, 0, 16),
+                ]),
+            ]);
+    }
+
+    #[test]
+    fn rules_can_be_replaced_in_place() {
+        let validator = Validator::new()
+            .replace_rule("NoUnusedFragments", || NoIntrospection);
+
+        assert_eq!(validator.rule_names(), SPEC_RULE_NAMES.to_vec());
+        assert_eq!(
+            validate(&validator, "{ __schema { queryType { name } } } fragment Unused on Character { name }"),
+            vec![
+                RuleError::new("Introspection is disabled", &[
+                    SourcePosition::new(2, 0, 2),
+                ]),
+            ]);
+    }
+
+    #[test]
+    fn custom_rules_can_check_operations() {
+        let validator = Validator::empty()
+            .add_rule("MutationNames", || MutationNames);
+
+        assert_eq!(
+            validate(&validator, "mutation Rename { rename }"),
+            vec![
+                RuleError::new("Mutation names must end in Mutation", &[
+                    SourcePosition::new(0, 0, 0),
+                ]),
+            ]);
+        assert_eq!(validate(&validator, "mutation RenameMutation { rename }"), vec![]);
+        assert_eq!(validate(&validator, "query Rename { hero { name } }"), vec![]);
+    }
+
+    #[test]
+    fn empty_validator_only_runs_execution_rules() {
+        assert_eq!(Validator::empty().rule_names(), EXECUTION_RULE_NAMES.to_vec());
+
+        assert_eq!(
+            validate(&Validator::empty(), "{ hero { name } } fragment Unused on Character { name }"),
+            vec![]);
+        assert_eq!(
+            validate(&Validator::empty(), "{ unknown { field } }"),
+            vec![
+                RuleError::new(r#"Unknown field "unknown" on type "Query""#, &[
+                    SourcePosition::new(2, 0, 2),
+                ]),
+            ]);
+    }
+
+    #[test]
+    #[should_panic(expected = "The FieldsOnCorrectType rule is needed for execution")]
+    fn execution_rules_can_not_be_removed() {
+        Validator::new().remove_rule("FieldsOnCorrectType");
+    }
+
+    #[test]
+    #[should_panic(expected = "The KnownFragmentNames rule is needed for execution")]
+    fn execution_rules_can_not_be_replaced() {
+        Validator::new().replace_rule("KnownFragmentNames", || NoIntrospection);
+    }
+
+    #[test]
+    fn execute_uses_the_schema_validator() {
+        let database = Database::new();
+        let schema = RootNode::new(&database, ())
+            .with_validator(Validator::new().add_rule("NoIntrospection", || NoIntrospection));
+
+        assert_eq!(
+            ::execute("{ __type(name: \"Droid\") { name } }", None, &schema, &HashMap::new(), &database),
+            Err(GraphQLError::ValidationError(vec![
+                RuleError::new("Introspection is disabled", &[
+                    SourcePosition::new(2, 0, 2),
+                ]),
+            ])));
+
+        assert!(::execute("{ hero { name } }", None, &schema, &HashMap::new(), &database).is_ok());
+    }
+}
@@ -25,6 +25,11 @@ fn visit_definitions<'a, V: Visitor<'a>>(v: &mut V, ctx: &mut ValidatorContext<'
                         item: Operation { operation_type: OperationType::Mutation, .. }, .. }) =>
                     ctx.schema.concrete_mutation_type()
                         .map(|t| Type::NonNullNamed(t.name().unwrap().to_owned())),
+                Definition::Operation(Spanning {
+                        item: Operation { operation_type: OperationType::Subscription, .. }, .. }) =>
+                    ctx.schema.concrete_subscription_type()
+                        .map(|t| Type::NonNullNamed(t.name().unwrap().to_owned())),
+                _ => None,
             };
 
         ctx.with_pushed_type(def_type.as_ref(), |ctx| {
@@ -39,6 +44,7 @@ fn enter_definition<'a, V: Visitor<'a>>(v: &mut V, ctx: &mut ValidatorContext<'a
     match *def {
         Definition::Operation(ref op) => v.enter_operation_definition(ctx, op),
         Definition::Fragment(ref f) => v.enter_fragment_definition(ctx, f),
+        _ => (),
     }
 }
 
@@ -46,6 +52,7 @@ fn exit_definition<'a, V: Visitor<'a>>(v: &mut V, ctx: &mut ValidatorContext<'a>
     match *def {
         Definition::Operation(ref op) => v.exit_operation_definition(ctx, op),
         Definition::Fragment(ref f) => v.exit_fragment_definition(ctx, f),
+        _ => (),
     }
 }
 
@@ -60,6 +67,7 @@ fn visit_definition<'a, V: Visitor<'a>>(v: &mut V, ctx: &mut ValidatorContext<'a
             visit_directives(v, ctx, &f.item.directives);
             visit_selection_set(v, ctx, &f.item.selection_set);
         },
+        _ => (),
     }
 }
 
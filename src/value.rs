@@ -1,7 +1,9 @@
-use std::collections::HashMap;
-use std::hash::Hash;
+use std::fmt;
+use std::iter::FromIterator;
+use std::slice;
+use std::vec;
 
-use rustc_serialize::json::{ToJson, Json};
+use rustc_serialize::{Encodable, Encoder};
 
 use parser::Spanning;
 use ast::{InputValue, ToInputValue};
@@ -15,7 +17,7 @@ use ast::{InputValue, ToInputValue};
 /// values or variables. Also, lists and objects do not contain any location
 /// information since they are generated by resolving fields and values rather
 /// than parsing a source query.
-#[derive(Debug, PartialEq)]
+#[derive(Clone, Debug, PartialEq)]
 #[allow(missing_docs)]
 pub enum Value {
     Null,
@@ -24,7 +26,25 @@ pub enum Value {
     String(String),
     Boolean(bool),
     List(Vec<Value>),
-    Object(HashMap<String, Value>),
+    Object(Object),
+}
+
+/// Field names and values of an object value, in insertion order
+///
+/// The executor inserts fields in the order they appear in the selection set,
+/// so iterating over or serializing an object follows the order of the query.
+///
+/// Fields are kept in a plain vector and looked up with a linear scan, so
+/// building an object with `n` fields costs `O(n²)` comparisons. Selection
+/// sets are usually small enough for this to be cheaper than maintaining a
+/// separate index.
+///
+/// Values are serialized with `Encodable`, which keeps the field order.
+/// There is no `ToJson` implementation, since `Json` objects sort their
+/// fields by name.
+#[derive(Clone, PartialEq)]
+pub struct Object {
+    key_value_list: Vec<(String, Value)>,
 }
 
 impl Value {
@@ -49,13 +69,7 @@ impl Value {
     pub fn list(l: Vec<Value>) -> Value { Value::List(l) }
 
     /// Construct an object value.
-    pub fn object<K>(o: HashMap<K, Value>) -> Value
-        where K: AsRef<str> + Eq + Hash
-    {
-        Value::Object(
-            o.into_iter().map(|(k, v)| (k.as_ref().to_owned(), v)).collect()
-        )
-    }
+    pub fn object(o: Object) -> Value { Value::Object(o) }
 
     // DISCRIMINATORS
 
@@ -68,7 +82,7 @@ impl Value {
     }
 
     /// View the underlying object value, if present.
-    pub fn as_object_value(&self) -> Option<&HashMap<String, Value>> {
+    pub fn as_object_value(&self) -> Option<&Object> {
         match *self {
             Value::Object(ref o) => Some(o),
             _ => None,
@@ -76,7 +90,7 @@ impl Value {
     }
 
     /// Mutable view into the underlying object value, if present.
-    pub fn as_mut_object_value(&mut self) -> Option<&mut HashMap<String, Value>> {
+    pub fn as_mut_object_value(&mut self) -> Option<&mut Object> {
         match *self {
             Value::Object(ref mut o) => Some(o),
             _ => None,
@@ -100,17 +114,133 @@ impl Value {
     }
 }
 
-impl ToJson for Value {
-    fn to_json(&self) -> Json {
+impl Object {
+    /// Construct an empty object.
+    pub fn new() -> Object {
+        Object { key_value_list: Vec::new() }
+    }
+
+    /// Construct an empty object with room for `capacity` fields.
+    pub fn with_capacity(capacity: usize) -> Object {
+        Object { key_value_list: Vec::with_capacity(capacity) }
+    }
+
+    /// Insert a field at the end of the object.
+    ///
+    /// If the field is already present, its value is replaced in place and the
+    /// old value is returned.
+    pub fn insert<K: AsRef<str>>(&mut self, key: K, value: Value) -> Option<Value> {
+        if let Some(existing) = self.get_mut(key.as_ref()) {
+            return Some(::std::mem::replace(existing, value));
+        }
+
+        self.key_value_list.push((key.as_ref().to_owned(), value));
+        None
+    }
+
+    /// Get the value of a field, if present.
+    pub fn get(&self, key: &str) -> Option<&Value> {
+        self.key_value_list.iter()
+            .find(|&&(ref k, _)| k == key)
+            .map(|&(_, ref v)| v)
+    }
+
+    /// Get a mutable reference to the value of a field, if present.
+    pub fn get_mut(&mut self, key: &str) -> Option<&mut Value> {
+        self.key_value_list.iter_mut()
+            .find(|&&mut (ref k, _)| k == key)
+            .map(|&mut (_, ref mut v)| v)
+    }
+
+    /// Does this object contain the given field?
+    pub fn contains_key(&self, key: &str) -> bool {
+        self.get(key).is_some()
+    }
+
+    /// The number of fields in the object.
+    pub fn len(&self) -> usize {
+        self.key_value_list.len()
+    }
+
+    /// Does this object contain no fields?
+    pub fn is_empty(&self) -> bool {
+        self.key_value_list.is_empty()
+    }
+
+    /// Iterate over the fields in insertion order.
+    pub fn iter(&self) -> slice::Iter<(String, Value)> {
+        self.key_value_list.iter()
+    }
+
+    /// Iterate over the field names in insertion order.
+    pub fn keys(&self) -> Vec<&str> {
+        self.key_value_list.iter().map(|&(ref k, _)| &k[..]).collect()
+    }
+}
+
+impl fmt::Debug for Object {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_map()
+            .entries(self.key_value_list.iter().map(|&(ref k, ref v)| (k, v)))
+            .finish()
+    }
+}
+
+impl<K> FromIterator<(K, Value)> for Object where K: AsRef<str> {
+    fn from_iter<I>(iter: I) -> Object where I: IntoIterator<Item = (K, Value)> {
+        let mut object = Object::new();
+
+        for (k, v) in iter {
+            object.insert(k, v);
+        }
+
+        object
+    }
+}
+
+impl IntoIterator for Object {
+    type Item = (String, Value);
+    type IntoIter = vec::IntoIter<(String, Value)>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.key_value_list.into_iter()
+    }
+}
+
+impl<'a> IntoIterator for &'a Object {
+    type Item = &'a (String, Value);
+    type IntoIter = slice::Iter<'a, (String, Value)>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.key_value_list.iter()
+    }
+}
+
+/// Encode the value as JSON, keeping the field order of objects
+impl Encodable for Value {
+    fn encode<S: Encoder>(&self, s: &mut S) -> Result<(), S::Error> {
         match *self {
-            Value::Null => Json::Null,
-            Value::Int(i) => Json::I64(i),
-            Value::Float(f) => Json::F64(f),
-            Value::String(ref s) => Json::String(s.clone()),
-            Value::Boolean(b) => Json::Boolean(b),
-            Value::List(ref l) => Json::Array(l.iter().map(|x| x.to_json()).collect()),
-            Value::Object(ref o) => Json::Object(o.iter().map(|(k,v)| (k.clone(), v.to_json())).collect()),
-       }
+            Value::Null => s.emit_nil(),
+            Value::Int(i) => s.emit_i64(i),
+            Value::Float(f) => s.emit_f64(f),
+            Value::String(ref v) => s.emit_str(v),
+            Value::Boolean(b) => s.emit_bool(b),
+            Value::List(ref l) => l.encode(s),
+            Value::Object(ref o) => o.encode(s),
+        }
+    }
+}
+
+impl Encodable for Object {
+    fn encode<S: Encoder>(&self, s: &mut S) -> Result<(), S::Error> {
+        s.emit_map(self.len(), |s| {
+            for (i, &(ref k, ref v)) in self.iter().enumerate() {
+                try!(s.emit_map_elt_key(i, |s| s.emit_str(k)));
+                try!(s.emit_map_elt_val(i, |s| v.encode(s)));
+            }
+
+            Ok(())
+        })
     }
 }
 
@@ -124,7 +254,7 @@ impl ToInputValue for Value {
             Value::Boolean(b) => InputValue::Boolean(b),
             Value::List(ref l) => InputValue::List(l.iter().map(|x|
                 Spanning::unlocated(x.to())).collect()),
-            Value::Object(ref o) => InputValue::Object(o.iter().map(|(k,v)|
+            Value::Object(ref o) => InputValue::Object(o.iter().map(|&(ref k, ref v)|
                 (Spanning::unlocated(k.clone()), Spanning::unlocated(v.to()))).collect()),
         }
     }